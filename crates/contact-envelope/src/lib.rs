@@ -0,0 +1,258 @@
+//! Envelope format for end-to-end encrypted contact request messages.
+//!
+//! Profiles publish an X25519 public key (`Profile::encryption_key`). A sender
+//! generates an ephemeral X25519 key pair, derives a symmetric key from the
+//! Diffie-Hellman secret with HKDF-SHA256 and encrypts the message with
+//! XChaCha20-Poly1305. The fields of [`Envelope`] map one-to-one, in order,
+//! onto `profile_manager::state::EncryptedMessage`.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hkdf::Hkdf;
+use rand_core::{OsRng, RngCore};
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, SharedSecret, StaticSecret};
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 24;
+pub const TAG_LEN: usize = 16;
+/// Largest plaintext the on-chain program accepts
+pub const MAX_PLAINTEXT_LEN: usize = 1000;
+pub const MAX_CIPHERTEXT_LEN: usize = MAX_PLAINTEXT_LEN + TAG_LEN;
+
+// Bump on any change to the derivation or layout
+const HKDF_INFO: &[u8] = b"cardpass/contact-envelope/v1";
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum EnvelopeError {
+    #[error("message is longer than {MAX_PLAINTEXT_LEN} bytes")]
+    MessageTooLong,
+    #[error("envelope was sealed to a different key")]
+    WrongRecipient,
+    #[error("ciphertext is malformed or has been tampered with")]
+    DecryptionFailed,
+    #[error("key is a low-order point and yields no shared secret")]
+    WeakKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    pub recipient_key: [u8; KEY_LEN], // Recipient's public key at seal time
+    pub ephemeral_key: [u8; KEY_LEN], // Sender's one-time public key
+    pub nonce: [u8; NONCE_LEN],
+    pub ciphertext: Vec<u8>,          // Plaintext followed by the Poly1305 tag
+}
+
+/// Returns a new `(secret, public)` X25519 key pair for a profile.
+pub fn generate_keypair() -> ([u8; KEY_LEN], [u8; KEY_LEN]) {
+    let secret = StaticSecret::random_from_rng(OsRng);
+    let public = PublicKey::from(&secret);
+    (secret.to_bytes(), public.to_bytes())
+}
+
+/// Returns the public key that belongs to `secret_key`.
+pub fn public_key(secret_key: &[u8; KEY_LEN]) -> [u8; KEY_LEN] {
+    PublicKey::from(&StaticSecret::from(*secret_key)).to_bytes()
+}
+
+/// Encrypts `plaintext` to the holder of `recipient_key`.
+pub fn seal(recipient_key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Envelope, EnvelopeError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    seal_with(recipient_key, plaintext, StaticSecret::random_from_rng(OsRng), nonce)
+}
+
+// `seal` with the randomness supplied by the caller
+fn seal_with(
+    recipient_key: &[u8; KEY_LEN],
+    plaintext: &[u8],
+    ephemeral_secret: StaticSecret,
+    nonce: [u8; NONCE_LEN],
+) -> Result<Envelope, EnvelopeError> {
+    if plaintext.len() > MAX_PLAINTEXT_LEN {
+        return Err(EnvelopeError::MessageTooLong);
+    }
+
+    let ephemeral_key = PublicKey::from(&ephemeral_secret).to_bytes();
+    let shared = contributory(ephemeral_secret.diffie_hellman(&PublicKey::from(*recipient_key)))?;
+
+    let cipher = cipher(shared.as_bytes(), &ephemeral_key, recipient_key);
+    let ciphertext = cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &associated_data(&ephemeral_key, recipient_key),
+            },
+        )
+        .expect("plaintext length is bounded by MAX_PLAINTEXT_LEN");
+
+    Ok(Envelope {
+        recipient_key: *recipient_key,
+        ephemeral_key,
+        nonce,
+        ciphertext,
+    })
+}
+
+/// Decrypts an envelope with the recipient's secret key.
+pub fn open(secret_key: &[u8; KEY_LEN], envelope: &Envelope) -> Result<Vec<u8>, EnvelopeError> {
+    let secret = StaticSecret::from(*secret_key);
+    let recipient_key = PublicKey::from(&secret).to_bytes();
+    if recipient_key != envelope.recipient_key {
+        return Err(EnvelopeError::WrongRecipient);
+    }
+    if envelope.ciphertext.len() < TAG_LEN || envelope.ciphertext.len() > MAX_CIPHERTEXT_LEN {
+        return Err(EnvelopeError::DecryptionFailed);
+    }
+
+    let shared = contributory(secret.diffie_hellman(&PublicKey::from(envelope.ephemeral_key)))?;
+    let cipher = cipher(shared.as_bytes(), &envelope.ephemeral_key, &recipient_key);
+    cipher
+        .decrypt(
+            XNonce::from_slice(&envelope.nonce),
+            Payload {
+                msg: &envelope.ciphertext,
+                aad: &associated_data(&envelope.ephemeral_key, &recipient_key),
+            },
+        )
+        .map_err(|_| EnvelopeError::DecryptionFailed)
+}
+
+// A low-order public key forces the all-zero shared secret, which would let
+// anyone derive the message key
+fn contributory(shared: SharedSecret) -> Result<SharedSecret, EnvelopeError> {
+    if shared.was_contributory() {
+        Ok(shared)
+    } else {
+        Err(EnvelopeError::WeakKey)
+    }
+}
+
+// Both public keys go into the salt and the AAD, binding the ciphertext to
+// this exact sender/recipient pair
+fn associated_data(ephemeral_key: &[u8; KEY_LEN], recipient_key: &[u8; KEY_LEN]) -> [u8; 2 * KEY_LEN] {
+    let mut data = [0u8; 2 * KEY_LEN];
+    data[..KEY_LEN].copy_from_slice(ephemeral_key);
+    data[KEY_LEN..].copy_from_slice(recipient_key);
+    data
+}
+
+fn cipher(shared_secret: &[u8; KEY_LEN], ephemeral_key: &[u8; KEY_LEN], recipient_key: &[u8; KEY_LEN]) -> XChaCha20Poly1305 {
+    let salt = associated_data(ephemeral_key, recipient_key);
+    let mut key = [0u8; KEY_LEN];
+    Hkdf::<Sha256>::new(Some(&salt), shared_secret)
+        .expand(HKDF_INFO, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    XChaCha20Poly1305::new(&key.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn unhex<const N: usize>(s: &str) -> [u8; N] {
+        let bytes: Vec<u8> = (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect();
+        bytes.try_into().unwrap()
+    }
+
+    fn sealed(plaintext: &[u8]) -> ([u8; KEY_LEN], Envelope) {
+        let (secret, public) = generate_keypair();
+        (secret, seal(&public, plaintext).unwrap())
+    }
+
+    #[test]
+    fn seal_then_open_roundtrips() {
+        for plaintext in [&b""[..], b"hello", &[7u8; MAX_PLAINTEXT_LEN]] {
+            let (secret, envelope) = sealed(plaintext);
+            assert_eq!(envelope.ciphertext.len(), plaintext.len() + TAG_LEN);
+            assert_eq!(open(&secret, &envelope).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_message() {
+        let (_, public) = generate_keypair();
+        assert_eq!(
+            seal(&public, &[0u8; MAX_PLAINTEXT_LEN + 1]).unwrap_err(),
+            EnvelopeError::MessageTooLong
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_recipient_key() {
+        let (_, envelope) = sealed(b"hello");
+        let (other_secret, _) = generate_keypair();
+        assert_eq!(open(&other_secret, &envelope).unwrap_err(), EnvelopeError::WrongRecipient);
+    }
+
+    #[test]
+    fn rejects_tampering() {
+        let (secret, envelope) = sealed(b"hello");
+
+        let mut tampered = envelope.clone();
+        tampered.ciphertext[0] ^= 1;
+        assert_eq!(open(&secret, &tampered).unwrap_err(), EnvelopeError::DecryptionFailed);
+
+        let mut tampered = envelope.clone();
+        *tampered.ciphertext.last_mut().unwrap() ^= 1;
+        assert_eq!(open(&secret, &tampered).unwrap_err(), EnvelopeError::DecryptionFailed);
+
+        let mut tampered = envelope.clone();
+        tampered.ciphertext.truncate(TAG_LEN - 1);
+        assert_eq!(open(&secret, &tampered).unwrap_err(), EnvelopeError::DecryptionFailed);
+
+        let mut tampered = envelope.clone();
+        tampered.nonce[0] ^= 1;
+        assert_eq!(open(&secret, &tampered).unwrap_err(), EnvelopeError::DecryptionFailed);
+
+        let mut tampered = envelope.clone();
+        tampered.ephemeral_key = generate_keypair().1;
+        assert_eq!(open(&secret, &tampered).unwrap_err(), EnvelopeError::DecryptionFailed);
+    }
+
+    #[test]
+    fn rejects_low_order_keys() {
+        // The identity point: every scalar maps it to the all-zero secret
+        let low_order = [0u8; KEY_LEN];
+        assert_eq!(seal(&low_order, b"hello").unwrap_err(), EnvelopeError::WeakKey);
+
+        let (secret, mut envelope) = sealed(b"hello");
+        envelope.ephemeral_key = low_order;
+        assert_eq!(open(&secret, &envelope).unwrap_err(), EnvelopeError::WeakKey);
+    }
+
+    // X25519 keys from RFC 7748 section 6.1; Alice sends, Bob receives
+    const ALICE_SECRET: &str = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+    const ALICE_PUBLIC: &str = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+    const BOB_SECRET: &str = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+    const BOB_PUBLIC: &str = "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f";
+    const NONCE: &str = "000102030405060708090a0b0c0d0e0f1011121314151617";
+    // Recorded from the v1 format; a change here breaks existing envelopes
+    const CIPHERTEXT: &str =
+        "1337c3ac9bd0981a447c3c101b722575d11b919613a4b8fe7a42f38d68879d756200c6263b22531953ca2b76b5110c";
+
+    #[test]
+    fn known_answer() {
+        let bob_secret = unhex::<KEY_LEN>(BOB_SECRET);
+        assert_eq!(hex(&public_key(&bob_secret)), BOB_PUBLIC);
+
+        let envelope = seal_with(
+            &unhex(BOB_PUBLIC),
+            b"Hi Bob, are you open to a call?",
+            StaticSecret::from(unhex::<KEY_LEN>(ALICE_SECRET)),
+            unhex(NONCE),
+        )
+        .unwrap();
+        assert_eq!(hex(&envelope.ephemeral_key), ALICE_PUBLIC);
+        assert_eq!(hex(&envelope.ciphertext), CIPHERTEXT);
+        assert_eq!(open(&bob_secret, &envelope).unwrap(), b"Hi Bob, are you open to a call?");
+    }
+}
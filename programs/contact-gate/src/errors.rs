@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ContactGateError {
+    #[msg("Inbox needs between one and five tiers")]
+    InvalidTierCount,
+    #[msg("Tiers need a price, a 1-168 hour response window and a label of at most 32 bytes")]
+    InvalidTier,
+    #[msg("Invalid tier index")]
+    InvalidTierIndex,
+    #[msg("Inbox is not accepting requests")]
+    InboxClosed,
+    #[msg("Inbox has too many pending requests")]
+    TooManyPendingRequests,
+    #[msg("This inbox has blocked the sender")]
+    SenderBlocked,
+    #[msg("Inbox policy list is full")]
+    PolicyFull,
+    #[msg("Request is no longer pending")]
+    RequestNotPending,
+    #[msg("Request has expired")]
+    RequestExpired,
+    #[msg("Request has not expired yet")]
+    RequestNotExpired,
+    #[msg("Request is still pending")]
+    RequestNotResolved,
+    #[msg("Request was not accepted")]
+    RequestNotAccepted,
+    #[msg("Request has already been redeemed")]
+    AlreadyRedeemed,
+    #[msg("Only the requester can do this")]
+    NotRequester,
+    #[msg("Auctions need 1-10 slots, a minimum bid and a 1-168 hour bidding window")]
+    InvalidAuctionConfig,
+    #[msg("Auction is no longer taking bids")]
+    BiddingClosed,
+    #[msg("Auction bidding is still open")]
+    BiddingStillOpen,
+    #[msg("Bid is below the minimum or does not outrank the lowest winning bid")]
+    BidTooLow,
+    #[msg("Invalid settlement accounts")]
+    InvalidSettlementAccounts,
+    #[msg("Bid has not been settled yet")]
+    BidNotSettled,
+}
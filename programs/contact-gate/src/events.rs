@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::state::{AuctionLeader, BidStatus, RequestStatus};
+
+#[event]
+pub struct InboxCreated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub tier_count: u8,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct InboxUpdated {
+    pub inbox: Pubkey,
+    pub tier_count: u8,
+    pub is_open: bool,
+    pub max_pending: u16,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct InboxPolicyUpdated {
+    pub inbox: Pubkey,
+    pub blocked_count: u16,
+    pub allowed_count: u16,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct InboxRequestSent {
+    pub request: Pubkey,
+    pub inbox: Pubkey,
+    pub requester: Pubkey,
+    pub nonce: u64,
+    pub tier_index: u8,
+    pub amount: u64,                   // Escrowed, net of transfer fees; 0 if allowlisted
+    pub memo_hash: [u8; 32],
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct InboxRequestResolved {
+    pub request: Pubkey,
+    pub inbox: Pubkey,
+    pub requester: Pubkey,
+    pub status: RequestStatus,
+    pub paid: u64,                     // To the inbox owner
+    pub refunded: u64,                 // To the requester
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct InboxRequestRedeemed {
+    pub request: Pubkey,
+    pub inbox: Pubkey,
+    pub requester: Pubkey,
+    pub redeemed_at: i64,
+}
+
+#[event]
+pub struct InboxRequestClosed {
+    pub request: Pubkey,
+    pub inbox: Pubkey,
+    pub requester: Pubkey,
+    pub status: RequestStatus,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct AuctionCreated {
+    pub auction: Pubkey,
+    pub inbox: Pubkey,
+    pub auction_id: u64,
+    pub slots: u8,
+    pub min_bid: u64,
+    pub bidding_ends_at: i64,
+}
+
+#[event]
+pub struct AuctionBidPlaced {
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub bidder: Pubkey,
+    pub added: u64,                    // Escrowed by this call
+    pub amount: u64,                   // Bid total after this call
+    pub sequence: u64,
+    pub memo_hash: [u8; 32],
+    pub placed_at: i64,
+}
+
+#[event]
+pub struct AuctionSettled {
+    pub auction: Pubkey,
+    pub inbox: Pubkey,
+    pub winners: Vec<AuctionLeader>,   // Best first
+    pub clearing_price: u64,
+    pub bid_count: u32,
+    pub settled_at: i64,
+}
+
+#[event]
+pub struct AuctionBidSettled {
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub won: bool,                     // Paid to the owner if true, else refunded
+    pub settled_at: i64,
+}
+
+#[event]
+pub struct AuctionBidClosed {
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub bidder: Pubkey,
+    pub status: BidStatus,
+    pub closed_at: i64,
+}
@@ -0,0 +1,390 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::escrow::{close_bid_escrow, fund_escrow, release_bid_escrow};
+
+/// Accounts per bid in `remaining_accounts` for `settle_auction`, in this
+/// order: bid, bid_escrow, bidder_token_account. Winning bids are paid to
+/// the owner's token account instead, but still pass the bidder's.
+pub const SETTLE_ACCOUNTS_PER_BID: usize = 3;
+
+/// Opens an auction for `slots` responses, taking bids for
+/// `bidding_window_hours`.
+pub fn create_auction(
+    ctx: Context<CreateAuction>,
+    slots: u8,
+    min_bid: u64,
+    bidding_window_hours: u16,
+) -> Result<()> {
+    require!(
+        slots > 0
+            && slots as usize <= MAX_AUCTION_SLOTS
+            && min_bid > 0
+            && bidding_window_hours > 0
+            && bidding_window_hours <= MAX_BIDDING_WINDOW_HOURS,
+        ContactGateError::InvalidAuctionConfig
+    );
+    require!(ctx.accounts.inbox.is_open, ContactGateError::InboxClosed);
+    let clock = Clock::get()?;
+
+    let inbox = &mut ctx.accounts.inbox;
+    let auction_id = inbox.auction_count;
+    inbox.auction_count += 1;
+
+    let auction = &mut ctx.accounts.auction;
+    auction.inbox = inbox.key();
+    auction.owner = inbox.owner;
+    auction.mint = inbox.mint;
+    auction.auction_id = auction_id;
+    auction.slots = slots;
+    auction.min_bid = min_bid;
+    auction.leaders = Vec::new();
+    auction.bid_count = 0;
+    auction.bid_sequence = 0;
+    auction.settled_count = 0;
+    auction.status = AuctionStatus::Open;
+    auction.clearing_price = 0;
+    auction.total_paid = 0;
+    auction.created_at = clock.unix_timestamp;
+    auction.bidding_ends_at = clock.unix_timestamp + bidding_window_hours as i64 * 3600;
+    auction.settled_at = 0;
+    auction.bump = ctx.bumps.auction;
+
+    emit!(AuctionCreated {
+        auction: auction.key(),
+        inbox: auction.inbox,
+        auction_id,
+        slots,
+        min_bid,
+        bidding_ends_at: auction.bidding_ends_at,
+    });
+
+    Ok(())
+}
+
+/// Places a bid, or raises the bidder's existing one by `amount`.
+pub fn place_bid(ctx: Context<PlaceBid>, amount: u64, memo_hash: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+    let auction = &ctx.accounts.auction;
+    let bidder = ctx.accounts.bidder.key();
+
+    require!(auction.status == AuctionStatus::Open, ContactGateError::BiddingClosed);
+    require!(clock.unix_timestamp < auction.bidding_ends_at, ContactGateError::BiddingClosed);
+    require!(ctx.accounts.inbox.is_open, ContactGateError::InboxClosed);
+    require!(!ctx.accounts.inbox.is_blocked(&bidder), ContactGateError::SenderBlocked);
+
+    let added = fund_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.bidder_token_account,
+        &mut ctx.accounts.bid_escrow,
+        &ctx.accounts.bidder.to_account_info(),
+        amount,
+    )?;
+
+    let auction = &mut ctx.accounts.auction;
+    let bid = &mut ctx.accounts.bid;
+    if bid.auction == Pubkey::default() {
+        bid.auction = auction.key();
+        bid.bidder = bidder;
+        bid.status = BidStatus::Active;
+        bid.bump = ctx.bumps.bid;
+        auction.bid_count += 1;
+    }
+    bid.amount += added;
+    require!(bid.amount >= auction.min_bid, ContactGateError::BidTooLow);
+    bid.sequence = auction.bid_sequence;
+    bid.memo_hash = memo_hash;
+    bid.placed_at = clock.unix_timestamp;
+    auction.bid_sequence += 1;
+
+    auction.place(AuctionLeader {
+        bid: bid.key(),
+        amount: bid.amount,
+        sequence: bid.sequence,
+    })?;
+
+    emit!(AuctionBidPlaced {
+        auction: auction.key(),
+        bid: bid.key(),
+        bidder,
+        added,
+        amount: bid.amount,
+        sequence: bid.sequence,
+        memo_hash,
+        placed_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Settles an auction whose bidding has ended. Callable by anyone, in as
+/// many batches as needed: the first call fixes the winners (the leaderboard
+/// as it stood when bidding closed), and every call pays out or refunds the
+/// bids passed in `remaining_accounts`. Already-settled bids are skipped.
+pub fn settle_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>,
+) -> Result<()> {
+    let groups = ctx.remaining_accounts.chunks_exact(SETTLE_ACCOUNTS_PER_BID);
+    require!(groups.remainder().is_empty(), ContactGateError::InvalidSettlementAccounts);
+
+    let clock = Clock::get()?;
+    let auction = &mut ctx.accounts.auction;
+
+    if auction.status == AuctionStatus::Open {
+        require!(clock.unix_timestamp >= auction.bidding_ends_at, ContactGateError::BiddingStillOpen);
+        auction.status = AuctionStatus::Settled;
+        auction.clearing_price = auction.leaders.last().map_or(0, |leader| leader.amount);
+        auction.settled_at = clock.unix_timestamp;
+
+        emit!(AuctionSettled {
+            auction: auction.key(),
+            inbox: auction.inbox,
+            winners: auction.leaders.clone(),
+            clearing_price: auction.clearing_price,
+            bid_count: auction.bid_count,
+            settled_at: clock.unix_timestamp,
+        });
+    }
+
+    for group in groups {
+        if let Some(paid) = settle_bid(ctx.accounts, group, clock.unix_timestamp)? {
+            let auction = &mut ctx.accounts.auction;
+            auction.settled_count += 1;
+            auction.total_paid += paid;
+        }
+    }
+
+    Ok(())
+}
+
+// Returns what was paid to the owner (0 for a refund), or None if the bid
+// was already settled
+fn settle_bid<'info>(
+    accounts: &SettleAuction<'info>,
+    group: &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<Option<u64>> {
+    let auction = &accounts.auction;
+    let [bid_info, escrow_info, bidder_token_info] = group else {
+        return err!(ContactGateError::InvalidSettlementAccounts);
+    };
+
+    let mut bid = Account::<AuctionBid>::try_from(bid_info)?;
+    require_keys_eq!(bid.auction, auction.key(), ContactGateError::InvalidSettlementAccounts);
+    if bid.status != BidStatus::Active {
+        return Ok(None);
+    }
+
+    let (escrow_address, _) = Pubkey::find_program_address(&[b"bid_escrow", bid_info.key.as_ref()], &crate::ID);
+    require_keys_eq!(escrow_address, escrow_info.key(), ContactGateError::InvalidSettlementAccounts);
+    require_keys_eq!(*escrow_info.owner, accounts.token_program.key(), ContactGateError::InvalidSettlementAccounts);
+    let escrow = InterfaceAccount::<TokenAccount>::try_from(escrow_info)?;
+
+    let bidder_token_account = InterfaceAccount::<TokenAccount>::try_from(bidder_token_info)?;
+    require!(
+        bidder_token_account.mint == auction.mint && bidder_token_account.owner == bid.bidder,
+        ContactGateError::InvalidSettlementAccounts
+    );
+
+    let won = auction.is_winner(&bid.key());
+    let to = if won { &accounts.owner_token_account } else { &bidder_token_account };
+    release_bid_escrow(
+        &accounts.token_program.to_account_info(),
+        &accounts.mint,
+        &bid,
+        &escrow,
+        to,
+        bid.amount,
+    )?;
+
+    bid.status = if won { BidStatus::Won } else { BidStatus::Refunded };
+    bid.exit(&crate::ID)?;
+
+    emit!(AuctionBidSettled {
+        auction: auction.key(),
+        bid: bid.key(),
+        bidder: bid.bidder,
+        amount: bid.amount,
+        won,
+        settled_at: now,
+    });
+
+    Ok(Some(if won { bid.amount } else { 0 }))
+}
+
+/// Closes a settled bid and its escrow, returning both rents to the bidder.
+pub fn close_bid(ctx: Context<CloseBid>) -> Result<()> {
+    let bid = &ctx.accounts.bid;
+    require!(bid.status != BidStatus::Active, ContactGateError::BidNotSettled);
+
+    // Stray deposits go back to the bidder so they cannot block the close
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let leftover = ctx.accounts.bid_escrow.amount;
+    release_bid_escrow(
+        &token_program,
+        &ctx.accounts.mint,
+        bid,
+        &ctx.accounts.bid_escrow,
+        &ctx.accounts.bidder_token_account,
+        leftover,
+    )?;
+    close_bid_escrow(
+        &token_program,
+        bid,
+        &ctx.accounts.bid_escrow,
+        &ctx.accounts.bidder.to_account_info(),
+    )?;
+
+    emit!(AuctionBidClosed {
+        auction: bid.auction,
+        bid: bid.key(),
+        bidder: bid.bidder,
+        status: bid.status,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    // The bid account itself is closed by the `close` constraint
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AttentionAuction::INIT_SPACE,
+        seeds = [b"auction", inbox.key().as_ref(), &inbox.auction_count.to_le_bytes()],
+        bump
+    )]
+    pub auction: Account<'info, AttentionAuction>,
+
+    #[account(
+        mut,
+        seeds = [b"inbox", owner.key().as_ref()],
+        bump = inbox.bump,
+        has_one = owner
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuctionBid::INIT_SPACE,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, AuctionBid>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", inbox.key().as_ref(), &auction.auction_id.to_le_bytes()],
+        bump = auction.bump,
+        has_one = inbox,
+        has_one = mint
+    )]
+    pub auction: Account<'info, AttentionAuction>,
+
+    pub inbox: Account<'info, Inbox>,
+
+    /// Wallet or calling program's PDA
+    pub bidder: Signer<'info>,
+
+    /// Pays rent, so PDA bidders need no lamports of their own
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bidder,
+        token::token_program = token_program
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = mint,
+        token::authority = bid,
+        token::token_program = token_program,
+        seeds = [b"bid_escrow", bid.key().as_ref()],
+        bump
+    )]
+    pub bid_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.inbox.as_ref(), &auction.auction_id.to_le_bytes()],
+        bump = auction.bump,
+        has_one = mint
+    )]
+    pub auction: Account<'info, AttentionAuction>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = auction.owner
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Shared by every escrow in the batch
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"bid", bid.auction.as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        has_one = bidder,
+        close = bidder
+    )]
+    pub bid: Account<'info, AuctionBid>,
+
+    /// Receives both rents
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bidder
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"bid_escrow", bid.key().as_ref()],
+        bump
+    )]
+    pub bid_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = bid_escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
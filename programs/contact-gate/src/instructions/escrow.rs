@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TransferChecked};
+use crate::state::{AuctionBid, InboxRequest};
+
+// Every request escrows into its own token account at [b"escrow", request],
+// owned by the request PDA, so only instructions that load the request can
+// move its funds. Auction bids do the same at [b"bid_escrow", bid].
+
+/// Moves the requester's deposit into the escrow and returns what the escrow
+/// actually received, net of any transfer fee.
+pub(crate) fn fund_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    escrow: &mut InterfaceAccount<'info, TokenAccount>,
+    requester: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<u64> {
+    let balance_before = escrow.amount;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: escrow.to_account_info(),
+                authority: requester.clone(),
+            },
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    escrow.reload()?;
+    Ok(escrow.amount - balance_before)
+}
+
+/// Pays `amount` out of the escrow, signed by the request PDA.
+pub(crate) fn release_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    request: &Account<'info, InboxRequest>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let nonce = request.nonce.to_le_bytes();
+    let seeds = &[b"request".as_ref(), request.inbox.as_ref(), nonce.as_ref(), &[request.bump]];
+    transfer_signed(token_program, mint, escrow, to, &request.to_account_info(), seeds, amount)
+}
+
+/// Closes the emptied escrow, returning its rent to `destination`.
+pub(crate) fn close_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    request: &Account<'info, InboxRequest>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let nonce = request.nonce.to_le_bytes();
+    let seeds = &[b"request".as_ref(), request.inbox.as_ref(), nonce.as_ref(), &[request.bump]];
+    close_signed(token_program, escrow, destination, &request.to_account_info(), seeds)
+}
+
+/// Pays `amount` out of a bid escrow, signed by the bid PDA.
+pub(crate) fn release_bid_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    bid: &Account<'info, AuctionBid>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[b"bid".as_ref(), bid.auction.as_ref(), bid.bidder.as_ref(), &[bid.bump]];
+    transfer_signed(token_program, mint, escrow, to, &bid.to_account_info(), seeds, amount)
+}
+
+/// Closes an emptied bid escrow, returning its rent to `destination`.
+pub(crate) fn close_bid_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    bid: &Account<'info, AuctionBid>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let seeds = &[b"bid".as_ref(), bid.auction.as_ref(), bid.bidder.as_ref(), &[bid.bump]];
+    close_signed(token_program, escrow, destination, &bid.to_account_info(), seeds)
+}
+
+fn transfer_signed<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    // Allowlisted requests and empty sweeps have nothing to move
+    if amount == 0 {
+        return Ok(());
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked {
+                from: escrow.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: authority.clone(),
+            },
+            &[seeds],
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+fn close_signed<'info>(
+    token_program: &AccountInfo<'info>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: destination.clone(),
+            authority: authority.clone(),
+        },
+        &[seeds],
+    ))
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::events::*;
+
+pub fn create_inbox(
+    ctx: Context<CreateInbox>,
+    tiers: Vec<InboxTier>,
+    max_pending: u16,
+) -> Result<()> {
+    Inbox::validate_tiers(&tiers)?;
+    let clock = Clock::get()?;
+
+    let inbox = &mut ctx.accounts.inbox;
+    inbox.owner = ctx.accounts.owner.key();
+    inbox.mint = ctx.accounts.mint.key();
+    inbox.tiers = tiers;
+    inbox.blocked = Vec::new();
+    inbox.allowed = Vec::new();
+    inbox.is_open = true;
+    inbox.max_pending = max_pending;
+    inbox.pending_count = 0;
+    inbox.request_count = 0;
+    inbox.auction_count = 0;
+    inbox.accepted_count = 0;
+    inbox.rejected_count = 0;
+    inbox.expired_count = 0;
+    inbox.cancelled_count = 0;
+    inbox.created_at = clock.unix_timestamp;
+    inbox.bump = ctx.bumps.inbox;
+
+    emit!(InboxCreated {
+        inbox: inbox.key(),
+        owner: inbox.owner,
+        mint: inbox.mint,
+        tier_count: inbox.tiers.len() as u8,
+        created_at: inbox.created_at,
+    });
+
+    Ok(())
+}
+
+/// Changes take effect for new requests only; pending requests keep the
+/// price and window they were sent with.
+pub fn update_inbox(
+    ctx: Context<UpdateInbox>,
+    tiers: Option<Vec<InboxTier>>,
+    is_open: Option<bool>,
+    max_pending: Option<u16>,
+) -> Result<()> {
+    let inbox = &mut ctx.accounts.inbox;
+
+    if let Some(tiers) = tiers {
+        Inbox::validate_tiers(&tiers)?;
+        inbox.tiers = tiers;
+    }
+    if let Some(is_open) = is_open {
+        inbox.is_open = is_open;
+    }
+    if let Some(max_pending) = max_pending {
+        inbox.max_pending = max_pending;
+    }
+
+    emit!(InboxUpdated {
+        inbox: inbox.key(),
+        tier_count: inbox.tiers.len() as u8,
+        is_open: inbox.is_open,
+        max_pending: inbox.max_pending,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn update_inbox_policy(
+    ctx: Context<UpdateInbox>,
+    updates: Vec<InboxPolicyUpdate>,
+) -> Result<()> {
+    let inbox = &mut ctx.accounts.inbox;
+    for update in &updates {
+        inbox.apply_policy(update)?;
+    }
+
+    emit!(InboxPolicyUpdated {
+        inbox: inbox.key(),
+        blocked_count: inbox.blocked.len() as u16,
+        allowed_count: inbox.allowed.len() as u16,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateInbox<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Inbox::INIT_SPACE,
+        seeds = [b"inbox", owner.key().as_ref()],
+        bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    /// Wallet or program PDA that will own the inbox
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateInbox<'info> {
+    #[account(
+        mut,
+        seeds = [b"inbox", owner.key().as_ref()],
+        bump = inbox.bump,
+        has_one = owner
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,8 @@
+pub mod inbox;
+pub mod request;
+pub mod auction;
+pub mod escrow;
+
+pub use inbox::*;
+pub use request::*;
+pub use auction::*;
@@ -0,0 +1,418 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::escrow::{close_escrow, fund_escrow, release_escrow};
+
+// See `InboxRequest` for the lifecycle.
+
+pub fn send_request(
+    ctx: Context<SendRequest>,
+    tier_index: u8,
+    memo_hash: [u8; 32], // Hash of the off-chain message
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let inbox = &ctx.accounts.inbox;
+    let requester = ctx.accounts.requester.key();
+
+    require!(inbox.is_open, ContactGateError::InboxClosed);
+    require!(
+        inbox.max_pending == 0 || inbox.pending_count < inbox.max_pending,
+        ContactGateError::TooManyPendingRequests
+    );
+    require!(!inbox.is_blocked(&requester), ContactGateError::SenderBlocked);
+    let tier = inbox
+        .tiers
+        .get(tier_index as usize)
+        .ok_or(ContactGateError::InvalidTierIndex)?;
+    let price = tier.price;
+    let response_window_hours = tier.response_window_hours;
+
+    // Allowlisted senders still get a tracked request, with nothing escrowed
+    let amount = if inbox.is_allowed(&requester) {
+        0
+    } else {
+        fund_escrow(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint,
+            &ctx.accounts.requester_token_account,
+            &mut ctx.accounts.escrow_token_account,
+            &ctx.accounts.requester.to_account_info(),
+            price,
+        )?
+    };
+
+    let inbox = &mut ctx.accounts.inbox;
+    let nonce = inbox.request_count;
+    inbox.request_count += 1;
+    inbox.pending_count += 1;
+
+    let request = &mut ctx.accounts.request;
+    request.inbox = inbox.key();
+    request.requester = requester;
+    request.nonce = nonce;
+    request.tier_index = tier_index;
+    request.amount = amount;
+    request.memo_hash = memo_hash;
+    request.created_at = clock.unix_timestamp;
+    request.expires_at = clock.unix_timestamp + response_window_hours as i64 * 3600;
+    request.resolved_at = 0;
+    request.status = RequestStatus::Pending;
+    request.redeemed = false;
+    request.bump = ctx.bumps.request;
+
+    emit!(InboxRequestSent {
+        request: request.key(),
+        inbox: request.inbox,
+        requester,
+        nonce,
+        tier_index,
+        amount,
+        memo_hash,
+        expires_at: request.expires_at,
+    });
+
+    Ok(())
+}
+
+pub fn respond_to_request(ctx: Context<RespondToRequest>, accept: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let request = &ctx.accounts.request;
+
+    require!(request.is_pending(), ContactGateError::RequestNotPending);
+    require!(!request.is_expired(clock.unix_timestamp), ContactGateError::RequestExpired);
+
+    let to = if accept {
+        &ctx.accounts.owner_token_account
+    } else {
+        &ctx.accounts.requester_token_account
+    };
+    release_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        request,
+        &ctx.accounts.escrow_token_account,
+        to,
+        request.amount,
+    )?;
+
+    let status = if accept { RequestStatus::Accepted } else { RequestStatus::Rejected };
+    let (paid, refunded) = if accept { (request.amount, 0) } else { (0, request.amount) };
+    resolve(&mut ctx.accounts.inbox, &mut ctx.accounts.request, status, paid, refunded, clock.unix_timestamp);
+
+    Ok(())
+}
+
+/// Withdraws a pending request for a full refund.
+pub fn cancel_request(ctx: Context<SettleRequest>) -> Result<()> {
+    let clock = Clock::get()?;
+    let request = &ctx.accounts.request;
+
+    require!(request.is_pending(), ContactGateError::RequestNotPending);
+    require_keys_eq!(ctx.accounts.caller.key(), request.requester, ContactGateError::NotRequester);
+
+    refund(&ctx)?;
+    let amount = ctx.accounts.request.amount;
+    resolve(&mut ctx.accounts.inbox, &mut ctx.accounts.request, RequestStatus::Cancelled, 0, amount, clock.unix_timestamp);
+
+    Ok(())
+}
+
+/// Refunds a request the owner did not answer in time. Callable by anyone.
+pub fn expire_request(ctx: Context<SettleRequest>) -> Result<()> {
+    let clock = Clock::get()?;
+    let request = &ctx.accounts.request;
+
+    require!(request.is_pending(), ContactGateError::RequestNotPending);
+    require!(request.is_expired(clock.unix_timestamp), ContactGateError::RequestNotExpired);
+
+    refund(&ctx)?;
+    let amount = ctx.accounts.request.amount;
+    resolve(&mut ctx.accounts.inbox, &mut ctx.accounts.request, RequestStatus::Expired, 0, amount, clock.unix_timestamp);
+
+    Ok(())
+}
+
+/// Consumes an accepted request, for gates that admit one interaction per
+/// paid contact. The requester signs, so calling programs redeem via CPI
+/// with their own PDA as requester.
+pub fn redeem_request(ctx: Context<RedeemRequest>) -> Result<()> {
+    let request = &mut ctx.accounts.request;
+
+    request.redeem()?;
+
+    emit!(InboxRequestRedeemed {
+        request: request.key(),
+        inbox: request.inbox,
+        requester: request.requester,
+        redeemed_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Closes a resolved request and its escrow, returning both rents to the
+/// requester. Accepted requests that gate something should be redeemed or
+/// no longer needed first, since readers lose the record.
+pub fn close_request(ctx: Context<CloseRequest>) -> Result<()> {
+    let request = &ctx.accounts.request;
+    require!(!request.is_pending(), ContactGateError::RequestNotResolved);
+
+    // Stray deposits go back to the requester so they cannot block the close
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let leftover = ctx.accounts.escrow_token_account.amount;
+    release_escrow(
+        &token_program,
+        &ctx.accounts.mint,
+        request,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.requester_token_account,
+        leftover,
+    )?;
+    close_escrow(
+        &token_program,
+        request,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.requester.to_account_info(),
+    )?;
+
+    emit!(InboxRequestClosed {
+        request: request.key(),
+        inbox: request.inbox,
+        requester: request.requester,
+        status: request.status,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    // The request account itself is closed by the `close` constraint
+    Ok(())
+}
+
+fn refund(ctx: &Context<SettleRequest>) -> Result<()> {
+    release_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.request,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.requester_token_account,
+        ctx.accounts.request.amount,
+    )
+}
+
+fn resolve(
+    inbox: &mut Inbox,
+    request: &mut Account<InboxRequest>,
+    status: RequestStatus,
+    paid: u64,
+    refunded: u64,
+    now: i64,
+) {
+    request.status = status;
+    request.resolved_at = now;
+
+    inbox.pending_count = inbox.pending_count.saturating_sub(1);
+    match status {
+        RequestStatus::Accepted => inbox.accepted_count += 1,
+        RequestStatus::Rejected => inbox.rejected_count += 1,
+        RequestStatus::Expired => inbox.expired_count += 1,
+        RequestStatus::Cancelled => inbox.cancelled_count += 1,
+        RequestStatus::Pending => {}
+    }
+
+    emit!(InboxRequestResolved {
+        request: request.key(),
+        inbox: request.inbox,
+        requester: request.requester,
+        status,
+        paid,
+        refunded,
+        resolved_at: now,
+    });
+}
+
+#[derive(Accounts)]
+pub struct SendRequest<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + InboxRequest::INIT_SPACE,
+        seeds = [b"request", inbox.key().as_ref(), &inbox.request_count.to_le_bytes()],
+        bump
+    )]
+    pub request: Account<'info, InboxRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"inbox", inbox.owner.as_ref()],
+        bump = inbox.bump,
+        has_one = mint
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    /// Wallet or calling program's PDA
+    pub requester: Signer<'info>,
+
+    /// Pays rent, so PDA requesters need no lamports of their own
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = requester,
+        token::token_program = token_program
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = request,
+        token::token_program = token_program,
+        seeds = [b"escrow", request.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RespondToRequest<'info> {
+    #[account(
+        mut,
+        seeds = [b"request", inbox.key().as_ref(), &request.nonce.to_le_bytes()],
+        bump = request.bump,
+        has_one = inbox
+    )]
+    pub request: Account<'info, InboxRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"inbox", owner.key().as_ref()],
+        bump = inbox.bump,
+        has_one = owner,
+        has_one = mint
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = request.requester
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", request.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Shared by `cancel_request` (requester only) and `expire_request` (anyone)
+#[derive(Accounts)]
+pub struct SettleRequest<'info> {
+    #[account(
+        mut,
+        seeds = [b"request", inbox.key().as_ref(), &request.nonce.to_le_bytes()],
+        bump = request.bump,
+        has_one = inbox
+    )]
+    pub request: Account<'info, InboxRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"inbox", inbox.owner.as_ref()],
+        bump = inbox.bump,
+        has_one = mint
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = request.requester
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", request.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemRequest<'info> {
+    #[account(
+        mut,
+        seeds = [b"request", request.inbox.as_ref(), &request.nonce.to_le_bytes()],
+        bump = request.bump,
+        has_one = requester
+    )]
+    pub request: Account<'info, InboxRequest>,
+
+    pub requester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRequest<'info> {
+    #[account(
+        mut,
+        seeds = [b"request", request.inbox.as_ref(), &request.nonce.to_le_bytes()],
+        bump = request.bump,
+        has_one = requester,
+        close = requester
+    )]
+    pub request: Account<'info, InboxRequest>,
+
+    /// Receives both rents
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = requester
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", request.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,15 +1,90 @@
+//! Generic paid inbox for any pubkey.
+//!
+//! An inbox owner prices contact in tiers; requesters escrow the tier price,
+//! and the owner accepts (paid out) or rejects (refunded) before the tier's
+//! window closes. Other programs build with the `cpi` feature to send or
+//! answer requests with their own PDAs, and gate their instructions on an
+//! accepted request via `InboxRequest::grants_access` or `redeem_request`.
+//!
+//! Owners can also auction a fixed number of responses: the top bids when
+//! bidding closes win (`AuctionBid::grants_access`) and the rest are refunded
+//! at settlement.
+
 use anchor_lang::prelude::*;
 
+pub mod instructions;
+pub mod state;
+pub mod errors;
+pub mod events;
+
+use instructions::*;
+use state::*;
+
 declare_id!("Dp7V95LBVp5Y6YVzYTfgrD2PKgXCgYXqw69QT4DqZoP6");
 
 #[program]
 pub mod contact_gate {
     use super::*;
 
-    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
-        Ok(())
+    pub fn create_inbox(ctx: Context<CreateInbox>, tiers: Vec<InboxTier>, max_pending: u16) -> Result<()> {
+        instructions::inbox::create_inbox(ctx, tiers, max_pending)
+    }
+
+    pub fn update_inbox(
+        ctx: Context<UpdateInbox>,
+        tiers: Option<Vec<InboxTier>>,
+        is_open: Option<bool>,
+        max_pending: Option<u16>,
+    ) -> Result<()> {
+        instructions::inbox::update_inbox(ctx, tiers, is_open, max_pending)
+    }
+
+    pub fn update_inbox_policy(ctx: Context<UpdateInbox>, updates: Vec<InboxPolicyUpdate>) -> Result<()> {
+        instructions::inbox::update_inbox_policy(ctx, updates)
+    }
+
+    pub fn send_request(ctx: Context<SendRequest>, tier_index: u8, memo_hash: [u8; 32]) -> Result<()> {
+        instructions::request::send_request(ctx, tier_index, memo_hash)
+    }
+
+    pub fn respond_to_request(ctx: Context<RespondToRequest>, accept: bool) -> Result<()> {
+        instructions::request::respond_to_request(ctx, accept)
+    }
+
+    pub fn cancel_request(ctx: Context<SettleRequest>) -> Result<()> {
+        instructions::request::cancel_request(ctx)
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize {}
\ No newline at end of file
+    pub fn expire_request(ctx: Context<SettleRequest>) -> Result<()> {
+        instructions::request::expire_request(ctx)
+    }
+
+    pub fn redeem_request(ctx: Context<RedeemRequest>) -> Result<()> {
+        instructions::request::redeem_request(ctx)
+    }
+
+    pub fn close_request(ctx: Context<CloseRequest>) -> Result<()> {
+        instructions::request::close_request(ctx)
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        slots: u8,
+        min_bid: u64,
+        bidding_window_hours: u16,
+    ) -> Result<()> {
+        instructions::auction::create_auction(ctx, slots, min_bid, bidding_window_hours)
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64, memo_hash: [u8; 32]) -> Result<()> {
+        instructions::auction::place_bid(ctx, amount, memo_hash)
+    }
+
+    pub fn settle_auction<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>) -> Result<()> {
+        instructions::auction::settle_auction(ctx)
+    }
+
+    pub fn close_bid(ctx: Context<CloseBid>) -> Result<()> {
+        instructions::auction::close_bid(ctx)
+    }
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use crate::errors::ContactGateError;
+
+pub const MAX_AUCTION_SLOTS: usize = 10;
+pub const MAX_BIDDING_WINDOW_HOURS: u16 = 168;
+
+/// Sells `slots` responses from an inbox to the highest bidders, at
+/// `[b"auction", inbox, auction_id]`.
+///
+/// `leaders` holds the current top bids, best first: higher amount wins, and
+/// ties go to the lower `sequence` (the bid committed earlier). Every bid
+/// event carries its amount and sequence, so the winners can be recomputed
+/// off-chain and checked against `AuctionSettled`.
+#[account]
+#[derive(InitSpace)]
+pub struct AttentionAuction {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,                 // Inbox owner, paid by the winners
+    pub mint: Pubkey,
+    pub auction_id: u64,               // Position in the inbox's auction_count
+    pub slots: u8,
+    pub min_bid: u64,
+    #[max_len(10)]
+    pub leaders: Vec<AuctionLeader>,
+    pub bid_count: u32,                // Distinct bidders
+    pub bid_sequence: u64,             // Next sequence; bumped on every bid or raise
+    pub settled_count: u32,            // Bids paid out or refunded
+    pub status: AuctionStatus,
+    pub clearing_price: u64,           // Lowest winning bid, set at settlement
+    pub total_paid: u64,
+    pub created_at: i64,
+    pub bidding_ends_at: i64,
+    pub settled_at: i64,               // 0 until settled
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug)]
+pub struct AuctionLeader {
+    pub bid: Pubkey,
+    pub amount: u64,
+    pub sequence: u64,
+}
+
+impl AuctionLeader {
+    fn ranks_above(&self, other: &AuctionLeader) -> bool {
+        self.amount > other.amount || (self.amount == other.amount && self.sequence < other.sequence)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum AuctionStatus {
+    Open,    // Taking bids until bidding_ends_at
+    Settled, // Winners fixed; bids are paid out or refunded in batches
+}
+
+impl AttentionAuction {
+    /// Enters or moves a bid on the leaderboard. Once all slots are taken, a
+    /// bid must outrank the last leader, which then drops off and is refunded
+    /// at settlement.
+    pub fn place(&mut self, entry: AuctionLeader) -> Result<()> {
+        self.leaders.retain(|leader| leader.bid != entry.bid);
+        if self.leaders.len() == self.slots as usize {
+            let last = self.leaders[self.leaders.len() - 1];
+            require!(entry.ranks_above(&last), ContactGateError::BidTooLow);
+            self.leaders.pop();
+        }
+
+        let position = self
+            .leaders
+            .iter()
+            .position(|leader| entry.ranks_above(leader))
+            .unwrap_or(self.leaders.len());
+        self.leaders.insert(position, entry);
+        Ok(())
+    }
+
+    pub fn is_winner(&self, bid: &Pubkey) -> bool {
+        self.status == AuctionStatus::Settled && self.leaders.iter().any(|leader| leader.bid == *bid)
+    }
+}
+
+/// One bidder's escrowed bid at `[b"bid", auction, bidder]`, with its
+/// deposit held at `[b"bid_escrow", bid]` under the bid PDA. Bidding again
+/// raises it.
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionBid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,                // Wallet or calling program's PDA
+    pub amount: u64,                   // Total escrowed, net of transfer fees
+    pub sequence: u64,                 // From the latest bid or raise
+    pub memo_hash: [u8; 32],           // Hash of the off-chain message
+    pub placed_at: i64,
+    pub status: BidStatus,
+    pub bump: u8,
+}
+
+impl AuctionBid {
+    /// Whether this bid won `bidder` a slot in `auction`.
+    pub fn grants_access(&self, auction: &Pubkey, bidder: &Pubkey) -> bool {
+        self.status == BidStatus::Won && self.auction == *auction && self.bidder == *bidder
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum BidStatus {
+    Active,   // Escrowed, awaiting settlement
+    Won,      // Escrow paid to the inbox owner
+    Refunded, // Outbid, escrow returned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auction(slots: u8) -> AttentionAuction {
+        AttentionAuction {
+            inbox: Pubkey::default(),
+            owner: Pubkey::default(),
+            mint: Pubkey::default(),
+            auction_id: 0,
+            slots,
+            min_bid: 1,
+            leaders: Vec::new(),
+            bid_count: 0,
+            bid_sequence: 0,
+            settled_count: 0,
+            status: AuctionStatus::Open,
+            clearing_price: 0,
+            total_paid: 0,
+            created_at: 0,
+            bidding_ends_at: 0,
+            settled_at: 0,
+            bump: 0,
+        }
+    }
+
+    fn entry(bid: u8, amount: u64, sequence: u64) -> AuctionLeader {
+        AuctionLeader {
+            bid: Pubkey::new_from_array([bid; 32]),
+            amount,
+            sequence,
+        }
+    }
+
+    // Leaders as (bid, amount), best first
+    fn board(auction: &AttentionAuction) -> Vec<(u8, u64)> {
+        auction.leaders.iter().map(|leader| (leader.bid.to_bytes()[0], leader.amount)).collect()
+    }
+
+    #[test]
+    fn ranks_by_amount_then_earlier_sequence() {
+        assert!(entry(1, 20, 5).ranks_above(&entry(2, 10, 0)));
+        assert!(!entry(1, 10, 0).ranks_above(&entry(2, 20, 5)));
+        assert!(entry(1, 10, 0).ranks_above(&entry(2, 10, 1)));
+        assert!(!entry(1, 10, 1).ranks_above(&entry(2, 10, 0)));
+        assert!(!entry(1, 10, 1).ranks_above(&entry(2, 10, 1)));
+    }
+
+    #[test]
+    fn keeps_leaders_sorted_with_ties_to_earlier_bids() {
+        let mut auction = auction(3);
+        auction.place(entry(1, 10, 0)).unwrap();
+        auction.place(entry(2, 30, 1)).unwrap();
+        auction.place(entry(3, 10, 2)).unwrap();
+        assert_eq!(board(&auction), vec![(2, 30), (1, 10), (3, 10)]);
+    }
+
+    #[test]
+    fn evicts_the_last_leader_when_full() {
+        let mut auction = auction(2);
+        auction.place(entry(1, 10, 0)).unwrap();
+        auction.place(entry(2, 20, 1)).unwrap();
+
+        // Below or tied with the last leader, but later: turned away
+        assert_eq!(auction.place(entry(3, 5, 2)).unwrap_err(), ContactGateError::BidTooLow.into());
+        assert_eq!(auction.place(entry(3, 10, 3)).unwrap_err(), ContactGateError::BidTooLow.into());
+        assert_eq!(board(&auction), vec![(2, 20), (1, 10)]);
+
+        auction.place(entry(3, 15, 4)).unwrap();
+        assert_eq!(board(&auction), vec![(2, 20), (3, 15)]);
+    }
+
+    #[test]
+    fn raise_by_a_leader_moves_it_without_evicting() {
+        let mut auction = auction(3);
+        auction.place(entry(1, 10, 0)).unwrap();
+        auction.place(entry(2, 20, 1)).unwrap();
+        auction.place(entry(3, 30, 2)).unwrap();
+
+        auction.place(entry(1, 40, 3)).unwrap();
+        assert_eq!(board(&auction), vec![(1, 40), (3, 30), (2, 20)]);
+
+        // A raise only to a tie ranks behind the earlier bid
+        auction.place(entry(2, 30, 4)).unwrap();
+        assert_eq!(board(&auction), vec![(1, 40), (3, 30), (2, 30)]);
+    }
+
+    #[test]
+    fn winners_only_once_settled() {
+        let mut auction = auction(1);
+        auction.place(entry(1, 10, 0)).unwrap();
+        let winner = Pubkey::new_from_array([1; 32]);
+        assert!(!auction.is_winner(&winner));
+
+        auction.status = AuctionStatus::Settled;
+        assert!(auction.is_winner(&winner));
+        assert!(!auction.is_winner(&Pubkey::new_from_array([2; 32])));
+    }
+}
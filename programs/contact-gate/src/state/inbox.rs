@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::errors::ContactGateError;
+
+pub const MAX_INBOX_TIERS: usize = 5;
+pub const MAX_TIER_LABEL_LEN: usize = 32;
+pub const MAX_RESPONSE_WINDOW_HOURS: u16 = 168;
+pub const MAX_POLICY_ENTRIES: usize = 32;
+
+/// A paid inbox for any pubkey at `[b"inbox", owner]`. The owner may be a
+/// wallet or another program's PDA, which then answers requests via CPI.
+#[account]
+#[derive(InitSpace)]
+pub struct Inbox {
+    pub owner: Pubkey,
+    pub mint: Pubkey,                  // Every tier is priced in this mint
+    #[max_len(5)]
+    pub tiers: Vec<InboxTier>,
+    #[max_len(32)]
+    pub blocked: Vec<Pubkey>,          // Refused outright
+    #[max_len(32)]
+    pub allowed: Vec<Pubkey>,          // Contact the owner for free
+    pub is_open: bool,
+    pub max_pending: u16,              // 0 = no cap
+    pub pending_count: u16,
+    pub request_count: u64,            // Requests ever sent; also the next nonce
+    pub auction_count: u64,            // Auctions ever created; also the next auction_id
+    pub accepted_count: u32,
+    pub rejected_count: u32,
+    pub expired_count: u32,
+    pub cancelled_count: u32,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct InboxTier {
+    pub price: u64,
+    pub response_window_hours: u16,
+    #[max_len(32)]
+    pub label: String,
+}
+
+impl InboxTier {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.price > 0
+                && self.response_window_hours > 0
+                && self.response_window_hours <= MAX_RESPONSE_WINDOW_HOURS
+                && self.label.len() <= MAX_TIER_LABEL_LEN,
+            ContactGateError::InvalidTier
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum InboxPolicyUpdate {
+    Block(Pubkey),
+    Allow(Pubkey),
+    Clear(Pubkey),
+}
+
+impl Inbox {
+    pub fn validate_tiers(tiers: &[InboxTier]) -> Result<()> {
+        require!(
+            !tiers.is_empty() && tiers.len() <= MAX_INBOX_TIERS,
+            ContactGateError::InvalidTierCount
+        );
+        tiers.iter().try_for_each(InboxTier::validate)
+    }
+
+    pub fn is_blocked(&self, sender: &Pubkey) -> bool {
+        self.blocked.contains(sender)
+    }
+
+    pub fn is_allowed(&self, sender: &Pubkey) -> bool {
+        self.allowed.contains(sender)
+    }
+
+    /// Applies one policy change. A sender is on at most one list.
+    pub fn apply_policy(&mut self, update: &InboxPolicyUpdate) -> Result<()> {
+        match update {
+            InboxPolicyUpdate::Block(sender) => {
+                self.allowed.retain(|key| key != sender);
+                if !self.blocked.contains(sender) {
+                    require!(self.blocked.len() < MAX_POLICY_ENTRIES, ContactGateError::PolicyFull);
+                    self.blocked.push(*sender);
+                }
+            }
+            InboxPolicyUpdate::Allow(sender) => {
+                self.blocked.retain(|key| key != sender);
+                if !self.allowed.contains(sender) {
+                    require!(self.allowed.len() < MAX_POLICY_ENTRIES, ContactGateError::PolicyFull);
+                    self.allowed.push(*sender);
+                }
+            }
+            InboxPolicyUpdate::Clear(sender) => {
+                self.blocked.retain(|key| key != sender);
+                self.allowed.retain(|key| key != sender);
+            }
+        }
+        Ok(())
+    }
+}
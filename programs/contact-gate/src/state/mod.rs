@@ -0,0 +1,7 @@
+pub mod inbox;
+pub mod request;
+pub mod auction;
+
+pub use inbox::*;
+pub use request::*;
+pub use auction::*;
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use crate::errors::ContactGateError;
+
+/// A paid request to an inbox at `[b"request", inbox, nonce]`, with its
+/// deposit escrowed at `[b"escrow", request]` under the request PDA.
+///
+/// Accepting pays the escrow to the inbox owner; rejecting, cancelling or
+/// expiring refunds the requester. Other programs gate their own
+/// instructions on an accepted request, either by reading it (see
+/// `grants_access`) or by having the requester `redeem_request` it once.
+#[account]
+#[derive(InitSpace)]
+pub struct InboxRequest {
+    pub inbox: Pubkey,
+    pub requester: Pubkey,             // Wallet or calling program's PDA
+    pub nonce: u64,                    // Position in the inbox's request_count
+    pub tier_index: u8,
+    pub amount: u64,
+    pub memo_hash: [u8; 32],           // Hash of the off-chain message
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub resolved_at: i64,              // 0 while pending
+    pub status: RequestStatus,
+    pub redeemed: bool,
+    pub bump: u8,
+}
+
+impl InboxRequest {
+    pub fn is_pending(&self) -> bool {
+        self.status == RequestStatus::Pending
+    }
+
+    /// The owner may answer up to and including `expires_at`.
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+
+    /// Uses up an accepted request; each one redeems once.
+    pub fn redeem(&mut self) -> Result<()> {
+        require!(self.status == RequestStatus::Accepted, ContactGateError::RequestNotAccepted);
+        require!(!self.redeemed, ContactGateError::AlreadyRedeemed);
+        self.redeemed = true;
+        Ok(())
+    }
+
+    /// Whether this request lets `requester` past a gate on `inbox`.
+    pub fn grants_access(&self, inbox: &Pubkey, requester: &Pubkey) -> bool {
+        self.status == RequestStatus::Accepted && self.inbox == *inbox && self.requester == *requester
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RequestStatus {
+    Pending,   // Funded, awaiting the owner
+    Accepted,  // Escrow paid to the owner
+    Rejected,  // Escrow refunded
+    Expired,   // No answer in time, escrow refunded
+    Cancelled, // Withdrawn by the requester, escrow refunded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(inbox: Pubkey, requester: Pubkey) -> InboxRequest {
+        InboxRequest {
+            inbox,
+            requester,
+            nonce: 0,
+            tier_index: 0,
+            amount: 1_000,
+            memo_hash: [7; 32],
+            created_at: 100,
+            expires_at: 100 + 24 * 3600,
+            resolved_at: 0,
+            status: RequestStatus::Pending,
+            redeemed: false,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn expires_only_after_the_response_window() {
+        let request = request(Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(!request.is_expired(request.created_at));
+        assert!(!request.is_expired(request.expires_at));
+        assert!(request.is_expired(request.expires_at + 1));
+    }
+
+    #[test]
+    fn only_an_accepted_request_grants_access() {
+        let (inbox, requester) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut request = request(inbox, requester);
+        assert!(request.is_pending());
+        assert!(!request.grants_access(&inbox, &requester));
+
+        for status in [RequestStatus::Rejected, RequestStatus::Expired, RequestStatus::Cancelled] {
+            request.status = status;
+            assert!(!request.is_pending());
+            assert!(!request.grants_access(&inbox, &requester));
+        }
+
+        request.status = RequestStatus::Accepted;
+        assert!(request.grants_access(&inbox, &requester));
+    }
+
+    #[test]
+    fn access_is_scoped_to_the_inbox_and_requester() {
+        let (inbox, requester) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut request = request(inbox, requester);
+        request.status = RequestStatus::Accepted;
+
+        assert!(!request.grants_access(&Pubkey::new_unique(), &requester));
+        assert!(!request.grants_access(&inbox, &Pubkey::new_unique()));
+        assert!(!request.grants_access(&requester, &inbox));
+    }
+
+    #[test]
+    fn redeems_an_accepted_request_once() {
+        let mut request = request(Pubkey::new_unique(), Pubkey::new_unique());
+        request.status = RequestStatus::Accepted;
+
+        request.redeem().unwrap();
+        assert!(request.redeemed);
+        assert_eq!(request.redeem().unwrap_err(), ContactGateError::AlreadyRedeemed.into());
+    }
+
+    #[test]
+    fn cannot_redeem_an_unaccepted_request() {
+        for status in [
+            RequestStatus::Pending,
+            RequestStatus::Rejected,
+            RequestStatus::Expired,
+            RequestStatus::Cancelled,
+        ] {
+            let mut request = request(Pubkey::new_unique(), Pubkey::new_unique());
+            request.status = status;
+            assert_eq!(request.redeem().unwrap_err(), ContactGateError::RequestNotAccepted.into());
+            assert!(!request.redeemed);
+        }
+    }
+}
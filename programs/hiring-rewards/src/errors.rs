@@ -8,4 +8,6 @@ pub enum HiringRewardError {
     NoTiersAvailable,
     #[msg("Invalid tier index.")]
     InvalidTierIndex,
+    #[msg("Mint is not accepted for payments.")]
+    MintNotAccepted,
 }
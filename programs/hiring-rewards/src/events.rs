@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RewardDistributed {
+    pub reward_pool: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub referrer_token_account: Option<Pubkey>,
+    pub tier_index: u8,
+    pub reward_amount: u64,
+    pub recipient_amount: u64,
+    pub referrer_amount: u64,
+    pub platform_fee: u64,
+}
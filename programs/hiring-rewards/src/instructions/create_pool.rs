@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint};
+use anchor_spl::token_interface::Mint;
+use profile_manager::state::AcceptedMint;
 use crate::state::{RewardPool, RewardTier};
+use crate::errors::HiringRewardError;
 
 #[derive(Accounts)]
 #[instruction(reward_tiers: Vec<RewardTier>)]
@@ -17,7 +19,15 @@ pub struct CreateRewardPool<'info> {
     )]
     pub reward_pool: Account<'info, RewardPool>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"accepted_mint", usdc_mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        seeds::program = profile_manager::ID,
+        constraint = accepted_mint.is_active @ HiringRewardError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
 
     pub system_program: Program<'info, System>,
 }
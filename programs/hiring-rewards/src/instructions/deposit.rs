@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
+use profile_manager::state::AcceptedMint;
 use crate::state::RewardPool;
+use crate::errors::HiringRewardError;
 
 #[derive(Accounts)]
 pub struct DepositToPool<'info> {
@@ -23,29 +25,50 @@ pub struct DepositToPool<'info> {
         payer = authority,
         associated_token::mint = usdc_mint,
         associated_token::authority = reward_pool,
+        associated_token::token_program = token_program,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub source_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
-    pub usdc_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"accepted_mint", usdc_mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        seeds::program = profile_manager::ID,
+        constraint = accepted_mint.is_active @ HiringRewardError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
-    let cpi_accounts = Transfer {
+    let balance_before = ctx.accounts.reward_vault.amount;
+
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.source_token_account.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
         to: ctx.accounts.reward_vault.to_account_info(),
         authority: ctx.accounts.authority.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+
+    // Credit what the vault received, which is net of any transfer fee
+    ctx.accounts.reward_vault.reload()?;
+    let received = ctx.accounts.reward_vault.amount - balance_before;
 
-    ctx.accounts.reward_pool.total_amount = ctx.accounts.reward_pool.total_amount.checked_add(amount).unwrap();
+    ctx.accounts.reward_pool.total_amount = ctx.accounts.reward_pool.total_amount.checked_add(received).unwrap();
 
     Ok(())
 }
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
-use crate::state::{RewardPool, Referral};
+use profile_manager::state::PlatformConfig;
+use crate::state::RewardPool;
 use crate::errors::HiringRewardError;
+use crate::events::RewardDistributed;
 
 #[derive(Accounts)]
 pub struct DistributeReward<'info> {
@@ -21,20 +23,35 @@ pub struct DistributeReward<'info> {
         mut,
         associated_token::mint = usdc_mint,
         associated_token::authority = reward_pool,
+        associated_token::token_program = token_program,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Optional referrer token account
     #[account(mut)]
     pub referrer_token_account: Option<AccountInfo<'info>>,
 
-    pub usdc_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     pub referral: Option<AccountInfo<'info>>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        seeds::program = profile_manager::ID
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = platform_config.treasury
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 }
 
 pub fn distribute_reward(ctx: Context<DistributeReward>, tier_index: u8) -> Result<()> {
@@ -52,45 +69,79 @@ pub fn distribute_reward(ctx: Context<DistributeReward>, tier_index: u8) -> Resu
         &[pool.bump],
     ];
     let signer = &[&seeds[..]];
+    let decimals = ctx.accounts.usdc_mint.decimals;
+
+    // Platform cut comes off the top, the rest is split as before
+    let platform_fee = PlatformConfig::fee_for(reward_amount, ctx.accounts.platform_config.bounty_fee_bps);
+    let payout = reward_amount - platform_fee;
+
+    if platform_fee > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, platform_fee, decimals)?;
+    }
 
-    if let (Some(referral), Some(referrer_token_account)) = (&ctx.accounts.referral, &ctx.accounts.referrer_token_account) {
+    let (referee_reward, referrer_reward) = if let (Some(_referral), Some(referrer_token_account)) = (&ctx.accounts.referral, &ctx.accounts.referrer_token_account) {
         // 50/50 split
-        let referee_reward = reward_amount / 2;
-        let referrer_reward = reward_amount - referee_reward; // Avoid dust
+        let referee_reward = payout / 2;
+        let referrer_reward = payout - referee_reward; // Avoid dust
 
         // Transfer to referee
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
             to: ctx.accounts.destination_token_account.to_account_info(),
             authority: pool.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, referee_reward)?;
+        token_interface::transfer_checked(cpi_ctx, referee_reward, decimals)?;
 
         // Transfer to referrer
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
             to: referrer_token_account.to_account_info(),
             authority: pool.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, referrer_reward)?;
+        token_interface::transfer_checked(cpi_ctx, referrer_reward, decimals)?;
 
+        (referee_reward, referrer_reward)
     } else {
         // Transfer to referee only
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
             to: ctx.accounts.destination_token_account.to_account_info(),
             authority: pool.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, reward_amount)?;
-    }
+        token_interface::transfer_checked(cpi_ctx, payout, decimals)?;
+
+        (payout, 0)
+    };
 
     pool.total_amount = pool.total_amount.checked_sub(reward_amount).unwrap();
 
+    emit!(RewardDistributed {
+        reward_pool: pool.key(),
+        recipient_token_account: ctx.accounts.destination_token_account.key(),
+        referrer_token_account: ctx.accounts.referrer_token_account.as_ref().map(|acc| acc.key()).filter(|_| referrer_reward > 0),
+        tier_index,
+        reward_amount,
+        recipient_amount: referee_reward,
+        referrer_amount: referrer_reward,
+        platform_fee,
+    });
+
     Ok(())
 }
\ No newline at end of file
@@ -3,9 +3,9 @@ use anchor_lang::prelude::*;
 pub mod instructions;
 pub mod state;
 pub mod errors;
+pub mod events;
 
 use instructions::*;
-use errors::*;
 use state::*;
 
 declare_id!("HQAgXyTzVkb7nPcULH8BbigDFR1Wc8mQWrG2Su3UeD9b");
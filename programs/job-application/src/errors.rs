@@ -20,4 +20,18 @@ pub enum JobApplicationError {
     Unauthorized,
     #[msg("Invalid bounty amount")]
     InvalidBountyAmount,
-}
\ No newline at end of file
+    #[msg("Platform config and treasury accounts are required to pay a reward")]
+    MissingPlatformAccounts,
+    #[msg("Mint is not accepted for payments")]
+    MintNotAccepted,
+    #[msg("Contact sponsorship cap is below what has already been spent")]
+    InvalidSponsorshipCap,
+    #[msg("Contact fee exceeds the job's sponsorship cap")]
+    SponsorshipCapExceeded,
+    #[msg("Recruiter has not accepted a contact request from the applicant")]
+    ContactNotAccepted,
+    #[msg("Application is already prioritized")]
+    AlreadyPrioritized,
+    #[msg("Contact request was not sponsored by this job")]
+    NotSponsoredByJob,
+}
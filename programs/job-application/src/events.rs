@@ -17,6 +17,15 @@ pub struct ApplicationSubmitted {
     pub applied_at: i64,
 }
 
+#[event]
+pub struct ApplicationPrioritized {
+    pub application: Pubkey,
+    pub job: Pubkey,
+    pub applicant: Pubkey,
+    pub inbox_request: Pubkey,      // contact-gate request that was redeemed
+    pub prioritized_at: i64,
+}
+
 #[event]
 pub struct ApplicationStatusUpdated {
     pub application: Pubkey,
@@ -30,4 +39,32 @@ pub struct ReferralLinkCreated {
     pub referrer: Pubkey,
     pub link_id: u64,
     pub created_at: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct ContactSponsorshipUpdated {
+    pub job: Pubkey,
+    pub cap: u64,
+    pub spent: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct ContactSponsorshipRefunded {
+    pub job: Pubkey,
+    pub contact_request: Pubkey,
+    pub amount: u64,                // Refund credited back to the bounty
+    pub spent: u64,
+    pub hiring_bounty: u64,
+    pub credited_at: i64,
+}
+
+#[event]
+pub struct ContactSponsored {
+    pub job: Pubkey,
+    pub contact_request: Pubkey,
+    pub target_profile: Pubkey,
+    pub amount: u64,                // Drawn from the bounty escrow
+    pub spent: u64,
+    pub sponsored_at: i64,
+}
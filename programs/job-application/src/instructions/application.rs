@@ -4,7 +4,10 @@ use crate::errors::*;
 use crate::events::*;
 use hiring_rewards::cpi::accounts::DistributeReward;
 use hiring_rewards::program::HiringRewards;
-use anchor_spl::token::{Token, Mint, TokenAccount};
+use contact_gate::cpi::accounts::RedeemRequest;
+use contact_gate::program::ContactGate;
+use contact_gate::state::{Inbox, InboxRequest};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 
 pub fn apply_to_job(
     ctx: Context<ApplyToJob>,
@@ -36,6 +39,7 @@ pub fn apply_to_job(
     application.status = ApplicationStatus::Pending;
     application.referrer = referrer;
     application.referral_link_id = referral_link_id;
+    application.priority_request = None;
     application.bump = ctx.bumps.application;
 
     job.application_count = job.application_count.checked_add(1).unwrap();
@@ -54,6 +58,42 @@ pub fn apply_to_job(
     Ok(())
 }
 
+/// Marks an application as priority with a paid contact-gate request the
+/// recruiter accepted in their inbox. The request is redeemed, so each paid
+/// contact prioritizes one application.
+pub fn prioritize_application(ctx: Context<PrioritizeApplication>) -> Result<()> {
+    let clock = Clock::get()?;
+    let applicant = ctx.accounts.applicant.key();
+    let inbox_request = &ctx.accounts.inbox_request;
+
+    require!(
+        inbox_request.grants_access(&ctx.accounts.recruiter_inbox.key(), &applicant),
+        JobApplicationError::ContactNotAccepted
+    );
+    require!(ctx.accounts.application.priority_request.is_none(), JobApplicationError::AlreadyPrioritized);
+
+    // The applicant's signature carries through to contact-gate
+    let cpi_accounts = RedeemRequest {
+        request: inbox_request.to_account_info(),
+        requester: ctx.accounts.applicant.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.contact_gate_program.to_account_info(), cpi_accounts);
+    contact_gate::cpi::redeem_request(cpi_ctx)?;
+
+    let application = &mut ctx.accounts.application;
+    application.priority_request = Some(inbox_request.key());
+
+    emit!(ApplicationPrioritized {
+        application: application.key(),
+        job: application.job,
+        applicant,
+        inbox_request: inbox_request.key(),
+        prioritized_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 pub fn update_application_status(
     ctx: Context<UpdateApplicationStatus>,
     new_status: ApplicationStatus,
@@ -86,6 +126,12 @@ pub fn hire_applicant(ctx: Context<HireApplicant>, tier_index: u8) -> Result<()>
 
     // Only call reward distribution if there's a reward pool and referral
     if let (Some(reward_pool), Some(reward_vault)) = (&ctx.accounts.reward_pool, &ctx.accounts.reward_vault) {
+        // Bounty payouts carry the platform fee, so the fee accounts become mandatory
+        let platform_config = ctx.accounts.platform_config.as_ref()
+            .ok_or(JobApplicationError::MissingPlatformAccounts)?;
+        let treasury_token_account = ctx.accounts.treasury_token_account.as_ref()
+            .ok_or(JobApplicationError::MissingPlatformAccounts)?;
+
         let cpi_accounts = DistributeReward {
             authority: ctx.accounts.recruiter.to_account_info(),
             reward_pool: reward_pool.to_account_info(),
@@ -95,6 +141,8 @@ pub fn hire_applicant(ctx: Context<HireApplicant>, tier_index: u8) -> Result<()>
             usdc_mint: ctx.accounts.usdc_mint.to_account_info(),
             token_program: ctx.accounts.token_program.to_account_info(),
             referral: ctx.accounts.referral.as_ref().map(|acc| acc.to_account_info()),
+            platform_config: platform_config.to_account_info(),
+            treasury_token_account: treasury_token_account.to_account_info(),
         };
         let cpi_ctx = CpiContext::new(ctx.accounts.hiring_rewards_program.to_account_info(), cpi_accounts);
         hiring_rewards::cpi::distribute_reward(cpi_ctx, tier_index)?;
@@ -129,6 +177,35 @@ pub struct ApplyToJob<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct PrioritizeApplication<'info> {
+    #[account(
+        mut,
+        seeds = [b"application", job.key().as_ref(), applicant.key().as_ref()],
+        bump = application.bump,
+        has_one = job,
+        has_one = applicant
+    )]
+    pub application: Account<'info, Application>,
+
+    pub job: Account<'info, Job>,
+
+    #[account(
+        seeds = [b"inbox", job.recruiter.as_ref()],
+        bump = recruiter_inbox.bump,
+        seeds::program = contact_gate_program.key()
+    )]
+    pub recruiter_inbox: Account<'info, Inbox>,
+
+    /// Redeemed by contact-gate, which checks it belongs to the applicant
+    #[account(mut)]
+    pub inbox_request: Account<'info, InboxRequest>,
+
+    pub applicant: Signer<'info>,
+
+    pub contact_gate_program: Program<'info, ContactGate>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateApplicationStatus<'info> {
     #[account(
@@ -173,7 +250,7 @@ pub struct HireApplicant<'info> {
     pub reward_vault: Option<AccountInfo<'info>>,
 
     #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: This is not dangerous because we are not writing to this account
     #[account(mut)]
@@ -182,8 +259,15 @@ pub struct HireApplicant<'info> {
     /// CHECK: This is not dangerous because we are not writing to this account
     pub usdc_mint: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// CHECK: This is not dangerous because we are not writing to this account
     pub referral: Option<AccountInfo<'info>>,
+
+    /// CHECK: profile-manager PlatformConfig, validated by hiring-rewards
+    pub platform_config: Option<AccountInfo<'info>>,
+
+    /// CHECK: Treasury token account, validated by hiring-rewards
+    #[account(mut)]
+    pub treasury_token_account: Option<AccountInfo<'info>>,
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use profile_manager::state::AcceptedMint;
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_job(
     ctx: Context<CreateJob>,
     title: String,
@@ -15,8 +17,6 @@ pub fn create_job(
     job_id: u64,
     hiring_bounty: u64,
 ) -> Result<()> {
-    let job = &mut ctx.accounts.job;
-    let job_bounty = &mut ctx.accounts.job_bounty;
     let clock = Clock::get()?;
 
     require!(title.len() <= 100, JobApplicationError::TitleTooLong);
@@ -30,15 +30,22 @@ pub fn create_job(
     if hiring_bounty > 0 {
         let transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.recruiter_token_account.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
                 to: ctx.accounts.bounty_escrow_account.to_account_info(),
                 authority: ctx.accounts.recruiter.to_account_info(),
             },
         );
-        token::transfer(transfer_ctx, hiring_bounty)?;
+        token_interface::transfer_checked(transfer_ctx, hiring_bounty, ctx.accounts.usdc_mint.decimals)?;
     }
 
+    // Record what the escrow actually holds; transfer-fee mints deliver less
+    ctx.accounts.bounty_escrow_account.reload()?;
+    let escrowed_bounty = ctx.accounts.bounty_escrow_account.amount;
+
+    let job = &mut ctx.accounts.job;
+    let job_bounty = &mut ctx.accounts.job_bounty;
     job.recruiter = ctx.accounts.recruiter.key();
     job.title = title.clone();
     job.description = description;
@@ -49,14 +56,14 @@ pub fn create_job(
     job.deadline = clock.unix_timestamp + (deadline_days as i64 * 24 * 60 * 60);
     job.is_active = true;
     job.application_count = 0;
-    job.hiring_bounty = hiring_bounty;
+    job.hiring_bounty = escrowed_bounty;
     job.bounty_distributed = false;
     job.job_id = job_id;
     job.bump = ctx.bumps.job;
 
     job_bounty.job = job.key();
     job_bounty.recruiter = ctx.accounts.recruiter.key();
-    job_bounty.amount = hiring_bounty;
+    job_bounty.amount = escrowed_bounty;
     job_bounty.direct_hire_percentage = 70; // 70% for direct hire candidate
     job_bounty.referral_percentage = 20;    // 20% for referrer
     job_bounty.candidate_percentage = 50;   // 50% for referred candidate
@@ -67,7 +74,7 @@ pub fn create_job(
         job_id: job.key(),
         recruiter: job.recruiter,
         title,
-        hiring_bounty,
+        hiring_bounty: escrowed_bounty,
         created_at: job.created_at,
     });
 
@@ -98,18 +105,24 @@ pub struct CreateJob<'info> {
     #[account(mut)]
     pub recruiter: Signer<'info>,
 
-    #[account(mut)]
-    pub recruiter_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = recruiter,
+        token::token_program = token_program
+    )]
+    pub recruiter_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
         payer = recruiter,
         token::mint = usdc_mint,
         token::authority = bounty_authority,
+        token::token_program = token_program,
         seeds = [b"bounty_escrow", job.key().as_ref()],
         bump
     )]
-    pub bounty_escrow_account: Account<'info, TokenAccount>,
+    pub bounty_escrow_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA authority for bounty escrow
     #[account(
@@ -118,9 +131,17 @@ pub struct CreateJob<'info> {
     )]
     pub bounty_authority: AccountInfo<'info>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"accepted_mint", usdc_mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        seeds::program = profile_manager::ID,
+        constraint = accepted_mint.is_active @ JobApplicationError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 pub mod job;
 pub mod application;
 pub mod referral;
+pub mod sponsorship;
 
 pub use job::*;
 pub use application::*;
-pub use referral::*;
\ No newline at end of file
+pub use referral::*;
+pub use sponsorship::*;
\ No newline at end of file
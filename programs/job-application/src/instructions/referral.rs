@@ -1,6 +1,5 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
-use crate::errors::*;
 use crate::events::*;
 
 pub fn create_referral_link(
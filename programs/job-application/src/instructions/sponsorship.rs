@@ -0,0 +1,319 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use profile_manager::cpi::accounts::{CloseContactRequest, SendContactRequest};
+use profile_manager::program::ProfileManager;
+use profile_manager::state::{ContactRequest, EncryptedMessage};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+/// Sets how much of the job's bounty escrow may be spent on contact fees.
+pub fn set_contact_sponsorship(ctx: Context<SetContactSponsorship>, cap: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let sponsorship = &mut ctx.accounts.contact_sponsorship;
+
+    if sponsorship.job == Pubkey::default() {
+        sponsorship.job = ctx.accounts.job.key();
+        sponsorship.recruiter = ctx.accounts.recruiter.key();
+        sponsorship.bump = ctx.bumps.contact_sponsorship;
+    }
+    require!(cap >= sponsorship.spent, JobApplicationError::InvalidSponsorshipCap);
+    sponsorship.cap = cap;
+
+    emit!(ContactSponsorshipUpdated {
+        job: sponsorship.job,
+        cap,
+        spent: sponsorship.spent,
+        updated_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Closes a settled request the job sponsored, crediting what profile-manager
+/// refunded for it back to the bounty and the sponsorship cap. Profile-manager
+/// only closes job-sponsored requests with the bounty authority's signature,
+/// so each refund is credited exactly once.
+pub fn close_sponsored_contact_request(ctx: Context<CloseSponsoredContactRequest>) -> Result<()> {
+    let clock = Clock::get()?;
+    let job_key = ctx.accounts.job.key();
+    let contact_request = ctx.accounts.contact_request.key();
+    let refunded = ctx.accounts.contact_request.refunded.min(ctx.accounts.contact_request.amount);
+
+    let cpi_accounts = CloseContactRequest {
+        contact_request: ctx.accounts.contact_request.to_account_info(),
+        requester: ctx.accounts.recruiter.to_account_info(),
+        // Stray deposits in the request's escrow are swept into the bounty
+        // escrow, uncredited
+        requester_token_account: ctx
+            .accounts
+            .escrow_token_account
+            .as_ref()
+            .map(|_| ctx.accounts.bounty_escrow_account.to_account_info()),
+        escrow_token_account: ctx.accounts.escrow_token_account.as_ref().map(|acc| acc.to_account_info()),
+        usdc_mint: ctx.accounts.usdc_mint.as_ref().map(|acc| acc.to_account_info()),
+        subscription: ctx.accounts.subscription.as_ref().map(|acc| acc.to_account_info()),
+        outreach_campaign: None,
+        job_sponsor: Some(ctx.accounts.bounty_authority.to_account_info()),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let seeds = &[
+        b"bounty_authority".as_ref(),
+        job_key.as_ref(),
+        &[ctx.bumps.bounty_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.profile_manager_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    profile_manager::cpi::close_contact_request(cpi_ctx)?;
+
+    if refunded > 0 {
+        let sponsorship = &mut ctx.accounts.contact_sponsorship;
+        ctx.accounts.job_bounty.amount += refunded;
+        ctx.accounts.job.hiring_bounty += refunded;
+        sponsorship.spent = sponsorship.spent.saturating_sub(refunded);
+        sponsorship.refunded += refunded;
+
+        emit!(ContactSponsorshipRefunded {
+            job: job_key,
+            contact_request,
+            amount: refunded,
+            spent: sponsorship.spent,
+            hiring_bounty: ctx.accounts.job.hiring_bounty,
+            credited_at: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// Sends a profile-manager contact request about this job, paid from the
+/// bounty escrow. The bounty authority signs the deposit, so profile-manager
+/// links the request to the job and refunds it to the bounty escrow. The fee
+/// comes off the job's bounty until `close_sponsored_contact_request` credits
+/// the refund back.
+pub fn sponsor_contact_request(
+    ctx: Context<SponsorContactRequest>,
+    message: EncryptedMessage,
+    tier_index: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(ctx.accounts.job.is_active, JobApplicationError::JobNotActive);
+
+    let job_key = ctx.accounts.job.key();
+    let balance_before = ctx.accounts.bounty_escrow_account.amount;
+
+    let cpi_accounts = SendContactRequest {
+        contact_thread: ctx.accounts.contact_thread.to_account_info(),
+        contact_request: ctx.accounts.contact_request.to_account_info(),
+        target_profile: ctx.accounts.target_profile.to_account_info(),
+        contact_policy: ctx.accounts.contact_policy.as_ref().map(|acc| acc.to_account_info()),
+        requester: ctx.accounts.recruiter.to_account_info(),
+        requester_token_account: ctx.accounts.recruiter_token_account.to_account_info(),
+        job_sponsor: Some(ctx.accounts.bounty_authority.to_account_info()),
+        sponsor_token_account: Some(ctx.accounts.bounty_escrow_account.to_account_info()),
+        escrow_token_account: ctx.accounts.escrow_token_account.as_ref().map(|acc| acc.to_account_info()),
+        contact_credits: None,
+        outreach_campaign: None,
+        subscription: ctx.accounts.subscription.to_account_info(),
+        usdc_mint: ctx.accounts.usdc_mint.to_account_info(),
+        accepted_mint: ctx.accounts.accepted_mint.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+    let seeds = &[
+        b"bounty_authority".as_ref(),
+        job_key.as_ref(),
+        &[ctx.bumps.bounty_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.profile_manager_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    profile_manager::cpi::send_contact_request(cpi_ctx, message, tier_index, Some(job_key))?;
+
+    // Charge the cap with what actually left the escrow; allowlisted and
+    // subscription-drawn requests cost nothing
+    ctx.accounts.bounty_escrow_account.reload()?;
+    let amount = balance_before - ctx.accounts.bounty_escrow_account.amount;
+
+    let sponsorship = &mut ctx.accounts.contact_sponsorship;
+    sponsorship.spent += amount;
+    require!(sponsorship.spent <= sponsorship.cap, JobApplicationError::SponsorshipCapExceeded);
+    sponsorship.requests_sponsored += 1;
+    ctx.accounts.job.hiring_bounty -= amount;
+    ctx.accounts.job_bounty.amount -= amount;
+
+    emit!(ContactSponsored {
+        job: job_key,
+        contact_request: ctx.accounts.contact_request.key(),
+        target_profile: ctx.accounts.target_profile.key(),
+        amount,
+        spent: sponsorship.spent,
+        sponsored_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetContactSponsorship<'info> {
+    #[account(
+        init_if_needed,
+        payer = recruiter,
+        space = 8 + ContactSponsorship::INIT_SPACE,
+        seeds = [b"contact_sponsorship", job.key().as_ref()],
+        bump
+    )]
+    pub contact_sponsorship: Account<'info, ContactSponsorship>,
+
+    #[account(has_one = recruiter)]
+    pub job: Account<'info, Job>,
+
+    #[account(mut)]
+    pub recruiter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SponsorContactRequest<'info> {
+    #[account(
+        mut,
+        seeds = [b"contact_sponsorship", job.key().as_ref()],
+        bump = contact_sponsorship.bump,
+        has_one = job
+    )]
+    pub contact_sponsorship: Account<'info, ContactSponsorship>,
+
+    #[account(mut, has_one = recruiter)]
+    pub job: Account<'info, Job>,
+
+    #[account(
+        mut,
+        seeds = [b"job_bounty", job.key().as_ref()],
+        bump = job_bounty.bump
+    )]
+    pub job_bounty: Account<'info, JobBounty>,
+
+    #[account(mut)]
+    pub recruiter: Signer<'info>,
+
+    /// CHECK: PDA authority for bounty escrow
+    #[account(
+        seeds = [b"bounty_authority", job.key().as_ref()],
+        bump
+    )]
+    pub bounty_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty_escrow", job.key().as_ref()],
+        bump
+    )]
+    pub bounty_escrow_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validated by profile-manager
+    #[account(mut)]
+    pub recruiter_token_account: AccountInfo<'info>,
+
+    /// CHECK: Validated by profile-manager
+    #[account(mut)]
+    pub contact_thread: AccountInfo<'info>,
+
+    /// CHECK: Validated by profile-manager
+    #[account(mut)]
+    pub contact_request: AccountInfo<'info>,
+
+    /// CHECK: Validated by profile-manager
+    #[account(mut)]
+    pub target_profile: AccountInfo<'info>,
+
+    /// CHECK: Validated by profile-manager
+    pub contact_policy: Option<AccountInfo<'info>>,
+
+    /// CHECK: Validated by profile-manager; omitted when the recruiter's
+    /// subscription to the target covers the request
+    #[account(mut)]
+    pub escrow_token_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: Validated by profile-manager
+    #[account(mut)]
+    pub subscription: AccountInfo<'info>,
+
+    /// CHECK: Validated by profile-manager
+    pub usdc_mint: AccountInfo<'info>,
+
+    /// CHECK: Validated by profile-manager
+    pub accepted_mint: AccountInfo<'info>,
+
+    pub profile_manager_program: Program<'info, ProfileManager>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSponsoredContactRequest<'info> {
+    #[account(
+        mut,
+        seeds = [b"contact_sponsorship", job.key().as_ref()],
+        bump = contact_sponsorship.bump,
+        has_one = job
+    )]
+    pub contact_sponsorship: Account<'info, ContactSponsorship>,
+
+    #[account(mut, has_one = recruiter)]
+    pub job: Account<'info, Job>,
+
+    #[account(
+        mut,
+        seeds = [b"job_bounty", job.key().as_ref()],
+        bump = job_bounty.bump
+    )]
+    pub job_bounty: Account<'info, JobBounty>,
+
+    #[account(mut)]
+    pub recruiter: Signer<'info>,
+
+    /// CHECK: PDA authority for bounty escrow
+    #[account(
+        seeds = [b"bounty_authority", job.key().as_ref()],
+        bump
+    )]
+    pub bounty_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bounty_escrow", job.key().as_ref()],
+        bump
+    )]
+    pub bounty_escrow_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Closed and otherwise validated by profile-manager
+    #[account(
+        mut,
+        constraint = contact_request.job == Some(job.key()) @ JobApplicationError::NotSponsoredByJob
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    /// CHECK: Validated by profile-manager; required for escrow-funded requests
+    #[account(mut)]
+    pub escrow_token_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: Validated by profile-manager; required for escrow-funded requests
+    pub usdc_mint: Option<AccountInfo<'info>>,
+
+    /// CHECK: Validated by profile-manager; required for subscription-drawn requests
+    #[account(mut)]
+    pub subscription: Option<AccountInfo<'info>>,
+
+    pub profile_manager_program: Program<'info, ProfileManager>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -7,8 +7,7 @@ pub mod events;
 
 use instructions::*;
 use state::*;
-use errors::*;
-use events::*;
+use profile_manager::state::EncryptedMessage;
 
 declare_id!("2qABiq2mqKPrp8H2eFqshFZ4EjTYMHPcmepnHD4TuwgN");
 
@@ -16,6 +15,7 @@ declare_id!("2qABiq2mqKPrp8H2eFqshFZ4EjTYMHPcmepnHD4TuwgN");
 pub mod job_application {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_job(
         ctx: Context<CreateJob>,
         title: String,
@@ -56,6 +56,10 @@ pub mod job_application {
         instructions::referral::create_referral_link(ctx, job_key, link_id)
     }
 
+    pub fn prioritize_application(ctx: Context<PrioritizeApplication>) -> Result<()> {
+        instructions::application::prioritize_application(ctx)
+    }
+
     pub fn update_application_status(
         ctx: Context<UpdateApplicationStatus>,
         new_status: ApplicationStatus,
@@ -66,4 +70,20 @@ pub mod job_application {
     pub fn hire_applicant(ctx: Context<HireApplicant>, tier_index: u8) -> Result<()> {
         instructions::application::hire_applicant(ctx, tier_index)
     }
-}
\ No newline at end of file
+
+    pub fn set_contact_sponsorship(ctx: Context<SetContactSponsorship>, cap: u64) -> Result<()> {
+        instructions::sponsorship::set_contact_sponsorship(ctx, cap)
+    }
+
+    pub fn sponsor_contact_request(
+        ctx: Context<SponsorContactRequest>,
+        message: EncryptedMessage,
+        tier_index: u8,
+    ) -> Result<()> {
+        instructions::sponsorship::sponsor_contact_request(ctx, message, tier_index)
+    }
+
+    pub fn close_sponsored_contact_request(ctx: Context<CloseSponsoredContactRequest>) -> Result<()> {
+        instructions::sponsorship::close_sponsored_contact_request(ctx)
+    }
+}
@@ -12,6 +12,7 @@ pub struct Application {
     pub status: ApplicationStatus,
     pub referrer: Option<Pubkey>, // for referral tracking
     pub referral_link_id: Option<u64>,
+    pub priority_request: Option<Pubkey>, // contact-gate request redeemed to prioritize it
     pub bump: u8,
 }
 
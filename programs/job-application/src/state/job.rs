@@ -33,4 +33,22 @@ pub struct JobBounty {
     pub candidate_percentage: u8,   // percentage for candidate
     pub distributed: bool,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+/// Recruiter-approved budget for contact fees paid out of a job's bounty
+/// escrow, at `[b"contact_sponsorship", job]`. Fees drawn come off the bounty
+/// (`Job::hiring_bounty`, `JobBounty::amount`). Refunds of sponsored requests
+/// land back in the bounty escrow; `close_sponsored_contact_request` adds
+/// each request's recorded refund back to the bounty and frees it from
+/// `spent`.
+#[account]
+#[derive(InitSpace)]
+pub struct ContactSponsorship {
+    pub job: Pubkey,
+    pub recruiter: Pubkey,
+    pub cap: u64,                   // Most the bounty escrow may pay in contact fees, net of refunds
+    pub spent: u64,                 // Drawn and not refunded
+    pub refunded: u64,              // Credited back from refunds so far
+    pub requests_sponsored: u32,
+    pub bump: u8,
+}
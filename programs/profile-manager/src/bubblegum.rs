@@ -0,0 +1,437 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
+};
+use solana_keccak_hasher::hashv;
+use crate::errors::ProfileManagerError;
+use crate::merkle::SPL_ACCOUNT_COMPRESSION_ID;
+
+// Minimal hand-written CPI client for mpl-bubblegum. The published SDK still
+// targets solana-program 1.x, so the instruction layouts are mirrored here.
+
+pub const MPL_BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+const CREATE_TREE_CONFIG_DISCRIMINATOR: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+const MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+const BURN_DISCRIMINATOR: [u8; 8] = [116, 110, 29, 56, 107, 219, 42, 93];
+const TREE_CONFIG_DISCRIMINATOR: [u8; 8] = [122, 245, 175, 248, 171, 34, 0, 207];
+
+// TreeConfig layout: discriminator | tree_creator | tree_delegate
+// | total_mint_capacity (u64) | num_minted (u64) | ...
+const TREE_CONFIG_NUM_MINTED_OFFSET: usize = 8 + 32 + 32 + 8;
+
+/// Leaves minted into the tree so far. Bubblegum uses this count as the
+/// nonce of the next leaf it mints.
+pub fn read_num_minted(tree_config: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(*tree_config.owner, MPL_BUBBLEGUM_ID, ProfileManagerError::InvalidTreeConfig);
+
+    let data = tree_config.try_borrow_data()?;
+    let end = TREE_CONFIG_NUM_MINTED_OFFSET + 8;
+    require!(
+        data.len() >= end && data[..8] == TREE_CONFIG_DISCRIMINATOR,
+        ProfileManagerError::InvalidTreeConfig
+    );
+
+    Ok(u64::from_le_bytes(data[TREE_CONFIG_NUM_MINTED_OFFSET..end].try_into().unwrap()))
+}
+
+#[derive(Clone)]
+pub struct Bubblegum;
+
+impl Id for Bubblegum {
+    fn id() -> Pubkey {
+        MPL_BUBBLEGUM_ID
+    }
+}
+
+#[derive(Clone)]
+pub struct SplAccountCompression;
+
+impl Id for SplAccountCompression {
+    fn id() -> Pubkey {
+        SPL_ACCOUNT_COMPRESSION_ID
+    }
+}
+
+#[derive(Clone)]
+pub struct Noop;
+
+impl Id for Noop {
+    fn id() -> Pubkey {
+        SPL_NOOP_ID
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+impl MetadataArgs {
+    /// Bubblegum's leaf data hash: keccak(keccak(args) || seller_fee_basis_points)
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        let metadata_hash = hashv(&[&self.try_to_vec()?]);
+        Ok(hashv(&[
+            metadata_hash.as_ref(),
+            &self.seller_fee_basis_points.to_le_bytes(),
+        ])
+        .to_bytes())
+    }
+
+    /// Bubblegum's leaf creator hash: keccak over (address || verified || share) per creator
+    pub fn creator_hash(&self) -> [u8; 32] {
+        let creator_data: Vec<Vec<u8>> = self
+            .creators
+            .iter()
+            .map(|c| [c.address.as_ref(), &[c.verified as u8], &[c.share]].concat())
+            .collect();
+        let slices: Vec<&[u8]> = creator_data.iter().map(|c| c.as_slice()).collect();
+        hashv(&slices).to_bytes()
+    }
+}
+
+// create_tree(max_depth: u32, max_buffer_size: u32, public: Option<bool>)
+fn create_tree_config_data(max_depth: u32, max_buffer_size: u32) -> Result<Vec<u8>> {
+    let mut data = CREATE_TREE_CONFIG_DISCRIMINATOR.to_vec();
+    (max_depth, max_buffer_size, Some(false)).serialize(&mut data)?;
+    Ok(data)
+}
+
+// mint_v1(message: MetadataArgs)
+fn mint_v1_data(metadata: &MetadataArgs) -> Result<Vec<u8>> {
+    let mut data = MINT_V1_DISCRIMINATOR.to_vec();
+    metadata.serialize(&mut data)?;
+    Ok(data)
+}
+
+// burn(root, data_hash, creator_hash, nonce: u64, index: u32)
+fn burn_data(args: &BurnArgs) -> Result<Vec<u8>> {
+    let mut data = BURN_DISCRIMINATOR.to_vec();
+    (args.root, args.data_hash, args.creator_hash, args.nonce, args.index).serialize(&mut data)?;
+    Ok(data)
+}
+
+pub struct CreateTreeConfig<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub tree_creator: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl CreateTreeConfig<'_, '_> {
+    pub fn invoke_signed(
+        &self,
+        max_depth: u32,
+        max_buffer_size: u32,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let instruction = Instruction {
+            program_id: MPL_BUBBLEGUM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tree_config.key(), false),
+                AccountMeta::new(self.merkle_tree.key(), false),
+                AccountMeta::new(self.payer.key(), true),
+                AccountMeta::new_readonly(self.tree_creator.key(), true),
+                AccountMeta::new_readonly(self.log_wrapper.key(), false),
+                AccountMeta::new_readonly(self.compression_program.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data: create_tree_config_data(max_depth, max_buffer_size)?,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.tree_config.clone(),
+                self.merkle_tree.clone(),
+                self.payer.clone(),
+                self.tree_creator.clone(),
+                self.log_wrapper.clone(),
+                self.compression_program.clone(),
+                self.system_program.clone(),
+                self.bubblegum_program.clone(),
+            ],
+            signer_seeds,
+        )
+        .map_err(Into::into)
+    }
+}
+
+pub struct MintV1<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub leaf_delegate: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub tree_creator_or_delegate: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl MintV1<'_, '_> {
+    pub fn invoke_signed(&self, metadata: &MetadataArgs, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let instruction = Instruction {
+            program_id: MPL_BUBBLEGUM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tree_config.key(), false),
+                AccountMeta::new_readonly(self.leaf_owner.key(), false),
+                AccountMeta::new_readonly(self.leaf_delegate.key(), false),
+                AccountMeta::new(self.merkle_tree.key(), false),
+                AccountMeta::new_readonly(self.payer.key(), true),
+                AccountMeta::new_readonly(self.tree_creator_or_delegate.key(), true),
+                AccountMeta::new_readonly(self.log_wrapper.key(), false),
+                AccountMeta::new_readonly(self.compression_program.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data: mint_v1_data(metadata)?,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.tree_config.clone(),
+                self.leaf_owner.clone(),
+                self.leaf_delegate.clone(),
+                self.merkle_tree.clone(),
+                self.payer.clone(),
+                self.tree_creator_or_delegate.clone(),
+                self.log_wrapper.clone(),
+                self.compression_program.clone(),
+                self.system_program.clone(),
+                self.bubblegum_program.clone(),
+            ],
+            signer_seeds,
+        )
+        .map_err(Into::into)
+    }
+}
+
+pub struct BurnArgs {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+pub struct Burn<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub leaf_delegate: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> Burn<'_, 'info> {
+    /// `proof` is the list of proof node accounts, passed through as remaining accounts.
+    pub fn invoke(&self, args: &BurnArgs, proof: &[AccountInfo<'info>]) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.tree_config.key(), false),
+            AccountMeta::new_readonly(self.leaf_owner.key(), true),
+            AccountMeta::new_readonly(self.leaf_delegate.key(), false),
+            AccountMeta::new(self.merkle_tree.key(), false),
+            AccountMeta::new_readonly(self.log_wrapper.key(), false),
+            AccountMeta::new_readonly(self.compression_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+        ];
+        accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+
+        let mut account_infos = vec![
+            self.tree_config.clone(),
+            self.leaf_owner.clone(),
+            self.leaf_delegate.clone(),
+            self.merkle_tree.clone(),
+            self.log_wrapper.clone(),
+            self.compression_program.clone(),
+            self.system_program.clone(),
+            self.bubblegum_program.clone(),
+        ];
+        account_infos.extend(proof.iter().cloned());
+
+        let instruction = Instruction {
+            program_id: MPL_BUBBLEGUM_ID,
+            accounts,
+            data: burn_data(args)?,
+        };
+
+        invoke(&instruction, &account_infos).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    fn sighash(preimage: &str) -> [u8; 8] {
+        hash(preimage.as_bytes()).to_bytes()[..8].try_into().unwrap()
+    }
+
+    #[test]
+    fn discriminators_match_bubblegum() {
+        assert_eq!(CREATE_TREE_CONFIG_DISCRIMINATOR, sighash("global:create_tree"));
+        assert_eq!(MINT_V1_DISCRIMINATOR, sighash("global:mint_v1"));
+        assert_eq!(BURN_DISCRIMINATOR, sighash("global:burn"));
+        assert_eq!(TREE_CONFIG_DISCRIMINATOR, sighash("account:TreeConfig"));
+    }
+
+    #[test]
+    fn create_tree_config_layout() {
+        let data = create_tree_config_data(14, 64).unwrap();
+        let mut expected = CREATE_TREE_CONFIG_DISCRIMINATOR.to_vec();
+        expected.extend_from_slice(&[14, 0, 0, 0, 64, 0, 0, 0, 1, 0]);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn mint_v1_layout() {
+        let creator = Pubkey::new_from_array([9; 32]);
+        let metadata = MetadataArgs {
+            name: "N".to_string(),
+            symbol: "S".to_string(),
+            uri: "u".to_string(),
+            seller_fee_basis_points: 500,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: vec![Creator { address: creator, verified: false, share: 100 }],
+        };
+
+        let mut expected = MINT_V1_DISCRIMINATOR.to_vec();
+        expected.extend_from_slice(&[1, 0, 0, 0, b'N']);
+        expected.extend_from_slice(&[1, 0, 0, 0, b'S']);
+        expected.extend_from_slice(&[1, 0, 0, 0, b'u']);
+        expected.extend_from_slice(&500u16.to_le_bytes());
+        expected.extend_from_slice(&[0, 1]); // primary_sale_happened, is_mutable
+        expected.push(0); // edition_nonce: None
+        expected.extend_from_slice(&[1, 0]); // token_standard: Some(NonFungible)
+        expected.extend_from_slice(&[0, 0]); // collection, uses: None
+        expected.push(0); // token_program_version: Original
+        expected.extend_from_slice(&[1, 0, 0, 0]);
+        expected.extend_from_slice(creator.as_ref());
+        expected.extend_from_slice(&[0, 100]);
+        assert_eq!(mint_v1_data(&metadata).unwrap(), expected);
+    }
+
+    #[test]
+    fn burn_layout() {
+        let args = BurnArgs {
+            root: [1; 32],
+            data_hash: [2; 32],
+            creator_hash: [3; 32],
+            nonce: 0x0102_0304_0506_0708,
+            index: 7,
+        };
+
+        let mut expected = BURN_DISCRIMINATOR.to_vec();
+        expected.extend_from_slice(&[1; 32]);
+        expected.extend_from_slice(&[2; 32]);
+        expected.extend_from_slice(&[3; 32]);
+        expected.extend_from_slice(&[8, 7, 6, 5, 4, 3, 2, 1]);
+        expected.extend_from_slice(&[7, 0, 0, 0]);
+        assert_eq!(burn_data(&args).unwrap(), expected);
+    }
+
+    fn tree_config_data(num_minted: u64) -> Vec<u8> {
+        let mut data = TREE_CONFIG_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[4; 32]); // tree_creator
+        data.extend_from_slice(&[5; 32]); // tree_delegate
+        data.extend_from_slice(&1024u64.to_le_bytes()); // total_mint_capacity
+        data.extend_from_slice(&num_minted.to_le_bytes());
+        data.extend_from_slice(&[0, 0]); // is_public, is_decompressible
+        data
+    }
+
+    fn with_account<T>(data: &mut [u8], owner: Pubkey, f: impl FnOnce(&AccountInfo) -> T) -> T {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, data, &owner, false, 0);
+        f(&info)
+    }
+
+    #[test]
+    fn reads_num_minted_from_tree_config() {
+        let mut data = tree_config_data(3);
+        with_account(&mut data, MPL_BUBBLEGUM_ID, |info| {
+            assert_eq!(read_num_minted(info).unwrap(), 3)
+        });
+
+        let mut data = tree_config_data(3);
+        with_account(&mut data, Pubkey::new_unique(), |info| {
+            assert!(read_num_minted(info).is_err())
+        });
+
+        let mut data = tree_config_data(3);
+        data[0] ^= 1;
+        with_account(&mut data, MPL_BUBBLEGUM_ID, |info| {
+            assert!(read_num_minted(info).is_err())
+        });
+    }
+}
@@ -18,8 +18,8 @@ pub enum ProfileManagerError {
     ContactAlreadyProcessed,
     #[msg("Contact request expired")]
     ContactExpired,
-    #[msg("Invalid USDC mint address")]
-    InvalidUSDCMint,
+    #[msg("Mint is not accepted for payments")]
+    MintNotAccepted,
     #[msg("Invalid contact status for payment")]
     InvalidContactStatus,
     #[msg("Contact request has expired")]
@@ -42,4 +42,126 @@ pub enum ProfileManagerError {
     NoResumeData,
     #[msg("Invalid resume proof")]
     InvalidResumeProof,
-}
\ No newline at end of file
+    #[msg("Handle may only contain a-z, 0-9 and _, and must start with a letter")]
+    InvalidHandleCharacters,
+    #[msg("Handle is reserved")]
+    HandleReserved,
+    #[msg("Handle is already taken")]
+    HandleTaken,
+    #[msg("Handle was recently released and is still cooling down")]
+    HandleCooldownActive,
+    #[msg("Profile already holds a handle")]
+    HandleAlreadySet,
+    #[msg("Profile does not hold a handle")]
+    NoHandle,
+    #[msg("Account is not a valid concurrent merkle tree")]
+    InvalidMerkleTree,
+    #[msg("Proof length does not match the tree depth")]
+    InvalidProofLength,
+    #[msg("Resume merkle tree has not been created for this profile")]
+    NoResumeTree,
+    #[msg("Fee exceeds the hard cap")]
+    FeeTooHigh,
+    #[msg("Signer is not the platform admin")]
+    UnauthorizedAdmin,
+    #[msg("Contact request is still pending")]
+    ContactNotResolved,
+    #[msg("This profile has blocked the sender")]
+    SenderBlocked,
+    #[msg("Contact policy list is full")]
+    ContactPolicyFull,
+    #[msg("Contact policy account does not match the profile")]
+    InvalidContactPolicy,
+    #[msg("Profile is not accepting contact requests")]
+    ProfileUnavailable,
+    #[msg("Profile has reached its limit of pending contact requests")]
+    TooManyPendingRequests,
+    #[msg("Invalid SLA payout curve")]
+    InvalidPayoutCurve,
+    #[msg("Contact request has a counter-offer awaiting the requester")]
+    CounterOfferPending,
+    #[msg("Contact request has no open counter-offer")]
+    NoCounterOffer,
+    #[msg("Counter-offer must propose a different tier")]
+    InvalidCounterOffer,
+    #[msg("Contact tiers need a price, a response window and a non-zero quota")]
+    InvalidContactTier,
+    #[msg("Too many contact tiers")]
+    TooManyContactTiers,
+    #[msg("Two contact tiers offer the same channel at the same price")]
+    DuplicateContactTier,
+    #[msg("Daily quota for this contact tier has been reached")]
+    DailyQuotaReached,
+    #[msg("Profile has not published an encryption key")]
+    NoEncryptionKey,
+    #[msg("Encryption key must be a valid X25519 public key")]
+    InvalidEncryptionKey,
+    #[msg("Message was not encrypted to the profile's current key")]
+    EncryptionKeyMismatch,
+    #[msg("Encrypted message is malformed")]
+    InvalidEncryptedMessage,
+    #[msg("Contact request has not been accepted")]
+    ContactNotAccepted,
+    #[msg("Signer is not a party to this message thread")]
+    NotThreadParticipant,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Contact request is not under dispute")]
+    ContactNotDisputed,
+    #[msg("Signer is not an eligible arbitrator")]
+    NotArbitrator,
+    #[msg("Dispute ruling exceeds the held amount")]
+    InvalidDisputeRuling,
+    #[msg("Invalid dispute window or arbitrator set")]
+    InvalidArbitrationConfig,
+    #[msg("Crank accounts are missing or do not match their contact request")]
+    InvalidCrankAccounts,
+    #[msg("Not enough contact credits")]
+    InsufficientCredits,
+    #[msg("Credit-funded contact request needs the requester's credits account")]
+    MissingContactCredits,
+    #[msg("Fund a contact request from either an escrow or contact credits")]
+    InvalidFundingSource,
+    #[msg("Campaign-funded contact request needs its outreach campaign")]
+    MissingOutreachCampaign,
+    #[msg("Outreach campaign is closed")]
+    CampaignClosed,
+    #[msg("Outreach campaign budget is exhausted")]
+    InsufficientCampaignBudget,
+    #[msg("Contact tier costs more than the campaign allows per request")]
+    CampaignPriceCapExceeded,
+    #[msg("Outreach campaign still has open requests")]
+    CampaignHasOpenRequests,
+    #[msg("Job sponsor is not the job's bounty authority")]
+    InvalidJobSponsor,
+    #[msg("Job-sponsored requests cannot be topped up")]
+    SponsoredTopUpNotAllowed,
+    #[msg("This contact tier does not offer a subscription")]
+    SubscriptionNotOffered,
+    #[msg("Subscriptions need a price, a 1-366 day period and a per-period quota")]
+    InvalidSubscriptionTerms,
+    #[msg("Subscriptions are bought for 1-12 periods at a time")]
+    InvalidSubscriptionPeriods,
+    #[msg("Subscription is cancelled or has run out")]
+    SubscriptionInactive,
+    #[msg("Subscription quota for this period is used up")]
+    SubscriptionQuotaReached,
+    #[msg("Subscription does not cover this contact tier")]
+    SubscriptionTierMismatch,
+    #[msg("Subscription still holds funds for the target")]
+    SubscriptionNotSettled,
+    #[msg("Account is not a valid Bubblegum tree config")]
+    InvalidTreeConfig,
+    #[msg("Campaign contacts do not match the account groups passed")]
+    InvalidCampaignContacts,
+    #[msg("Requests drawn on a subscription cannot be countered")]
+    SubscriptionCounterNotAllowed,
+    #[msg("Subscription still has contact requests that are not closed")]
+    SubscriptionHasOpenRequests,
+    #[msg("Free requests from allowlisted senders cannot be countered")]
+    AllowlistedCounterNotAllowed,
+    #[msg("Contact request must be closed first")]
+    ContactRequestNotClosed,
+}
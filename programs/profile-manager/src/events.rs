@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{Availability, ContactChannel, ContactStatus, FundingSource};
 
 #[event]
 pub struct ProfileCreated {
@@ -11,13 +12,41 @@ pub struct ProfileCreated {
     pub created_at: i64,
 }
 
+#[event]
+pub struct HandleClaimed {
+    pub handle: String,
+    pub profile: Pubkey,
+    pub owner: Pubkey,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct HandleReleased {
+    pub handle: String,
+    pub profile: Pubkey,
+    pub owner: Pubkey,
+    pub released_at: i64,
+}
+
+#[event]
+pub struct ResumeTreeCreated {
+    pub owner: Pubkey,
+    pub profile: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub created_at: i64,
+}
+
 #[event]
 pub struct ResumeCompressed {
     pub owner: Pubkey,
     pub profile: Pubkey,
     pub merkle_tree: Pubkey,
     pub leaf_index: u32,
-    pub data_hash: [u8; 32],
+    pub replaced_leaf_index: Option<u32>,
+    pub root_hash: [u8; 32],
+    pub data_hash: [u8; 32],           // Bubblegum data hash of the minted leaf
     pub metadata_uri: String,
     pub compressed_at: i64,
 }
@@ -30,25 +59,354 @@ pub struct ResumeAccessed {
     pub accessed_at: i64,
 }
 
+#[event]
+pub struct PlatformConfigUpdated {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub contact_fee_bps: u16,
+    pub bounty_fee_bps: u16,
+    pub marketplace_fee_bps: u16,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct ArbitrationConfigUpdated {
+    pub dispute_window_hours: u16,
+    pub arbitrators: Vec<Pubkey>,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct KeeperRewardUpdated {
+    pub keeper_reward_lamports: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct AcceptedMintUpdated {
+    pub mint: Pubkey,
+    pub is_active: bool,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct EncryptionKeyUpdated {
+    pub profile: Pubkey,
+    pub encryption_key: Option<[u8; 32]>,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct AvailabilityUpdated {
+    pub profile: Pubkey,
+    pub availability: Availability,
+    pub max_pending_requests: u16,
+    pub pending_request_count: u16,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct ContactPolicyUpdated {
+    pub profile: Pubkey,
+    pub blocked_count: u16,
+    pub allowed_count: u16,
+    pub updated_at: i64,
+}
+
 #[event]
 pub struct ContactRequestSent {
+    pub contact_request: Pubkey,
     pub requester: Pubkey,
     pub target: Pubkey,
+    pub nonce: u64,
+    pub tier_index: u8,
+    pub channel: ContactChannel,
+    pub allowlisted: bool,             // Sent for free via the target's allowlist
+    pub funding: FundingSource,
+    pub job: Option<Pubkey>,
+    pub subscription: Option<Pubkey>,
     pub amount: u64,
     pub created_at: i64,
+    pub expires_at: i64,
 }
 
 #[event]
 pub struct ContactRequestProcessed {
+    pub contact_request: Pubkey,
     pub requester: Pubkey,
     pub target: Pubkey,
     pub accepted: bool,
     pub amount: u64,
+    pub target_amount: u64,            // Paid to the target, per the tier's SLA curve
+    pub refunded_amount: u64,          // Returned to the requester
+    pub platform_fee: u64,
+    pub held_until: i64,               // End of the dispute window if the target's share is held, else 0
+    pub resolved_at: i64,
 }
 
 #[event]
 pub struct ContactRequestExpired {
+    pub contact_request: Pubkey,
     pub requester: Pubkey,
     pub target: Pubkey,
     pub amount: u64,
-}
\ No newline at end of file
+    pub platform_fee: u64,
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct ContactRequestCancelled {
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub amount: u64,
+    pub cancellation_fee: u64,         // Paid to the target
+    pub refunded: u64,
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct ContactRequestCountered {
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub tier_index: u8,                // Tier the request was sent at
+    pub counter_tier_index: u8,
+    pub amount: u64,                   // Currently escrowed
+    pub counter_amount: u64,
+    pub expires_at: i64,               // Requester's deadline to answer
+    pub countered_at: i64,
+}
+
+#[event]
+pub struct CounterOfferAccepted {
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub tier_index: u8,
+    pub target_amount: u64,            // Paid to the target
+    pub topped_up: u64,                // Added to the escrow by the requester
+    pub refunded: u64,                 // Difference returned to the requester
+    pub held_until: i64,               // End of the dispute window if the target's share is held, else 0
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct CounterOfferDeclined {
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub refunded: u64,
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct MessageThreadOpened {
+    pub message_thread: Pubkey,
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub opened_at: i64,
+}
+
+#[event]
+pub struct MessagePosted {
+    pub message_thread: Pubkey,
+    pub sender: Pubkey,
+    pub index: u32,                    // Position in the thread, from 0
+    pub message_hash: [u8; 32],
+    pub head: [u8; 32],                // Thread hash chain after this post
+    pub posted_at: i64,
+}
+
+#[event]
+pub struct MessageThreadClosed {
+    pub message_thread: Pubkey,
+    pub contact_request: Pubkey,
+    pub message_count: u32,
+    pub head: [u8; 32],                // Final hash chain, for later proofs
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct ContactPayoutReleased {
+    pub contact_request: Pubkey,
+    pub target: Pubkey,
+    pub amount: u64,
+    pub released_at: i64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub dispute: Pubkey,
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub amount: u64,
+    pub reason_hash: [u8; 32],
+    pub target_replied: bool,
+    pub opened_at: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub dispute: Pubkey,
+    pub contact_request: Pubkey,
+    pub arbitrator: Pubkey,
+    pub target_bps: u16,
+    pub target_amount: u64,
+    pub refunded_amount: u64,
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct DisputeClosed {
+    pub dispute: Pubkey,
+    pub contact_request: Pubkey,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct ExpiredContactsCranked {
+    pub keeper: Pubkey,
+    pub processed: u32,
+    pub skipped: u32,                  // Already settled or not yet expired
+    pub keeper_reward: u64,            // Lamports, total for the batch
+    pub cranked_at: i64,
+}
+
+#[event]
+pub struct ContactRequestClosed {
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub status: ContactStatus,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct ContactCreditsToppedUp {
+    pub contact_credits: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,                   // Received by the vault, net of transfer fees
+    pub balance: u64,
+    pub topped_up_at: i64,
+}
+
+#[event]
+pub struct ContactCreditsWithdrawn {
+    pub contact_credits: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub withdrawn_at: i64,
+}
+
+#[event]
+pub struct ContactCreditsDebited {
+    pub contact_credits: Pubkey,
+    pub contact_request: Pubkey,
+    pub amount: u64,                   // Reserved for the request
+    pub balance: u64,
+}
+
+#[event]
+pub struct ContactCreditsRefunded {
+    pub contact_credits: Pubkey,
+    pub contact_request: Pubkey,
+    pub amount: u64,                   // Returned to the free balance
+    pub balance: u64,
+}
+
+#[event]
+pub struct OutreachCampaignCreated {
+    pub outreach_campaign: Pubkey,
+    pub owner: Pubkey,
+    pub campaign_id: u64,
+    pub mint: Pubkey,
+    pub budget: u64,                   // Received by the vault, net of transfer fees
+    pub max_price_per_request: u64,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct CampaignContactsSpawned {
+    pub outreach_campaign: Pubkey,
+    pub owner: Pubkey,
+    pub spawned: u32,
+    pub reserved: u64,                 // Budget reserved for the batch
+    pub available: u64,                // Budget left afterwards
+    pub spawned_at: i64,
+}
+
+#[event]
+pub struct OutreachCampaignClosed {
+    pub outreach_campaign: Pubkey,
+    pub owner: Pubkey,
+    pub refunded: u64,                 // Unused budget returned to the owner
+    pub spent: u64,
+    pub sent_count: u32,
+    pub accepted_count: u32,
+    pub rejected_count: u32,
+    pub expired_count: u32,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct SubscriptionStarted {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub target: Pubkey,
+    pub tier_index: u8,
+    pub period_price: u64,
+    pub period_secs: i64,
+    pub quota: u16,
+    pub periods: u32,
+    pub deposited: u64,                // Net of transfer fees
+    pub started_at: i64,
+    pub ends_at: i64,
+}
+
+#[event]
+pub struct SubscriptionRenewed {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub periods: u32,
+    pub deposited: u64,
+    pub ends_at: i64,
+    pub renewed_at: i64,
+}
+
+#[event]
+pub struct SubscriptionPayoutReleased {
+    pub subscription: Pubkey,
+    pub target: Pubkey,
+    pub periods: u32,
+    pub amount: u64,
+    pub periods_released: u32,
+    pub released_at: i64,
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub target: Pubkey,
+    pub released: u64,                 // To the target, including `prorated`
+    pub prorated: u64,                 // Target's share of the unfinished period
+    pub refunded: u64,                 // To the subscriber
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct SubscriptionClosed {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub target: Pubkey,
+    pub requests_sent: u32,
+    pub total_released: u64,
+    pub total_refunded: u64,
+    pub closed_at: i64,
+}
@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::contact::{check_contact_target, open_contact_request, NewContactRequest};
+use super::payment::{fund_escrow, release_campaign};
+
+/// Accounts per target in `remaining_accounts`, in this order:
+/// target_profile, contact_thread, contact_request, contact_policy.
+/// The thread and request are the pair's PDAs, created here as needed. The
+/// policy slot must hold the target's `ContactPolicy` when it has one; for
+/// targets without a policy any account will do, e.g. the profile again.
+pub const SPAWN_ACCOUNTS_PER_CONTACT: usize = 4;
+
+/// One target of `spawn_campaign_contacts`, in the order of the account
+/// groups.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CampaignContact {
+    pub tier_index: u8,
+    pub message: EncryptedMessage,     // Sealed to this target's encryption key
+}
+
+/// Escrows `budget` for a bulk outreach. Requests are then sent with
+/// `spawn_campaign_contacts`, or one target at a time with
+/// `send_contact_request` passing the campaign instead of an escrow, so no
+/// further transfers are needed from the recruiter's wallet.
+pub fn create_outreach_campaign(
+    ctx: Context<CreateOutreachCampaign>,
+    campaign_id: u64,
+    budget: u64,
+    max_price_per_request: u64,
+) -> Result<()> {
+    require!(budget > 0 && max_price_per_request > 0, ProfileManagerError::InsufficientPayment);
+    let clock = Clock::get()?;
+
+    let received = fund_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.owner_token_account,
+        &mut ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        budget,
+    )?;
+
+    let campaign = &mut ctx.accounts.outreach_campaign;
+    campaign.owner = ctx.accounts.owner.key();
+    campaign.campaign_id = campaign_id;
+    campaign.mint = ctx.accounts.mint.key();
+    campaign.vault = ctx.accounts.vault.key();
+    campaign.budget = received;
+    campaign.available = received;
+    campaign.reserved = 0;
+    campaign.spent = 0;
+    campaign.max_price_per_request = max_price_per_request;
+    campaign.sent_count = 0;
+    campaign.pending_count = 0;
+    campaign.open_requests = 0;
+    campaign.accepted_count = 0;
+    campaign.rejected_count = 0;
+    campaign.expired_count = 0;
+    campaign.cancelled_count = 0;
+    campaign.declined_count = 0;
+    campaign.status = CampaignStatus::Active;
+    campaign.created_at = clock.unix_timestamp;
+    campaign.closed_at = 0;
+    campaign.bump = ctx.bumps.outreach_campaign;
+
+    emit!(OutreachCampaignCreated {
+        outreach_campaign: campaign.key(),
+        owner: campaign.owner,
+        campaign_id,
+        mint: campaign.mint,
+        budget: received,
+        max_price_per_request,
+        created_at: campaign.created_at,
+    });
+
+    Ok(())
+}
+
+/// Sends a campaign-funded request to every target in `remaining_accounts`,
+/// as `send_contact_request` would with the campaign passed. A target that
+/// turns the request away (quota, policy, price cap) fails the whole batch.
+pub fn spawn_campaign_contacts<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SpawnCampaignContacts<'info>>,
+    contacts: Vec<CampaignContact>,
+) -> Result<()> {
+    let groups = ctx.remaining_accounts.chunks_exact(SPAWN_ACCOUNTS_PER_CONTACT);
+    require!(
+        !contacts.is_empty() && groups.len() == contacts.len() && groups.remainder().is_empty(),
+        ProfileManagerError::InvalidCampaignContacts
+    );
+    require!(ctx.accounts.outreach_campaign.is_active(), ProfileManagerError::CampaignClosed);
+
+    let clock = Clock::get()?;
+    let reserved_before = ctx.accounts.outreach_campaign.reserved;
+    let spawned = contacts.len() as u32;
+    for (group, contact) in groups.zip(contacts) {
+        spawn_contact(ctx.accounts, group, contact, clock.unix_timestamp)?;
+    }
+
+    let campaign = &ctx.accounts.outreach_campaign;
+    emit!(CampaignContactsSpawned {
+        outreach_campaign: campaign.key(),
+        owner: campaign.owner,
+        spawned,
+        reserved: campaign.reserved - reserved_before,
+        available: campaign.available,
+        spawned_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn spawn_contact<'info>(
+    accounts: &mut SpawnCampaignContacts<'info>,
+    group: &'info [AccountInfo<'info>],
+    contact: CampaignContact,
+    now: i64,
+) -> Result<()> {
+    let [profile_info, thread_info, request_info, policy_info] = group else {
+        return err!(ProfileManagerError::InvalidCampaignContacts);
+    };
+
+    let mut target_profile = Account::<Profile>::try_from(profile_info)?;
+    let contact_policy = match target_profile.contact_policy {
+        Some(_) => Some(Account::<ContactPolicy>::try_from(policy_info)?),
+        None => None,
+    };
+    let requester = accounts.owner.key();
+    let (tier, allowlisted) = check_contact_target(
+        &target_profile,
+        contact_policy.as_ref(),
+        &requester,
+        &contact.message,
+        contact.tier_index,
+    )?;
+
+    // The pair's thread is created with their first request
+    let (thread_address, thread_bump) = Pubkey::find_program_address(
+        &[b"contact_thread", requester.as_ref(), profile_info.key.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(thread_info.key(), thread_address, ProfileManagerError::InvalidCampaignContacts);
+    let mut contact_thread = if thread_info.data_is_empty() {
+        create_pda(
+            accounts,
+            thread_info,
+            8 + ContactThread::INIT_SPACE,
+            &[b"contact_thread", requester.as_ref(), profile_info.key.as_ref(), &[thread_bump]],
+        )?;
+        Account::<ContactThread>::try_from_unchecked(thread_info)?
+    } else {
+        Account::<ContactThread>::try_from(thread_info)?
+    };
+
+    let nonce = contact_thread.request_count.to_le_bytes();
+    let (request_address, request_bump) = Pubkey::find_program_address(
+        &[b"contact", requester.as_ref(), profile_info.key.as_ref(), nonce.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(request_info.key(), request_address, ProfileManagerError::InvalidCampaignContacts);
+    create_pda(
+        accounts,
+        request_info,
+        8 + ContactRequest::INIT_SPACE,
+        &[b"contact", requester.as_ref(), profile_info.key.as_ref(), nonce.as_ref(), &[request_bump]],
+    )?;
+    let mut contact_request = Account::<ContactRequest>::try_from_unchecked(request_info)?;
+
+    // Same funding as `send_contact_request` with the campaign passed
+    let deposit = if allowlisted { 0 } else { tier.price };
+    let campaign = &mut accounts.outreach_campaign;
+    campaign.reserve(deposit)?;
+    campaign.record_sent();
+
+    open_contact_request(
+        &mut contact_request,
+        request_bump,
+        &mut contact_thread,
+        thread_bump,
+        &mut target_profile,
+        NewContactRequest {
+            requester,
+            message: contact.message,
+            tier_index: contact.tier_index,
+            allowlisted,
+            funding: FundingSource::Campaign,
+            vault: campaign.vault,
+            amount: deposit,
+            job: None,
+            subscription: None,
+            refund_authority: requester,
+        },
+        now,
+    )?;
+
+    // Later groups may reload these, e.g. a second request to the same target
+    contact_request.exit(&crate::ID)?;
+    contact_thread.exit(&crate::ID)?;
+    target_profile.exit(&crate::ID)
+}
+
+// Creates a program-owned PDA paid by the campaign owner, the way Anchor's
+// `init` does, including when the address was already sent lamports
+fn create_pda<'info>(
+    accounts: &SpawnCampaignContacts<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let system = accounts.system_program.to_account_info();
+    let payer = accounts.owner.to_account_info();
+    let signer = &[seeds];
+    let rent = Rent::get()?.minimum_balance(space);
+
+    let balance = account.lamports();
+    if balance == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(system, CreateAccount { from: payer, to: account.clone() }, signer),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    if balance < rent {
+        system_program::transfer(
+            CpiContext::new(system.clone(), Transfer { from: payer, to: account.clone() }),
+            rent - balance,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(system.clone(), Allocate { account_to_allocate: account.clone() }, signer),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(system, Assign { account_to_assign: account.clone() }, signer),
+        &crate::ID,
+    )
+}
+
+/// Refunds the unused budget and closes the vault. Every request the campaign
+/// funded must have been closed first, since an open one still settles
+/// against the vault; the campaign account is kept for its stats.
+pub fn close_outreach_campaign(ctx: Context<CloseOutreachCampaign>) -> Result<()> {
+    let clock = Clock::get()?;
+    let campaign = &ctx.accounts.outreach_campaign;
+
+    require!(campaign.is_active(), ProfileManagerError::CampaignClosed);
+    require!(
+        campaign.open_requests == 0 && campaign.reserved == 0,
+        ProfileManagerError::CampaignHasOpenRequests
+    );
+
+    // Anything sent to the vault directly is refunded along with the budget
+    let refunded = ctx.accounts.vault.amount;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    release_campaign(
+        &token_program,
+        &ctx.accounts.mint,
+        campaign,
+        &ctx.accounts.vault,
+        &ctx.accounts.owner_token_account,
+        refunded,
+    )?;
+
+    let campaign_id = campaign.campaign_id.to_le_bytes();
+    let seeds = &[
+        b"outreach_campaign".as_ref(),
+        campaign.owner.as_ref(),
+        campaign_id.as_ref(),
+        &[campaign.bump],
+    ];
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: campaign.to_account_info(),
+        },
+        &[&seeds[..]],
+    ))?;
+
+    let campaign = &mut ctx.accounts.outreach_campaign;
+    campaign.available = 0;
+    campaign.status = CampaignStatus::Closed;
+    campaign.closed_at = clock.unix_timestamp;
+
+    emit!(OutreachCampaignClosed {
+        outreach_campaign: campaign.key(),
+        owner: campaign.owner,
+        refunded,
+        spent: campaign.spent,
+        sent_count: campaign.sent_count,
+        accepted_count: campaign.accepted_count,
+        rejected_count: campaign.rejected_count,
+        expired_count: campaign.expired_count,
+        closed_at: campaign.closed_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u64)]
+pub struct CreateOutreachCampaign<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + OutreachCampaign::INIT_SPACE,
+        seeds = [b"outreach_campaign", owner.key().as_ref(), &campaign_id.to_le_bytes()],
+        bump
+    )]
+    pub outreach_campaign: Account<'info, OutreachCampaign>,
+
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = outreach_campaign,
+        token::token_program = token_program,
+        seeds = [b"campaign_vault", outreach_campaign.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"accepted_mint", mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        constraint = accepted_mint.is_active @ ProfileManagerError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOutreachCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [b"outreach_campaign", owner.key().as_ref(), &outreach_campaign.campaign_id.to_le_bytes()],
+        bump = outreach_campaign.bump,
+        has_one = owner,
+        has_one = vault,
+        has_one = mint
+    )]
+    pub outreach_campaign: Account<'info, OutreachCampaign>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SpawnCampaignContacts<'info> {
+    #[account(
+        mut,
+        seeds = [b"outreach_campaign", owner.key().as_ref(), &outreach_campaign.campaign_id.to_le_bytes()],
+        bump = outreach_campaign.bump,
+        has_one = owner
+    )]
+    pub outreach_campaign: Account<'info, OutreachCampaign>,
+
+    /// Sends every request and pays their rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"accepted_mint", outreach_campaign.mint.as_ref()],
+        bump = accepted_mint.bump,
+        constraint = accepted_mint.is_active @ ProfileManagerError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+
+    pub system_program: Program<'info, System>,
+}
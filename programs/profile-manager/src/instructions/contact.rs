@@ -1,50 +1,302 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use super::payment::{close_escrow, fund_escrow, release_escrow, Settlement};
+
+// See `ContactRequest` for the full lifecycle.
 
 pub fn send_contact_request(
     ctx: Context<SendContactRequest>,
-    message: String,
+    message: EncryptedMessage,
     tier_index: u8,
+    job: Option<Pubkey>, // job-application Job whose bounty escrow pays the fee
 ) -> Result<()> {
-    let contact_request = &mut ctx.accounts.contact_request;
-    let target_profile = &ctx.accounts.target_profile;
     let clock = Clock::get()?;
+    let requester_key = ctx.accounts.requester.key();
+    let (tier, allowlisted) = check_contact_target(
+        &ctx.accounts.target_profile,
+        ctx.accounts.contact_policy.as_ref(),
+        &requester_key,
+        &message,
+        tier_index,
+    )?;
 
-    require!(message.len() <= 1000, ProfileManagerError::MessageTooLong);
-    require!(!target_profile.contact_prices.is_empty(), ProfileManagerError::ContactNotAllowed);
-    require!((tier_index as usize) < target_profile.contact_prices.len(), ProfileManagerError::InvalidTierIndex);
+    // Requests covered by the requester's subscription to the target were
+    // prepaid with the period and draw on its quota; they need no escrow.
+    // Allowlisted senders are free anyway, so they leave the quota alone.
+    let subscription_vault = if allowlisted {
+        None
+    } else {
+        draw_subscription(
+            &ctx.accounts.subscription,
+            &ctx.accounts.usdc_mint.key(),
+            tier_index,
+            &tier,
+            clock.unix_timestamp,
+        )?
+    };
+
+    // Otherwise create and fund in one step, from a fresh escrow, prepaid
+    // credits or a campaign budget. The request records what the escrow
+    // received, so transfer-fee mints settle against the real balance.
+    // Allowlisted senders still get a tracked request, with nothing escrowed.
+    let deposit = if allowlisted { 0 } else { tier.price };
 
-    let price = target_profile.contact_prices[tier_index as usize].price;
-    require!(price > 0, ProfileManagerError::ContactNotAllowed);
+    // A job's bounty authority can only sign through job-application, which
+    // enforces the recruiter's spending cap; refunds go back to the bounty
+    let refund_authority = match (job, &ctx.accounts.job_sponsor) {
+        (None, None) => requester_key,
+        (Some(job), Some(sponsor)) => {
+            let (bounty_authority, _) =
+                Pubkey::find_program_address(&[b"bounty_authority", job.as_ref()], &JOB_APPLICATION_ID);
+            require_keys_eq!(sponsor.key(), bounty_authority, ProfileManagerError::InvalidJobSponsor);
+            require!(
+                subscription_vault.is_some() || ctx.accounts.escrow_token_account.is_some(),
+                ProfileManagerError::InvalidFundingSource
+            );
+            bounty_authority
+        }
+        _ => return err!(ProfileManagerError::InvalidJobSponsor),
+    };
+    let (funding, vault, received) = match (
+        subscription_vault,
+        &mut ctx.accounts.escrow_token_account,
+        &mut ctx.accounts.contact_credits,
+        &mut ctx.accounts.outreach_campaign,
+    ) {
+        (Some(vault), None, None, None) => (FundingSource::Subscription, vault, 0),
+        (None, Some(escrow), None, None) => {
+            let (from, authority) = match (&ctx.accounts.job_sponsor, &ctx.accounts.sponsor_token_account) {
+                (None, _) => (&ctx.accounts.requester_token_account, ctx.accounts.requester.to_account_info()),
+                (Some(sponsor), Some(bounty_escrow)) => (bounty_escrow, sponsor.to_account_info()),
+                (Some(_), None) => return err!(ProfileManagerError::InvalidJobSponsor),
+            };
+            let received = if deposit == 0 {
+                0
+            } else {
+                fund_escrow(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.usdc_mint,
+                    from,
+                    escrow,
+                    &authority,
+                    deposit,
+                )?
+            };
+            (FundingSource::Escrow, escrow.key(), received)
+        }
+        (None, None, Some(credits), None) => {
+            credits.reserve(deposit)?;
+            credits.requests_funded += 1;
+            credits.updated_at = clock.unix_timestamp;
+            emit!(ContactCreditsDebited {
+                contact_credits: credits.key(),
+                contact_request: ctx.accounts.contact_request.key(),
+                amount: deposit,
+                balance: credits.balance,
+            });
+            (FundingSource::Credits, credits.vault, deposit)
+        }
+        (None, None, None, Some(campaign)) => {
+            require!(campaign.is_active(), ProfileManagerError::CampaignClosed);
+            campaign.reserve(deposit)?;
+            campaign.record_sent();
+            (FundingSource::Campaign, campaign.vault, deposit)
+        }
+        _ => return err!(ProfileManagerError::InvalidFundingSource),
+    };
 
-    // Transfer USDC to escrow
-    let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.requester_token_account.to_account_info(),
-            to: ctx.accounts.escrow_token_account.to_account_info(),
-            authority: ctx.accounts.requester.to_account_info(),
+    open_contact_request(
+        &mut ctx.accounts.contact_request,
+        ctx.bumps.contact_request,
+        &mut ctx.accounts.contact_thread,
+        ctx.bumps.contact_thread,
+        &mut ctx.accounts.target_profile,
+        NewContactRequest {
+            requester: requester_key,
+            message,
+            tier_index,
+            allowlisted,
+            funding,
+            vault,
+            amount: received,
+            job,
+            subscription: subscription_vault.map(|_| ctx.accounts.subscription.key()),
+            refund_authority,
         },
+        clock.unix_timestamp,
+    )
+}
+
+/// Draws a request at `tier_index` on the requester's subscription to the
+/// target if it exists and still covers `tier` in `mint`. Returns the
+/// subscription's vault when it was drawn on.
+fn draw_subscription(
+    subscription: &AccountInfo,
+    mint: &Pubkey,
+    tier_index: u8,
+    tier: &ContactPriceTier,
+    now: i64,
+) -> Result<Option<Pubkey>> {
+    // The PDA is always passed; without a subscription it is an empty account
+    if subscription.owner != &crate::ID || subscription.data_is_empty() {
+        return Ok(None);
+    }
+    let mut data = subscription.try_borrow_mut_data()?;
+    let mut state = Subscription::try_deserialize(&mut &data[..])?;
+    if state.mint != *mint || !state.covers(tier_index, tier, now) {
+        return Ok(None);
+    }
+
+    state.draw(tier_index, tier, now)?;
+    let mut writer: &mut [u8] = &mut data;
+    state.try_serialize(&mut writer)?;
+    Ok(Some(state.vault))
+}
+
+/// Checks that `target_profile` takes requests at `tier_index` from
+/// `requester`. Returns the tier and whether the target's allowlist lets the
+/// requester in for free.
+pub(crate) fn check_contact_target(
+    target_profile: &Profile,
+    contact_policy: Option<&Account<ContactPolicy>>,
+    requester: &Pubkey,
+    message: &EncryptedMessage,
+    tier_index: u8,
+) -> Result<(ContactPriceTier, bool)> {
+    // Messages are never stored in plaintext, so the target must have a key
+    let Some(encryption_key) = target_profile.encryption_key else {
+        return err!(ProfileManagerError::NoEncryptionKey);
+    };
+    message.validate(&encryption_key)?;
+    require!(target_profile.availability != Availability::Paused, ProfileManagerError::ProfileUnavailable);
+    require!(
+        target_profile.max_pending_requests == 0
+            || target_profile.pending_request_count < target_profile.max_pending_requests,
+        ProfileManagerError::TooManyPendingRequests
     );
-    token::transfer(transfer_ctx, price)?;
-
-    contact_request.requester = ctx.accounts.requester.key();
-    contact_request.target_profile = ctx.accounts.target_profile.key();
-    contact_request.message = message;
-    contact_request.amount = price;
-    contact_request.created_at = clock.unix_timestamp;
-    contact_request.expires_at = clock.unix_timestamp + (target_profile.response_time_hours as i64 * 3600);
+    require!(!target_profile.contact_prices.is_empty(), ProfileManagerError::ContactNotAllowed);
+    require!((tier_index as usize) < target_profile.contact_prices.len(), ProfileManagerError::InvalidTierIndex);
+
+    let tier = target_profile.contact_prices[tier_index as usize].clone();
+    require!(tier.price > 0, ProfileManagerError::ContactNotAllowed);
+
+    let allowlisted = check_contact_policy(target_profile, contact_policy, requester)?;
+    Ok((tier, allowlisted))
+}
+
+/// Rejects `sender` if `target_profile` has blocked them. Returns whether
+/// the target's allowlist lets them in for free.
+pub(crate) fn check_contact_policy(
+    target_profile: &Profile,
+    contact_policy: Option<&Account<ContactPolicy>>,
+    sender: &Pubkey,
+) -> Result<bool> {
+    // A profile with a policy must have it checked; the sender cannot skip it
+    match (&target_profile.contact_policy, contact_policy) {
+        (None, _) => Ok(false),
+        (Some(expected), Some(policy)) => {
+            require_keys_eq!(policy.key(), *expected, ProfileManagerError::InvalidContactPolicy);
+            require!(!policy.is_blocked(sender), ProfileManagerError::SenderBlocked);
+            Ok(policy.is_allowed(sender))
+        }
+        (Some(_), None) => err!(ProfileManagerError::InvalidContactPolicy),
+    }
+}
+
+/// A funded request about to be written by `open_contact_request`.
+pub(crate) struct NewContactRequest {
+    pub requester: Pubkey,
+    pub message: EncryptedMessage,
+    pub tier_index: u8,
+    pub allowlisted: bool,
+    pub funding: FundingSource,
+    pub vault: Pubkey,
+    pub amount: u64,                   // What the escrow received or the funding source reserved
+    pub job: Option<Pubkey>,
+    pub subscription: Option<Pubkey>,
+    pub refund_authority: Pubkey,
+}
+
+/// Writes a new request, counts it against the target's quotas and the
+/// pair's thread, and emits `ContactRequestSent`. The thread is initialized
+/// here on the pair's first request.
+pub(crate) fn open_contact_request(
+    contact_request: &mut Account<ContactRequest>,
+    request_bump: u8,
+    contact_thread: &mut Account<ContactThread>,
+    thread_bump: u8,
+    target_profile: &mut Account<Profile>,
+    request: NewContactRequest,
+    now: i64,
+) -> Result<()> {
+    let tier_index = request.tier_index;
+    target_profile.consume_tier_quota(tier_index, now)?;
+    target_profile.pending_request_count += 1;
+    let tier = &target_profile.contact_prices[tier_index as usize];
+
+    if contact_thread.request_count == 0 {
+        contact_thread.requester = request.requester;
+        contact_thread.target_profile = target_profile.key();
+        contact_thread.created_at = now;
+        contact_thread.bump = thread_bump;
+    }
+    let nonce = contact_thread.request_count;
+    contact_thread.request_count += 1;
+    contact_thread.pending_count += 1;
+    contact_thread.last_request = contact_request.key();
+    contact_thread.last_activity_at = now;
+
+    contact_request.requester = request.requester;
+    contact_request.target_profile = target_profile.key();
+    contact_request.nonce = nonce;
+    contact_request.message = request.message;
+    contact_request.amount = request.amount;
+    contact_request.funding = request.funding;
+    contact_request.vault = request.vault;
+    contact_request.job = request.job;
+    contact_request.subscription = request.subscription;
+    contact_request.refund_authority = request.refund_authority;
+    contact_request.created_at = now;
+    contact_request.expires_at = now + (tier.response_time_hours as i64 * 3600);
+    contact_request.resolved_at = 0;
+    // Policy changes after sending do not affect this request
+    let policy = target_profile.cancellation_policy.unwrap_or(CancellationPolicy {
+        free_cancel_minutes: u16::MAX,
+        cancellation_fee_bps: 0,
+    });
+    contact_request.free_cancel_until = now + policy.free_cancel_minutes as i64 * 60;
+    contact_request.cancellation_fee_bps = policy.cancellation_fee_bps;
+    contact_request.tier_index = tier_index;
+    contact_request.counter_tier_index = 0;
+    contact_request.counter_amount = 0;
+    contact_request.payout_amount = 0;
+    contact_request.refunded = 0;
+    contact_request.dispute_deadline = 0;
+    contact_request.has_message_thread = false;
+    (contact_request.full_payout_until, contact_request.min_payout_bps) = match tier.sla {
+        Some(sla) => (now + sla.full_payout_hours as i64 * 3600, sla.min_payout_bps),
+        None => (contact_request.expires_at, BPS_DENOMINATOR as u16),
+    };
     contact_request.status = ContactStatus::Pending;
-    contact_request.bump = ctx.bumps.contact_request;
+    contact_request.bump = request_bump;
 
     emit!(ContactRequestSent {
+        contact_request: contact_request.key(),
         requester: contact_request.requester,
         target: contact_request.target_profile,
+        nonce,
+        tier_index,
+        channel: tier.channel,
+        allowlisted: request.allowlisted,
+        funding: contact_request.funding,
+        job: contact_request.job,
+        subscription: contact_request.subscription,
         amount: contact_request.amount,
         created_at: contact_request.created_at,
+        expires_at: contact_request.expires_at,
     });
 
     Ok(())
@@ -54,152 +306,582 @@ pub fn respond_to_contact(
     ctx: Context<RespondToContact>,
     accept: bool,
 ) -> Result<()> {
-    let contact_request = &mut ctx.accounts.contact_request;
     let clock = Clock::get()?;
+    let contact_request = &ctx.accounts.contact_request;
 
+    require!(contact_request.is_open(), ProfileManagerError::ContactAlreadyProcessed);
     require!(
-        contact_request.status == ContactStatus::Pending,
-        ProfileManagerError::ContactAlreadyProcessed
+        contact_request.status != ContactStatus::Countered,
+        ProfileManagerError::CounterOfferPending
     );
     require!(
         clock.unix_timestamp <= contact_request.expires_at,
         ProfileManagerError::ContactExpired
     );
 
-    let contact_key = contact_request.key();
-    let escrow_authority_bump = ctx.bumps.escrow_authority;
-    let seeds = &[
-        b"escrow",
-        contact_key.as_ref(),
-        &[escrow_authority_bump]
-    ];
-    let signer_seeds = &[&seeds[..]];
+    // Accepted requests pay the target along the tier's SLA curve and return
+    // the rest to the requester fee-free, since the delay was the target's.
+    // The platform only takes its cut from rejected deposits.
+    let dispute_window_secs = ctx.accounts.platform_config.dispute_window_secs();
+    let mut settlement = Settlement {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.usdc_mint,
+        contact_request,
+        vault: &ctx.accounts.escrow_token_account,
+        credits: ctx.accounts.contact_credits.as_mut(),
+        campaign: ctx.accounts.outreach_campaign.as_mut(),
+    };
+    let (target_amount, refunded_amount, platform_fee) = if accept {
+        let target_amount = contact_request.target_payout(clock.unix_timestamp);
+        let refunded_amount = contact_request.amount - target_amount;
+        // With a dispute window the target's share stays in escrow for now
+        if dispute_window_secs == 0 {
+            settlement.pay(&ctx.accounts.target_token_account, target_amount)?;
+        }
+        settlement.refund(&ctx.accounts.requester_token_account, refunded_amount)?;
+        (target_amount, refunded_amount, 0)
+    } else {
+        let platform_fee = settlement.refund_less_fee(
+            &ctx.accounts.requester_token_account,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.platform_config,
+        )?;
+        (0, contact_request.amount - platform_fee, platform_fee)
+    };
 
+    let contact_request = &mut ctx.accounts.contact_request;
+    contact_request.record_refund(refunded_amount);
     if accept {
-        contact_request.status = ContactStatus::Responded;
-
-        // Transfer payment to target
-        let transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow_token_account.to_account_info(),
-                to: ctx.accounts.target_token_account.to_account_info(),
-                authority: ctx.accounts.escrow_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_ctx, contact_request.amount)?;
+        contact_request.mark_accepted(target_amount, dispute_window_secs, clock.unix_timestamp);
     } else {
         contact_request.status = ContactStatus::Rejected;
-
-        // Refund to requester
-        let transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow_token_account.to_account_info(),
-                to: ctx.accounts.requester_token_account.to_account_info(),
-                authority: ctx.accounts.escrow_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_ctx, contact_request.amount)?;
+        contact_request.resolved_at = clock.unix_timestamp;
     }
+    ctx.accounts.contact_thread.record_resolution(contact_request.status, clock.unix_timestamp);
+    record_campaign_resolution(ctx.accounts.outreach_campaign.as_mut(), contact_request)?;
+    ctx.accounts.target_profile.release_pending_slot();
 
     emit!(ContactRequestProcessed {
+        contact_request: contact_request.key(),
         requester: contact_request.requester,
         target: contact_request.target_profile,
         accepted: accept,
         amount: contact_request.amount,
+        target_amount,
+        refunded_amount,
+        platform_fee,
+        held_until: contact_request.dispute_deadline,
+        resolved_at: clock.unix_timestamp,
     });
 
     Ok(())
 }
 
 pub fn handle_expired_contact(ctx: Context<HandleExpiredContact>) -> Result<()> {
-    let contact_request = &mut ctx.accounts.contact_request;
     let clock = Clock::get()?;
+    let contact_request = &ctx.accounts.contact_request;
 
-    require!(
-        contact_request.status == ContactStatus::Pending,
-        ProfileManagerError::ContactNotExpired
-    );
+    require!(contact_request.is_open(), ProfileManagerError::ContactAlreadyProcessed);
     require!(
         clock.unix_timestamp > contact_request.expires_at,
         ProfileManagerError::ContactNotExpired
     );
 
+    // Refund to requester
+    let platform_fee = Settlement {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.usdc_mint,
+        contact_request,
+        vault: &ctx.accounts.escrow_token_account,
+        credits: ctx.accounts.contact_credits.as_mut(),
+        campaign: ctx.accounts.outreach_campaign.as_mut(),
+    }
+    .refund_less_fee(
+        &ctx.accounts.requester_token_account,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.platform_config,
+    )?;
+
+    let contact_request = &mut ctx.accounts.contact_request;
+    let refunded = contact_request.amount - platform_fee;
+    contact_request.record_refund(refunded);
     contact_request.status = ContactStatus::Expired;
+    contact_request.resolved_at = clock.unix_timestamp;
+    ctx.accounts.contact_thread.record_resolution(contact_request.status, clock.unix_timestamp);
+    record_campaign_resolution(ctx.accounts.outreach_campaign.as_mut(), contact_request)?;
+    ctx.accounts.target_profile.release_pending_slot();
 
-    // Refund to requester
-    let contact_key = contact_request.key();
-    let escrow_authority_bump = ctx.bumps.escrow_authority;
-    let seeds = &[
-        b"escrow",
-        contact_key.as_ref(),
-        &[escrow_authority_bump]
-    ];
-    let signer_seeds = &[&seeds[..]];
-
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.escrow_token_account.to_account_info(),
-            to: ctx.accounts.requester_token_account.to_account_info(),
-            authority: ctx.accounts.escrow_authority.to_account_info(),
-        },
-        signer_seeds,
+    emit!(ContactRequestExpired {
+        contact_request: contact_request.key(),
+        requester: contact_request.requester,
+        target: contact_request.target_profile,
+        amount: contact_request.amount,
+        platform_fee,
+        resolved_at: contact_request.resolved_at,
+    });
+
+    Ok(())
+}
+
+pub fn cancel_contact_request(ctx: Context<CancelContactRequest>) -> Result<()> {
+    let clock = Clock::get()?;
+    let contact_request = &ctx.accounts.contact_request;
+
+    require!(contact_request.is_open(), ProfileManagerError::ContactAlreadyProcessed);
+    require!(
+        contact_request.status != ContactStatus::Countered,
+        ProfileManagerError::CounterOfferPending
+    );
+    require!(
+        clock.unix_timestamp <= contact_request.expires_at,
+        ProfileManagerError::ContactExpired
     );
-    token::transfer(transfer_ctx, contact_request.amount)?;
 
-    emit!(ContactRequestExpired {
+    let cancellation_fee = if clock.unix_timestamp <= contact_request.free_cancel_until {
+        0
+    } else {
+        PlatformConfig::fee_for(contact_request.amount, contact_request.cancellation_fee_bps)
+    };
+
+    let mut settlement = Settlement {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.usdc_mint,
+        contact_request,
+        vault: &ctx.accounts.escrow_token_account,
+        credits: ctx.accounts.contact_credits.as_mut(),
+        campaign: ctx.accounts.outreach_campaign.as_mut(),
+    };
+    settlement.pay(&ctx.accounts.target_token_account, cancellation_fee)?;
+    let refunded = contact_request.amount - cancellation_fee;
+    settlement.refund(&ctx.accounts.requester_token_account, refunded)?;
+
+    let contact_request = &mut ctx.accounts.contact_request;
+    contact_request.record_refund(refunded);
+    contact_request.status = ContactStatus::Cancelled;
+    contact_request.resolved_at = clock.unix_timestamp;
+    ctx.accounts.contact_thread.record_resolution(contact_request.status, clock.unix_timestamp);
+    record_campaign_resolution(ctx.accounts.outreach_campaign.as_mut(), contact_request)?;
+    ctx.accounts.target_profile.release_pending_slot();
+
+    emit!(ContactRequestCancelled {
+        contact_request: contact_request.key(),
+        requester: contact_request.requester,
+        target: contact_request.target_profile,
+        amount: contact_request.amount,
+        cancellation_fee,
+        refunded,
+        resolved_at: contact_request.resolved_at,
+    });
+
+    Ok(())
+}
+
+pub fn counter_contact_request(
+    ctx: Context<CounterContactRequest>,
+    tier_index: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let target_profile = &mut ctx.accounts.target_profile;
+    let contact_request = &mut ctx.accounts.contact_request;
+
+    require!(contact_request.status == ContactStatus::Pending, ProfileManagerError::ContactAlreadyProcessed);
+    require!(
+        clock.unix_timestamp <= contact_request.expires_at,
+        ProfileManagerError::ContactExpired
+    );
+    require!((tier_index as usize) < target_profile.contact_prices.len(), ProfileManagerError::InvalidTierIndex);
+    require!(tier_index != contact_request.tier_index, ProfileManagerError::InvalidCounterOffer);
+    // There is no escrow to top up or refund from
+    require!(
+        contact_request.funding != FundingSource::Subscription,
+        ProfileManagerError::SubscriptionCounterNotAllowed
+    );
+    // Allowlisted senders escrowed nothing; a counter would make them pay
+    require!(contact_request.amount > 0, ProfileManagerError::AllowlistedCounterNotAllowed);
+
+    let counter_tier = target_profile.contact_prices[tier_index as usize].clone();
+    let counter_amount = counter_tier.price;
+    require!(counter_amount > 0, ProfileManagerError::ContactNotAllowed);
+    // The counter tier's daily quota is spent as if the request were sent there
+    target_profile.consume_tier_quota(tier_index, clock.unix_timestamp)?;
+
+    // The requester gets the counter tier's response window to answer; escrow
+    // stays put until they do
+    contact_request.status = ContactStatus::Countered;
+    contact_request.counter_tier_index = tier_index;
+    contact_request.counter_amount = counter_amount;
+    contact_request.expires_at = clock.unix_timestamp + (counter_tier.response_time_hours as i64 * 3600);
+
+    emit!(ContactRequestCountered {
+        contact_request: contact_request.key(),
         requester: contact_request.requester,
         target: contact_request.target_profile,
+        tier_index: contact_request.tier_index,
+        counter_tier_index: tier_index,
         amount: contact_request.amount,
+        counter_amount,
+        expires_at: contact_request.expires_at,
+        countered_at: clock.unix_timestamp,
     });
 
     Ok(())
 }
 
+pub fn accept_counter_offer(ctx: Context<AcceptCounterOffer>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.contact_request.status == ContactStatus::Countered,
+        ProfileManagerError::NoCounterOffer
+    );
+    require!(
+        clock.unix_timestamp <= ctx.accounts.contact_request.expires_at,
+        ProfileManagerError::ContactExpired
+    );
+
+    // Top up a cheaper request first, so the target is paid out of the
+    // escrow either way. The target already agreed, so no SLA curve applies.
+    let escrowed = ctx.accounts.contact_request.amount;
+    let counter_amount = ctx.accounts.contact_request.counter_amount;
+    let topped_up = if counter_amount <= escrowed {
+        0
+    } else if ctx.accounts.contact_request.job.is_some() {
+        return err!(ProfileManagerError::SponsoredTopUpNotAllowed);
+    } else if ctx.accounts.contact_request.funding == FundingSource::Credits {
+        let top_up = counter_amount - escrowed;
+        let Some(credits) = ctx.accounts.contact_credits.as_mut() else {
+            return err!(ProfileManagerError::MissingContactCredits);
+        };
+        credits.reserve(top_up)?;
+        credits.updated_at = clock.unix_timestamp;
+        emit!(ContactCreditsDebited {
+            contact_credits: credits.key(),
+            contact_request: ctx.accounts.contact_request.key(),
+            amount: top_up,
+            balance: credits.balance,
+        });
+        top_up
+    } else if ctx.accounts.contact_request.funding == FundingSource::Campaign {
+        let top_up = counter_amount - escrowed;
+        let Some(campaign) = ctx.accounts.outreach_campaign.as_mut() else {
+            return err!(ProfileManagerError::MissingOutreachCampaign);
+        };
+        campaign.reserve(top_up)?;
+        top_up
+    } else {
+        fund_escrow(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.usdc_mint,
+            &ctx.accounts.requester_token_account,
+            &mut ctx.accounts.escrow_token_account,
+            &ctx.accounts.requester.to_account_info(),
+            counter_amount - escrowed,
+        )?
+    };
+
+    // Transfer-fee mints can leave a top-up short of the counter price
+    let total = escrowed + topped_up;
+    let target_amount = counter_amount.min(total);
+    let refunded = total - target_amount;
+
+    let dispute_window_secs = ctx.accounts.platform_config.dispute_window_secs();
+    let mut settlement = Settlement {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.usdc_mint,
+        contact_request: &ctx.accounts.contact_request,
+        vault: &ctx.accounts.escrow_token_account,
+        credits: ctx.accounts.contact_credits.as_mut(),
+        campaign: ctx.accounts.outreach_campaign.as_mut(),
+    };
+    if dispute_window_secs == 0 {
+        settlement.pay(&ctx.accounts.target_token_account, target_amount)?;
+    }
+    settlement.refund(&ctx.accounts.requester_token_account, refunded)?;
+
+    let contact_request = &mut ctx.accounts.contact_request;
+    contact_request.tier_index = contact_request.counter_tier_index;
+    contact_request.amount = total;
+    contact_request.record_refund(refunded);
+    contact_request.mark_accepted(target_amount, dispute_window_secs, clock.unix_timestamp);
+    ctx.accounts.contact_thread.record_resolution(contact_request.status, clock.unix_timestamp);
+    record_campaign_resolution(ctx.accounts.outreach_campaign.as_mut(), contact_request)?;
+    ctx.accounts.target_profile.release_pending_slot();
+
+    emit!(CounterOfferAccepted {
+        contact_request: contact_request.key(),
+        requester: contact_request.requester,
+        target: contact_request.target_profile,
+        tier_index: contact_request.tier_index,
+        target_amount,
+        topped_up,
+        refunded,
+        held_until: contact_request.dispute_deadline,
+        resolved_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn decline_counter_offer(ctx: Context<DeclineCounterOffer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let contact_request = &ctx.accounts.contact_request;
+
+    require!(contact_request.status == ContactStatus::Countered, ProfileManagerError::NoCounterOffer);
+
+    // The target changed the terms, so the requester walks away whole
+    Settlement {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.usdc_mint,
+        contact_request,
+        vault: &ctx.accounts.escrow_token_account,
+        credits: ctx.accounts.contact_credits.as_mut(),
+        campaign: ctx.accounts.outreach_campaign.as_mut(),
+    }
+    .refund(&ctx.accounts.requester_token_account, contact_request.amount)?;
+
+    let contact_request = &mut ctx.accounts.contact_request;
+    let refunded = contact_request.amount;
+    contact_request.record_refund(refunded);
+    contact_request.status = ContactStatus::Declined;
+    contact_request.resolved_at = clock.unix_timestamp;
+    ctx.accounts.contact_thread.record_resolution(contact_request.status, clock.unix_timestamp);
+    record_campaign_resolution(ctx.accounts.outreach_campaign.as_mut(), contact_request)?;
+    ctx.accounts.target_profile.release_pending_slot();
+
+    emit!(CounterOfferDeclined {
+        contact_request: contact_request.key(),
+        requester: contact_request.requester,
+        target: contact_request.target_profile,
+        refunded: contact_request.amount,
+        resolved_at: contact_request.resolved_at,
+    });
+
+    Ok(())
+}
+
+/// Pays the target's held share once the dispute window has passed without a
+/// dispute. Permissionless, so targets or keepers can settle.
+pub fn release_accepted_contact(ctx: Context<ReleaseAcceptedContact>) -> Result<()> {
+    let clock = Clock::get()?;
+    let contact_request = &ctx.accounts.contact_request;
+
+    require!(
+        contact_request.status == ContactStatus::Accepted,
+        ProfileManagerError::ContactNotAccepted
+    );
+    require!(
+        clock.unix_timestamp > contact_request.dispute_deadline,
+        ProfileManagerError::DisputeWindowOpen
+    );
+
+    Settlement {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.usdc_mint,
+        contact_request,
+        vault: &ctx.accounts.escrow_token_account,
+        credits: ctx.accounts.contact_credits.as_mut(),
+        campaign: ctx.accounts.outreach_campaign.as_mut(),
+    }
+    .pay(&ctx.accounts.target_token_account, contact_request.payout_amount)?;
+
+    let contact_request = &mut ctx.accounts.contact_request;
+    contact_request.status = ContactStatus::Responded;
+    contact_request.resolved_at = clock.unix_timestamp;
+
+    emit!(ContactPayoutReleased {
+        contact_request: contact_request.key(),
+        target: contact_request.target_profile,
+        amount: contact_request.payout_amount,
+        released_at: contact_request.resolved_at,
+    });
+
+    Ok(())
+}
+
+pub fn close_contact_request(ctx: Context<CloseContactRequest>) -> Result<()> {
+    let contact_request = &ctx.accounts.contact_request;
+
+    require!(contact_request.is_resolved(), ProfileManagerError::ContactNotResolved);
+    // The job credits the request's refund to its bounty as it closes it
+    require!(
+        contact_request.job.is_none() || ctx.accounts.job_sponsor.is_some(),
+        ProfileManagerError::InvalidJobSponsor
+    );
+
+    // Anything sent to the escrow after settlement goes back to the requester,
+    // so stray deposits cannot block the close. Credit- and campaign-funded
+    // requests have no escrow of their own, so they need no token accounts
+    // here. Campaign- and subscription-drawn requests release their hold on
+    // the shared vault, which stays open until all of them are closed.
+    if contact_request.funding == FundingSource::Campaign {
+        let Some(campaign) = ctx.accounts.outreach_campaign.as_mut() else {
+            return err!(ProfileManagerError::MissingOutreachCampaign);
+        };
+        campaign.record_closed();
+    }
+    if contact_request.funding == FundingSource::Subscription {
+        let Some(subscription) = ctx.accounts.subscription.as_mut() else {
+            return err!(ProfileManagerError::InvalidFundingSource);
+        };
+        subscription.record_closed();
+    }
+    if contact_request.funding == FundingSource::Escrow {
+        let (Some(escrow), Some(requester_token_account), Some(usdc_mint)) = (
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.requester_token_account,
+            &ctx.accounts.usdc_mint,
+        ) else {
+            return err!(ProfileManagerError::InvalidFundingSource);
+        };
+
+        let token_program = ctx.accounts.token_program.to_account_info();
+        if escrow.amount > 0 {
+            release_escrow(
+                &token_program,
+                usdc_mint,
+                contact_request,
+                escrow,
+                requester_token_account,
+                escrow.amount,
+            )?;
+        }
+
+        close_escrow(
+            &token_program,
+            contact_request,
+            escrow,
+            &ctx.accounts.requester.to_account_info(),
+        )?;
+    }
+
+    emit!(ContactRequestClosed {
+        contact_request: contact_request.key(),
+        requester: contact_request.requester,
+        target: contact_request.target_profile,
+        status: contact_request.status,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    // The request account itself is closed by the `close` constraint
+    Ok(())
+}
+
+// Campaign stats follow the resolutions of the requests it funded
+fn record_campaign_resolution(
+    campaign: Option<&mut Account<OutreachCampaign>>,
+    contact_request: &ContactRequest,
+) -> Result<()> {
+    if contact_request.funding != FundingSource::Campaign {
+        return Ok(());
+    }
+    let Some(campaign) = campaign else {
+        return err!(ProfileManagerError::MissingOutreachCampaign);
+    };
+    campaign.record_resolution(contact_request.status);
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct SendContactRequest<'info> {
+    #[account(
+        init_if_needed,
+        payer = requester,
+        space = 8 + ContactThread::INIT_SPACE,
+        seeds = [b"contact_thread", requester.key().as_ref(), target_profile.key().as_ref()],
+        bump
+    )]
+    pub contact_thread: Account<'info, ContactThread>,
+
     #[account(
         init,
         payer = requester,
         space = 8 + ContactRequest::INIT_SPACE,
-        seeds = [b"contact", requester.key().as_ref(), target_profile.key().as_ref()],
+        seeds = [
+            b"contact",
+            requester.key().as_ref(),
+            target_profile.key().as_ref(),
+            &contact_thread.request_count.to_le_bytes()
+        ],
         bump
     )]
     pub contact_request: Account<'info, ContactRequest>,
 
+    #[account(mut)]
     pub target_profile: Account<'info, Profile>,
 
+    /// Required when `target_profile.contact_policy` is set
+    pub contact_policy: Option<Account<'info, ContactPolicy>>,
+
     #[account(mut)]
     pub requester: Signer<'info>,
 
-    #[account(mut)]
-    pub requester_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = requester,
+        token::token_program = token_program
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// The job's bounty authority, signing via job-application when `job` is set
+    pub job_sponsor: Option<Signer<'info>>,
+
+    /// The job's bounty escrow, which pays the fee instead of the requester
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = job_sponsor,
+        token::token_program = token_program
+    )]
+    pub sponsor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Pass exactly one of the escrow, the requester's credits and one of
+    /// the requester's outreach campaigns, or none when the request draws on
+    /// the subscription
     #[account(
         init,
         payer = requester,
         token::mint = usdc_mint,
-        token::authority = escrow_authority,
+        token::authority = contact_request,
+        token::token_program = token_program,
         seeds = [b"escrow", contact_request.key().as_ref()],
         bump
     )]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: PDA authority for escrow
     #[account(
-        seeds = [b"escrow", contact_request.key().as_ref()],
+        mut,
+        seeds = [b"contact_credits", requester.key().as_ref(), usdc_mint.key().as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    #[account(
+        mut,
+        constraint = outreach_campaign.owner == requester.key() @ ProfileManagerError::InvalidFundingSource,
+        constraint = outreach_campaign.mint == usdc_mint.key() @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    /// CHECK: The requester's subscription to the target, which may not
+    /// exist; requests at its tier draw on it while it covers them
+    #[account(
+        mut,
+        seeds = [b"subscription", requester.key().as_ref(), target_profile.key().as_ref()],
         bump
     )]
-    pub escrow_authority: AccountInfo<'info>,
+    pub subscription: UncheckedAccount<'info>,
 
-    /// CHECK: USDC mint
-    pub usdc_mint: AccountInfo<'info>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        seeds = [b"accepted_mint", usdc_mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        constraint = accepted_mint.is_active @ ProfileManagerError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -208,13 +890,26 @@ pub struct SendContactRequest<'info> {
 pub struct RespondToContact<'info> {
     #[account(
         mut,
-        seeds = [b"contact", contact_request.requester.as_ref(), target_profile.key().as_ref()],
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            target_profile.key().as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
         bump = contact_request.bump,
         has_one = target_profile
     )]
     pub contact_request: Account<'info, ContactRequest>,
 
     #[account(
+        mut,
+        seeds = [b"contact_thread", contact_request.requester.as_ref(), contact_request.target_profile.as_ref()],
+        bump = contact_thread.bump
+    )]
+    pub contact_thread: Account<'info, ContactThread>,
+
+    #[account(
+        mut,
         seeds = [b"profile", target.key().as_ref()],
         bump = target_profile.bump,
         constraint = target_profile.owner == target.key()
@@ -223,46 +918,453 @@ pub struct RespondToContact<'info> {
 
     pub target: Signer<'info>,
 
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = contact_request.refund_authority
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = target
+    )]
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The request's escrow, or the credits or campaign vault funding it
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for credit-funded requests
+    #[account(
+        mut,
+        seeds = [b"contact_credits", contact_request.requester.as_ref(), escrow_token_account.mint.as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = platform_config.treasury
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct HandleExpiredContact<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = target_profile
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"contact_thread", contact_request.requester.as_ref(), contact_request.target_profile.as_ref()],
+        bump = contact_thread.bump
+    )]
+    pub contact_thread: Account<'info, ContactThread>,
+
     #[account(mut)]
-    pub requester_token_account: Account<'info, TokenAccount>,
+    pub target_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = contact_request.refund_authority
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The request's escrow, or the credits or campaign vault funding it
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for credit-funded requests
+    #[account(
+        mut,
+        seeds = [b"contact_credits", contact_request.requester.as_ref(), escrow_token_account.mint.as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = platform_config.treasury
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelContactRequest<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = requester,
+        has_one = target_profile
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"contact_thread", contact_request.requester.as_ref(), contact_request.target_profile.as_ref()],
+        bump = contact_thread.bump
+    )]
+    pub contact_thread: Account<'info, ContactThread>,
 
     #[account(mut)]
-    pub target_token_account: Account<'info, TokenAccount>,
+    pub target_profile: Account<'info, Profile>,
+
+    pub requester: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = contact_request.refund_authority
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = target_profile.owner
+    )]
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The request's escrow, or the credits or campaign vault funding it
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for credit-funded requests
+    #[account(
+        mut,
+        seeds = [b"contact_credits", contact_request.requester.as_ref(), escrow_token_account.mint.as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseAcceptedContact<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = target_profile
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    pub target_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = target_profile.owner
+    )]
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The request's escrow, or the credits or campaign vault funding it
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for credit-funded requests
+    #[account(
+        mut,
+        seeds = [b"contact_credits", contact_request.requester.as_ref(), escrow_token_account.mint.as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseContactRequest<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = requester,
+        close = requester
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
 
     #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub requester: Signer<'info>,
 
-    /// CHECK: PDA authority for escrow
+    /// The escrow accounts are required for escrow-funded requests only
     #[account(
-        seeds = [b"escrow", contact_request.key().as_ref()],
-        bump
+        mut,
+        token::mint = usdc_mint,
+        token::authority = contact_request.refund_authority
+    )]
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub usdc_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Required for subscription-drawn requests
+    #[account(
+        mut,
+        constraint = Some(subscription.key()) == contact_request.subscription @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    /// Required for job-sponsored requests: the job's bounty authority
+    #[account(address = contact_request.refund_authority @ ProfileManagerError::InvalidJobSponsor)]
+    pub job_sponsor: Option<Signer<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CounterContactRequest<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            target_profile.key().as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = target_profile
     )]
-    pub escrow_authority: AccountInfo<'info>,
+    pub contact_request: Account<'info, ContactRequest>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        seeds = [b"profile", target.key().as_ref()],
+        bump = target_profile.bump,
+        constraint = target_profile.owner == target.key()
+    )]
+    pub target_profile: Account<'info, Profile>,
+
+    pub target: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct HandleExpiredContact<'info> {
+pub struct AcceptCounterOffer<'info> {
     #[account(
         mut,
-        seeds = [b"contact", contact_request.requester.as_ref(), contact_request.target_profile.as_ref()],
-        bump = contact_request.bump
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = requester,
+        has_one = target_profile
     )]
     pub contact_request: Account<'info, ContactRequest>,
 
+    #[account(
+        mut,
+        seeds = [b"contact_thread", contact_request.requester.as_ref(), contact_request.target_profile.as_ref()],
+        bump = contact_thread.bump
+    )]
+    pub contact_thread: Account<'info, ContactThread>,
+
     #[account(mut)]
-    pub requester_token_account: Account<'info, TokenAccount>,
+    pub target_profile: Account<'info, Profile>,
+
+    pub requester: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = contact_request.refund_authority,
+        token::token_program = token_program
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = target_profile.owner
+    )]
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The request's escrow, or the credits or campaign vault funding it
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for credit-funded requests
+    #[account(
+        mut,
+        seeds = [b"contact_credits", contact_request.requester.as_ref(), escrow_token_account.mint.as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct DeclineCounterOffer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = requester,
+        has_one = target_profile
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"contact_thread", contact_request.requester.as_ref(), contact_request.target_profile.as_ref()],
+        bump = contact_thread.bump
+    )]
+    pub contact_thread: Account<'info, ContactThread>,
 
     #[account(mut)]
-    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub target_profile: Account<'info, Profile>,
+
+    pub requester: Signer<'info>,
 
-    /// CHECK: PDA authority for escrow
     #[account(
-        seeds = [b"escrow", contact_request.key().as_ref()],
-        bump
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = contact_request.refund_authority
     )]
-    pub escrow_authority: AccountInfo<'info>,
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The request's escrow, or the credits or campaign vault funding it
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for credit-funded requests
+    #[account(
+        mut,
+        seeds = [b"contact_credits", contact_request.requester.as_ref(), escrow_token_account.mint.as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::payment::{close_escrow, release_escrow, Settlement};
+
+/// Accounts per request in `remaining_accounts`, in this order:
+/// contact_request, contact_thread, target_profile, escrow_token_account,
+/// mint, requester_token_account, requester, treasury_token_account.
+/// For credit- or campaign-funded requests the escrow slot holds that vault
+/// and the requester token account slot holds the requester's
+/// `ContactCredits` or `OutreachCampaign`; subscription-drawn requests pass
+/// the subscription's vault and the `Subscription` the same way.
+pub const CRANK_ACCOUNTS_PER_REQUEST: usize = 8;
+
+/// Expires, refunds and closes a batch of contact requests in one go. The
+/// keeper is paid `PlatformConfig::keeper_reward_lamports` per request out of
+/// the rent reclaimed from the request and its escrow; the rest of the rent
+/// goes back to the requester. Requests that are no longer open or not yet
+/// expired (e.g. already cranked by another keeper) are skipped, as are
+/// job-sponsored ones.
+pub fn crank_expired_contacts<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CrankExpiredContacts<'info>>,
+) -> Result<()> {
+    let groups = ctx.remaining_accounts.chunks_exact(CRANK_ACCOUNTS_PER_REQUEST);
+    require!(
+        groups.len() > 0 && groups.remainder().is_empty(),
+        ProfileManagerError::InvalidCrankAccounts
+    );
+
+    let clock = Clock::get()?;
+    let mut processed: u32 = 0;
+    let mut skipped: u32 = 0;
+    let mut total_reward: u64 = 0;
+
+    for group in groups {
+        match expire_and_close(ctx.accounts, group, clock.unix_timestamp)? {
+            Some(reward) => {
+                processed += 1;
+                total_reward += reward;
+            }
+            None => skipped += 1,
+        }
+    }
+
+    emit!(ExpiredContactsCranked {
+        keeper: ctx.accounts.keeper.key(),
+        processed,
+        skipped,
+        keeper_reward: total_reward,
+        cranked_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Returns the keeper reward paid, or None if the request was skipped
+fn expire_and_close<'info>(
+    accounts: &CrankExpiredContacts<'info>,
+    group: &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<Option<u64>> {
+    let [request_info, thread_info, profile_info, escrow_info, mint_info, refund_info, requester_info, treasury_info] =
+        group
+    else {
+        return err!(ProfileManagerError::InvalidCrankAccounts);
+    };
+
+    // Closed earlier in this batch
+    if request_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let contact_request = Account::<ContactRequest>::try_from(request_info)?;
+    let nonce = contact_request.nonce.to_le_bytes();
+    let request_address = Pubkey::create_program_address(
+        &[
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            nonce.as_ref(),
+            &[contact_request.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| ProfileManagerError::InvalidCrankAccounts)?;
+    require_keys_eq!(request_address, request_info.key(), ProfileManagerError::InvalidCrankAccounts);
+
+    // Job-sponsored requests are closed by their job, which credits the
+    // refund back to the bounty; `handle_expired_contact` still settles them
+    if !contact_request.is_open() || now <= contact_request.expires_at || contact_request.job.is_some() {
+        return Ok(None);
+    }
+
+    let mut contact_thread = Account::<ContactThread>::try_from(thread_info)?;
+    require!(
+        contact_thread.requester == contact_request.requester
+            && contact_thread.target_profile == contact_request.target_profile,
+        ProfileManagerError::InvalidCrankAccounts
+    );
+    let thread_address = Pubkey::create_program_address(
+        &[
+            b"contact_thread",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &[contact_thread.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| ProfileManagerError::InvalidCrankAccounts)?;
+    require_keys_eq!(thread_address, thread_info.key(), ProfileManagerError::InvalidCrankAccounts);
+
+    let mut target_profile = Account::<Profile>::try_from(profile_info)?;
+    require_keys_eq!(target_profile.key(), contact_request.target_profile, ProfileManagerError::InvalidCrankAccounts);
+
+    // The escrow must be the one the request was funded into: closing the
+    // request with its real escrow left behind would strand the funds
+    require_keys_eq!(contact_request.vault, escrow_info.key(), ProfileManagerError::InvalidCrankAccounts);
+    require_keys_eq!(*escrow_info.owner, accounts.token_program.key(), ProfileManagerError::InvalidCrankAccounts);
+    let mut escrow = InterfaceAccount::<TokenAccount>::try_from(escrow_info)?;
+
+    let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+    require_keys_eq!(mint.key(), escrow.mint, ProfileManagerError::InvalidCrankAccounts);
+    require_keys_eq!(requester_info.key(), contact_request.requester, ProfileManagerError::InvalidCrankAccounts);
+
+    let treasury_token_account = InterfaceAccount::<TokenAccount>::try_from(treasury_info)?;
+    require!(
+        treasury_token_account.mint == escrow.mint
+            && treasury_token_account.owner == accounts.platform_config.treasury,
+        ProfileManagerError::InvalidCrankAccounts
+    );
+
+    // Same settlement as `handle_expired_contact`
+    let token_program = accounts.token_program.to_account_info();
+    let platform_fee = match contact_request.funding {
+        FundingSource::Escrow => {
+            let requester_token_account = InterfaceAccount::<TokenAccount>::try_from(refund_info)?;
+            require!(
+                requester_token_account.mint == escrow.mint
+                    && requester_token_account.owner == contact_request.refund_authority,
+                ProfileManagerError::InvalidCrankAccounts
+            );
+
+            let platform_fee = Settlement {
+                token_program: token_program.clone(),
+                mint: &mint,
+                contact_request: &contact_request,
+                vault: &escrow,
+                credits: None,
+                campaign: None,
+            }
+            .refund_less_fee(&requester_token_account, &treasury_token_account, &accounts.platform_config)?;
+
+            // Then as `close_contact_request`, sweeping stray deposits first.
+            // The escrow rent is collected on the request so it can be split
+            // below.
+            escrow.reload()?;
+            if escrow.amount > 0 {
+                release_escrow(&token_program, &mint, &contact_request, &escrow, &requester_token_account, escrow.amount)?;
+            }
+            close_escrow(&token_program, &contact_request, &escrow, request_info)?;
+            platform_fee
+        }
+        FundingSource::Credits => {
+            let mut credits = Account::<ContactCredits>::try_from(refund_info)?;
+            require!(
+                credits.owner == contact_request.requester && credits.vault == escrow_info.key(),
+                ProfileManagerError::InvalidCrankAccounts
+            );
+
+            let platform_fee = contact_request.refund_fee(accounts.platform_config.contact_fee_bps);
+            let mut settlement = Settlement {
+                token_program: token_program.clone(),
+                mint: &mint,
+                contact_request: &contact_request,
+                vault: &escrow,
+                credits: Some(&mut credits),
+                campaign: None,
+            };
+            settlement.pay(&treasury_token_account, platform_fee)?;
+            settlement.refund_reserved(contact_request.amount - platform_fee)?;
+            credits.exit(&crate::ID)?;
+            platform_fee
+        }
+        FundingSource::Campaign => {
+            let mut campaign = Account::<OutreachCampaign>::try_from(refund_info)?;
+            require_keys_eq!(campaign.vault, escrow_info.key(), ProfileManagerError::InvalidCrankAccounts);
+
+            let platform_fee = contact_request.refund_fee(accounts.platform_config.contact_fee_bps);
+            let mut settlement = Settlement {
+                token_program: token_program.clone(),
+                mint: &mint,
+                contact_request: &contact_request,
+                vault: &escrow,
+                credits: None,
+                campaign: Some(&mut campaign),
+            };
+            settlement.pay(&treasury_token_account, platform_fee)?;
+            settlement.refund_reserved(contact_request.amount - platform_fee)?;
+            campaign.record_resolution(ContactStatus::Expired);
+            campaign.record_closed();
+            campaign.exit(&crate::ID)?;
+            platform_fee
+        }
+        FundingSource::Subscription => {
+            let mut subscription = Account::<Subscription>::try_from(refund_info)?;
+            require!(
+                Some(subscription.key()) == contact_request.subscription && subscription.vault == escrow_info.key(),
+                ProfileManagerError::InvalidCrankAccounts
+            );
+
+            // Nothing was held, so there is nothing to refund or take a fee from
+            subscription.record_closed();
+            subscription.exit(&crate::ID)?;
+            0
+        }
+    };
+
+    contact_thread.record_resolution(ContactStatus::Expired, now);
+    contact_thread.exit(&crate::ID)?;
+    target_profile.release_pending_slot();
+    target_profile.exit(&crate::ID)?;
+
+    let reward = accounts.platform_config.keeper_reward_lamports.min(request_info.lamports());
+    **request_info.try_borrow_mut_lamports()? -= reward;
+    **accounts.keeper.to_account_info().try_borrow_mut_lamports()? += reward;
+    contact_request.close(requester_info.clone())?;
+
+    emit!(ContactRequestExpired {
+        contact_request: request_info.key(),
+        requester: contact_request.requester,
+        target: contact_request.target_profile,
+        amount: contact_request.amount,
+        platform_fee,
+        resolved_at: now,
+    });
+    emit!(ContactRequestClosed {
+        contact_request: request_info.key(),
+        requester: contact_request.requester,
+        target: contact_request.target_profile,
+        status: ContactStatus::Expired,
+        closed_at: now,
+    });
+
+    Ok(Some(reward))
+}
+
+#[derive(Accounts)]
+pub struct CrankExpiredContacts<'info> {
+    /// Anyone; receives the keeper reward
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Shared by every escrow in the batch
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::payment::{fund_escrow, release_credits};
+
+/// Deposits `amount` into the owner's prepaid contact credits, creating the
+/// credits account and its vault on first use.
+pub fn top_up_credits(ctx: Context<TopUpCredits>, amount: u64) -> Result<()> {
+    require!(amount > 0, ProfileManagerError::InsufficientPayment);
+    let clock = Clock::get()?;
+
+    // Credit only what the vault received, so transfer-fee mints cannot
+    // overstate the balance
+    let received = fund_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.owner_token_account,
+        &mut ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        amount,
+    )?;
+
+    let credits = &mut ctx.accounts.contact_credits;
+    if credits.created_at == 0 {
+        credits.owner = ctx.accounts.owner.key();
+        credits.mint = ctx.accounts.mint.key();
+        credits.vault = ctx.accounts.vault.key();
+        credits.created_at = clock.unix_timestamp;
+        credits.bump = ctx.bumps.contact_credits;
+    }
+    credits.balance += received;
+    credits.total_deposited += received;
+    credits.updated_at = clock.unix_timestamp;
+
+    emit!(ContactCreditsToppedUp {
+        contact_credits: credits.key(),
+        owner: credits.owner,
+        mint: credits.mint,
+        amount: received,
+        balance: credits.balance,
+        topped_up_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Withdraws `amount` of the free balance. Credits reserved by unsettled
+/// requests stay in the vault until those requests settle.
+pub fn withdraw_credits(ctx: Context<WithdrawCredits>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let credits = &ctx.accounts.contact_credits;
+    require!(amount > 0 && amount <= credits.balance, ProfileManagerError::InsufficientCredits);
+
+    release_credits(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        credits,
+        &ctx.accounts.vault,
+        &ctx.accounts.owner_token_account,
+        amount,
+    )?;
+
+    let credits = &mut ctx.accounts.contact_credits;
+    credits.balance -= amount;
+    credits.total_withdrawn += amount;
+    credits.updated_at = clock.unix_timestamp;
+
+    emit!(ContactCreditsWithdrawn {
+        contact_credits: credits.key(),
+        owner: credits.owner,
+        mint: credits.mint,
+        amount,
+        balance: credits.balance,
+        withdrawn_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TopUpCredits<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ContactCredits::INIT_SPACE,
+        seeds = [b"contact_credits", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub contact_credits: Account<'info, ContactCredits>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        token::mint = mint,
+        token::authority = contact_credits,
+        token::token_program = token_program,
+        seeds = [b"credits_vault", contact_credits.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"accepted_mint", mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        constraint = accepted_mint.is_active @ ProfileManagerError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCredits<'info> {
+    #[account(
+        mut,
+        seeds = [b"contact_credits", owner.key().as_ref(), mint.key().as_ref()],
+        bump = contact_credits.bump,
+        has_one = owner,
+        has_one = vault
+    )]
+    pub contact_credits: Account<'info, ContactCredits>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
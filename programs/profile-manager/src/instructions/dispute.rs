@@ -0,0 +1,279 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::payment::Settlement;
+
+pub fn open_dispute(
+    ctx: Context<OpenDispute>,
+    reason_hash: [u8; 32], // Hash of the requester's off-chain statement
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let contact_request = &mut ctx.accounts.contact_request;
+
+    require!(
+        contact_request.status == ContactStatus::Accepted,
+        ProfileManagerError::ContactNotAccepted
+    );
+    require!(
+        clock.unix_timestamp <= contact_request.dispute_deadline,
+        ProfileManagerError::DisputeWindowClosed
+    );
+
+    // Snapshot the conversation so the arbitrator sees what the requester saw
+    let target_replied = if contact_request.has_message_thread {
+        // The seeds pin this program's PDA, so only the discriminator needs checking
+        let data = ctx.accounts.message_thread.try_borrow_data()?;
+        MessageThread::try_deserialize(&mut &data[..])?.has_target_replied()
+    } else {
+        false
+    };
+
+    contact_request.status = ContactStatus::Disputed;
+    ctx.accounts.contact_thread.record_dispute(clock.unix_timestamp);
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.contact_request = contact_request.key();
+    dispute.requester = contact_request.requester;
+    dispute.target = ctx.accounts.target_profile.owner;
+    dispute.amount = contact_request.payout_amount;
+    dispute.reason_hash = reason_hash;
+    dispute.target_replied = target_replied;
+    dispute.opened_at = clock.unix_timestamp;
+    dispute.arbitrator = None;
+    dispute.target_bps = 0;
+    dispute.resolved_at = 0;
+    dispute.bump = ctx.bumps.dispute;
+
+    emit!(DisputeOpened {
+        dispute: dispute.key(),
+        contact_request: dispute.contact_request,
+        requester: dispute.requester,
+        target: dispute.target,
+        amount: dispute.amount,
+        reason_hash,
+        target_replied,
+        opened_at: dispute.opened_at,
+    });
+
+    Ok(())
+}
+
+pub fn resolve_dispute(
+    ctx: Context<ResolveDispute>,
+    target_bps: u16, // Share of the held amount released to the target
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let contact_request = &ctx.accounts.contact_request;
+    let dispute = &ctx.accounts.dispute;
+    let arbitrator = ctx.accounts.arbitrator.key();
+
+    require!(
+        contact_request.status == ContactStatus::Disputed,
+        ProfileManagerError::ContactNotDisputed
+    );
+    require!(
+        ctx.accounts.platform_config.is_arbitrator(&arbitrator),
+        ProfileManagerError::NotArbitrator
+    );
+    // Parties cannot rule on their own dispute
+    require!(
+        arbitrator != dispute.requester && arbitrator != dispute.target,
+        ProfileManagerError::NotArbitrator
+    );
+    require!(target_bps as u64 <= BPS_DENOMINATOR, ProfileManagerError::InvalidDisputeRuling);
+
+    let target_amount = (dispute.amount as u128 * target_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    let refunded_amount = dispute.amount - target_amount;
+
+    let mut settlement = Settlement {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.usdc_mint,
+        contact_request,
+        vault: &ctx.accounts.escrow_token_account,
+        credits: ctx.accounts.contact_credits.as_mut(),
+        campaign: ctx.accounts.outreach_campaign.as_mut(),
+    };
+    settlement.pay(&ctx.accounts.target_token_account, target_amount)?;
+    settlement.refund(&ctx.accounts.requester_token_account, refunded_amount)?;
+
+    let contact_request = &mut ctx.accounts.contact_request;
+    contact_request.status = ContactStatus::Arbitrated;
+    contact_request.payout_amount = target_amount;
+    contact_request.record_refund(refunded_amount);
+    contact_request.resolved_at = clock.unix_timestamp;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.arbitrator = Some(arbitrator);
+    dispute.target_bps = target_bps;
+    dispute.resolved_at = clock.unix_timestamp;
+
+    emit!(DisputeResolved {
+        dispute: dispute.key(),
+        contact_request: dispute.contact_request,
+        arbitrator,
+        target_bps,
+        target_amount,
+        refunded_amount,
+        resolved_at: dispute.resolved_at,
+    });
+
+    Ok(())
+}
+
+/// Closes a settled dispute once its contact request has been closed,
+/// returning the rent to the requester who opened it.
+pub fn close_dispute(ctx: Context<CloseDispute>) -> Result<()> {
+    // A request can only be closed after the arbitrator has ruled, and request
+    // PDAs are never reused
+    require!(
+        ctx.accounts.contact_request.data_is_empty(),
+        ProfileManagerError::ContactRequestNotClosed
+    );
+
+    let dispute = &ctx.accounts.dispute;
+    emit!(DisputeClosed {
+        dispute: dispute.key(),
+        contact_request: dispute.contact_request,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    // The dispute itself is closed by the `close` constraint
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", contact_request.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = requester,
+        has_one = target_profile
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"contact_thread", contact_request.requester.as_ref(), contact_request.target_profile.as_ref()],
+        bump = contact_thread.bump
+    )]
+    pub contact_thread: Account<'info, ContactThread>,
+
+    pub target_profile: Account<'info, Profile>,
+
+    /// CHECK: The request's MessageThread PDA, read only when
+    /// `contact_request.has_message_thread` says it exists
+    #[account(
+        seeds = [b"message_thread", contact_request.key().as_ref()],
+        bump
+    )]
+    pub message_thread: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute", contact_request.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    pub arbitrator: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = contact_request.refund_authority
+    )]
+    pub requester_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = escrow_token_account.mint,
+        token::authority = dispute.target
+    )]
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The request's escrow, or the requester's credits vault
+    #[account(mut, address = contact_request.vault)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Required for credit-funded requests
+    #[account(
+        mut,
+        seeds = [b"contact_credits", contact_request.requester.as_ref(), escrow_token_account.mint.as_ref()],
+        bump = contact_credits.bump
+    )]
+    pub contact_credits: Option<Account<'info, ContactCredits>>,
+
+    /// Required for campaign-funded requests
+    #[account(
+        mut,
+        constraint = outreach_campaign.vault == contact_request.vault @ ProfileManagerError::InvalidFundingSource
+    )]
+    pub outreach_campaign: Option<Account<'info, OutreachCampaign>>,
+
+    #[account(address = escrow_token_account.mint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute", dispute.contact_request.as_ref()],
+        bump = dispute.bump,
+        has_one = requester,
+        close = requester
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: Only checked to have been closed
+    #[account(address = dispute.contact_request)]
+    pub contact_request: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+pub fn claim_handle(ctx: Context<ClaimHandle>, handle: String) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let clock = Clock::get()?;
+
+    require!(profile.handle.is_empty(), ProfileManagerError::HandleAlreadySet);
+
+    assign_handle(
+        &mut ctx.accounts.handle_record,
+        profile,
+        handle,
+        ctx.bumps.handle_record,
+        clock.unix_timestamp,
+    )
+}
+
+pub fn release_handle(ctx: Context<ReleaseHandle>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    unassign_handle(
+        &mut ctx.accounts.handle_record,
+        &mut ctx.accounts.profile,
+        clock.unix_timestamp,
+    )
+}
+
+pub fn change_handle(ctx: Context<ChangeHandle>, new_handle: String) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let clock = Clock::get()?;
+
+    require!(HandleRecord::normalize(&new_handle) != profile.handle, ProfileManagerError::HandleTaken);
+
+    unassign_handle(&mut ctx.accounts.current_handle_record, profile, clock.unix_timestamp)?;
+    assign_handle(
+        &mut ctx.accounts.new_handle_record,
+        profile,
+        new_handle,
+        ctx.bumps.new_handle_record,
+        clock.unix_timestamp,
+    )
+}
+
+/// Points `record` at `profile`, normalizing the handle and enforcing the
+/// re-claim cooldown. `record` must be the PDA of the normalized handle.
+pub(crate) fn assign_handle(
+    record: &mut Account<HandleRecord>,
+    profile: &mut Account<Profile>,
+    handle: String,
+    bump: u8,
+    now: i64,
+) -> Result<()> {
+    let handle = HandleRecord::normalize(&handle);
+    HandleRecord::validate(&handle)?;
+    record.assert_claimable(&profile.owner, now)?;
+
+    record.handle = handle.clone();
+    record.profile = profile.key();
+    record.owner = profile.owner;
+    record.is_active = true;
+    record.claimed_at = now;
+    record.released_at = 0;
+    record.bump = bump;
+
+    profile.handle = handle;
+    profile.updated_at = now;
+
+    emit!(HandleClaimed {
+        handle: record.handle.clone(),
+        profile: record.profile,
+        owner: record.owner,
+        claimed_at: now,
+    });
+
+    Ok(())
+}
+
+fn unassign_handle(
+    record: &mut Account<HandleRecord>,
+    profile: &mut Account<Profile>,
+    now: i64,
+) -> Result<()> {
+    record.is_active = false;
+    record.released_at = now;
+
+    profile.handle = String::new();
+    profile.updated_at = now;
+
+    emit!(HandleReleased {
+        handle: record.handle.clone(),
+        profile: record.profile,
+        owner: record.owner,
+        released_at: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(handle: String)]
+pub struct ClaimHandle<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + HandleRecord::INIT_SPACE,
+        seeds = [b"handle", HandleRecord::normalize(&handle).as_bytes()],
+        bump
+    )]
+    pub handle_record: Account<'info, HandleRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseHandle<'info> {
+    #[account(
+        mut,
+        seeds = [b"handle", profile.handle.as_bytes()],
+        bump = handle_record.bump,
+        constraint = handle_record.is_active @ ProfileManagerError::NoHandle,
+        constraint = handle_record.profile == profile.key() @ ProfileManagerError::NoHandle
+    )]
+    pub handle_record: Account<'info, HandleRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner
+    )]
+    pub profile: Account<'info, Profile>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_handle: String)]
+pub struct ChangeHandle<'info> {
+    #[account(
+        mut,
+        seeds = [b"handle", profile.handle.as_bytes()],
+        bump = current_handle_record.bump,
+        constraint = current_handle_record.is_active @ ProfileManagerError::NoHandle,
+        constraint = current_handle_record.profile == profile.key() @ ProfileManagerError::NoHandle
+    )]
+    pub current_handle_record: Account<'info, HandleRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + HandleRecord::INIT_SPACE,
+        seeds = [b"handle", HandleRecord::normalize(&new_handle).as_bytes()],
+        bump
+    )]
+    pub new_handle_record: Account<'info, HandleRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
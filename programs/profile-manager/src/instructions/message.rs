@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+pub fn open_message_thread(ctx: Context<OpenMessageThread>) -> Result<()> {
+    let contact_request = &mut ctx.accounts.contact_request;
+    let target = ctx.accounts.target_profile.owner;
+    let clock = Clock::get()?;
+
+    require!(contact_request.was_accepted(), ProfileManagerError::ContactNotAccepted);
+
+    // Either party may open the thread and pay its rent
+    let payer = ctx.accounts.payer.key();
+    require!(
+        payer == contact_request.requester || payer == target,
+        ProfileManagerError::NotThreadParticipant
+    );
+    contact_request.has_message_thread = true;
+
+    let message_thread = &mut ctx.accounts.message_thread;
+    message_thread.contact_request = contact_request.key();
+    message_thread.requester = contact_request.requester;
+    message_thread.target = target;
+    message_thread.target_profile = contact_request.target_profile;
+    message_thread.payer = payer;
+    message_thread.message_count = 0;
+    message_thread.requester_message_count = 0;
+    message_thread.target_message_count = 0;
+    message_thread.first_target_reply_at = 0;
+    message_thread.head = [0u8; 32];
+    message_thread.opened_at = clock.unix_timestamp;
+    message_thread.last_message_at = 0;
+    message_thread.bump = ctx.bumps.message_thread;
+
+    emit!(MessageThreadOpened {
+        message_thread: message_thread.key(),
+        contact_request: message_thread.contact_request,
+        requester: message_thread.requester,
+        target: message_thread.target,
+        opened_at: message_thread.opened_at,
+    });
+
+    Ok(())
+}
+
+pub fn post_message(
+    ctx: Context<PostMessage>,
+    message_hash: [u8; 32], // Hash of the encrypted payload kept off-chain
+) -> Result<()> {
+    let message_thread = &mut ctx.accounts.message_thread;
+    let sender = ctx.accounts.sender.key();
+    let clock = Clock::get()?;
+
+    require!(message_thread.is_participant(&sender), ProfileManagerError::NotThreadParticipant);
+
+    message_thread.append(&sender, &message_hash, clock.unix_timestamp);
+
+    emit!(MessagePosted {
+        message_thread: message_thread.key(),
+        sender,
+        index: message_thread.message_count - 1,
+        message_hash,
+        head: message_thread.head,
+        posted_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Closes the thread once its contact request has been closed, returning the
+/// rent to whichever party opened it.
+pub fn close_message_thread(ctx: Context<CloseMessageThread>) -> Result<()> {
+    // Request PDAs are never reused, so an emptied one stays closed
+    require!(
+        ctx.accounts.contact_request.data_is_empty(),
+        ProfileManagerError::ContactRequestNotClosed
+    );
+
+    let message_thread = &ctx.accounts.message_thread;
+    emit!(MessageThreadClosed {
+        message_thread: message_thread.key(),
+        contact_request: message_thread.contact_request,
+        message_count: message_thread.message_count,
+        head: message_thread.head,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    // The thread itself is closed by the `close` constraint
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenMessageThread<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MessageThread::INIT_SPACE,
+        seeds = [b"message_thread", contact_request.key().as_ref()],
+        bump
+    )]
+    pub message_thread: Account<'info, MessageThread>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"contact",
+            contact_request.requester.as_ref(),
+            contact_request.target_profile.as_ref(),
+            &contact_request.nonce.to_le_bytes()
+        ],
+        bump = contact_request.bump,
+        has_one = target_profile
+    )]
+    pub contact_request: Account<'info, ContactRequest>,
+
+    pub target_profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PostMessage<'info> {
+    #[account(
+        mut,
+        seeds = [b"message_thread", message_thread.contact_request.as_ref()],
+        bump = message_thread.bump
+    )]
+    pub message_thread: Account<'info, MessageThread>,
+
+    pub sender: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMessageThread<'info> {
+    #[account(
+        mut,
+        seeds = [b"message_thread", message_thread.contact_request.as_ref()],
+        bump = message_thread.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub message_thread: Account<'info, MessageThread>,
+
+    /// CHECK: Only checked to have been closed
+    #[account(address = message_thread.contact_request)]
+    pub contact_request: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
@@ -1,11 +1,26 @@
 pub mod profile;
+pub mod handle;
 pub mod contact;
 pub mod nft;
 pub mod payment;
 pub mod resume;
+pub mod platform;
+pub mod message;
+pub mod dispute;
+pub mod crank;
+pub mod credits;
+pub mod campaign;
+pub mod subscription;
 
 pub use profile::*;
+pub use handle::*;
 pub use contact::*;
 pub use nft::*;
-pub use payment::*;
-pub use resume::*;
\ No newline at end of file
+pub use resume::*;
+pub use platform::*;
+pub use message::*;
+pub use dispute::*;
+pub use crank::*;
+pub use credits::*;
+pub use campaign::*;
+pub use subscription::*;
\ No newline at end of file
@@ -1,308 +1,329 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{transfer, Mint, Token, TokenAccount, Transfer},
-};
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TransferChecked};
 
-use crate::state::{ContactRequest, ContactStatus, Profile};
 use crate::errors::ProfileManagerError;
-
-#[derive(Accounts)]
-pub struct ProcessPayment<'info> {
-    #[account(
-        seeds = [b"profile", target_profile_owner.key().as_ref()],
-        bump = target_profile.bump,
-    )]
-    pub target_profile: Account<'info, Profile>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"contact_request",
-            requester.key().as_ref(),
-            target_profile.key().as_ref()
-        ],
-        bump = contact_request.bump,
-        has_one = requester,
-        constraint = contact_request.status == ContactStatus::Pending @ ProfileManagerError::InvalidContactStatus,
-        constraint = contact_request.expires_at > Clock::get()?.unix_timestamp @ ProfileManagerError::ContactRequestExpired,
-    )]
-    pub contact_request: Account<'info, ContactRequest>,
-
-    #[account(mut)]
-    pub requester: Signer<'info>,
-
-    /// CHECK: This is the owner of the target profile
-    pub target_profile_owner: UncheckedAccount<'info>,
-
-    // USDC Mint - using a well-known USDC mint address
-    #[account(
-        constraint = usdc_mint.key() == get_usdc_mint_pubkey() @ ProfileManagerError::InvalidUSDCMint
-    )]
-    pub usdc_mint: Account<'info, Mint>,
-
-    // Requester's USDC token account
-    #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = requester,
-    )]
-    pub requester_usdc_account: Account<'info, TokenAccount>,
-
-    // Target profile owner's USDC token account
-    #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = target_profile_owner,
-    )]
-    pub target_usdc_account: Account<'info, TokenAccount>,
-
-    // Escrow account for holding payments during processing
-    #[account(
-        init,
-        payer = requester,
-        seeds = [
-            b"escrow",
-            contact_request.key().as_ref(),
-        ],
-        bump,
-        token::mint = usdc_mint,
-        token::authority = escrow_authority,
-    )]
-    pub escrow_account: Account<'info, TokenAccount>,
-
-    /// CHECK: PDA authority for the escrow account
-    #[account(
-        seeds = [b"escrow_authority"],
-        bump
-    )]
-    pub escrow_authority: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct CompletePayment<'info> {
-    #[account(
-        mut,
-        seeds = [b"profile", target_profile_owner.key().as_ref()],
-        bump = target_profile.bump,
-        constraint = target_profile.owner == target_profile_owner.key() @ ProfileManagerError::InvalidProfileOwner
-    )]
-    pub target_profile: Account<'info, Profile>,
-
-    #[account(
-        mut,
-        seeds = [
-            b"contact_request",
-            contact_request.requester.key().as_ref(),
-            target_profile.key().as_ref()
-        ],
-        bump = contact_request.bump,
-        constraint = contact_request.status == ContactStatus::Pending @ ProfileManagerError::InvalidContactStatus,
-    )]
-    pub contact_request: Account<'info, ContactRequest>,
-
-    #[account(mut)]
-    pub target_profile_owner: Signer<'info>,
-
-    /// CHECK: This is validated through the contact_request
-    pub requester: UncheckedAccount<'info>,
-
-    // USDC Mint
-    #[account(
-        constraint = usdc_mint.key() == get_usdc_mint_pubkey() @ ProfileManagerError::InvalidUSDCMint
-    )]
-    pub usdc_mint: Account<'info, Mint>,
-
-    // Target profile owner's USDC token account
-    #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = target_profile_owner,
-    )]
-    pub target_usdc_account: Account<'info, TokenAccount>,
-
-    // Escrow account
-    #[account(
-        mut,
-        seeds = [
-            b"escrow",
-            contact_request.key().as_ref(),
-        ],
-        bump,
-        token::mint = usdc_mint,
-        token::authority = escrow_authority,
-    )]
-    pub escrow_account: Account<'info, TokenAccount>,
-
-    /// CHECK: PDA authority for the escrow account
-    #[account(
-        seeds = [b"escrow_authority"],
-        bump
-    )]
-    pub escrow_authority: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-}
-
-pub fn process_payment(ctx: Context<ProcessPayment>) -> Result<()> {
-    let contact_request = &mut ctx.accounts.contact_request;
-    let amount = contact_request.amount;
-
-    // Transfer USDC from requester to escrow
-    transfer(
+use crate::events::ContactCreditsRefunded;
+use crate::state::{ContactCredits, ContactRequest, FundingSource, OutreachCampaign, PlatformConfig, Subscription};
+
+// Escrow movements for contact requests. A request's deposit sits either in
+// its own escrow token account at [b"escrow", contact_request], owned by the
+// contact request PDA itself, or in the vault of the requester's
+// ContactCredits or OutreachCampaign, owned by that PDA. Either way only
+// instructions that load the request can move its funds. Subscription vaults
+// hold prepaid periods rather than request deposits; requests drawn on a
+// subscription record its vault but have nothing to settle.
+
+/// Moves the requester's deposit into the request's escrow and returns what
+/// the escrow actually received, which is less than `amount` for mints with a
+/// transfer fee.
+pub(crate) fn fund_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    escrow: &mut InterfaceAccount<'info, TokenAccount>,
+    requester: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<u64> {
+    let balance_before = escrow.amount;
+
+    token_interface::transfer_checked(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.requester_usdc_account.to_account_info(),
-                to: ctx.accounts.escrow_account.to_account_info(),
-                authority: ctx.accounts.requester.to_account_info(),
+            token_program.clone(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: escrow.to_account_info(),
+                authority: requester.clone(),
             },
         ),
         amount,
+        mint.decimals,
     )?;
 
-    msg!("Payment of {} USDC escrowed for contact request", amount);
-    Ok(())
+    escrow.reload()?;
+    Ok(escrow.amount - balance_before)
 }
 
-pub fn complete_payment(ctx: Context<CompletePayment>, accept: bool) -> Result<()> {
-    let contact_request = &mut ctx.accounts.contact_request;
-    let amount = contact_request.amount;
-
-    if accept {
-        // Transfer from escrow to target profile owner
-        let escrow_authority_bump = ctx.bumps.escrow_authority;
-        let escrow_seeds = &[b"escrow_authority".as_ref(), &[escrow_authority_bump]];
-        let signer_seeds = &[&escrow_seeds[..]];
-
-        transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow_account.to_account_info(),
-                    to: ctx.accounts.target_usdc_account.to_account_info(),
-                    authority: ctx.accounts.escrow_authority.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            amount,
-        )?;
-
-        contact_request.status = ContactStatus::Responded;
-        msg!("Contact request accepted - payment of {} USDC transferred", amount);
-    } else {
-        // Return payment to requester (refund)
-        // Note: This would require the requester's token account
-        // For now, we'll mark as rejected and handle refund separately
-        contact_request.status = ContactStatus::Rejected;
-        msg!("Contact request rejected - payment held in escrow for refund");
+/// Pays `amount` out of a vault owned by a program PDA.
+pub(crate) fn transfer_from_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    // Free (allowlisted or subscription) requests have nothing to move
+    if amount == 0 {
+        return Ok(());
     }
 
-    Ok(())
-}
-
-pub fn refund_payment(ctx: Context<RefundPayment>) -> Result<()> {
-    let contact_request = &ctx.accounts.contact_request;
-    let amount = contact_request.amount;
-
-    require!(
-        contact_request.status == ContactStatus::Rejected ||
-        contact_request.expires_at < Clock::get()?.unix_timestamp,
-        ProfileManagerError::CannotRefund
-    );
-
-    // Transfer from escrow back to requester
-    let escrow_authority_bump = ctx.bumps.escrow_authority;
-    let escrow_seeds = &[b"escrow_authority".as_ref(), &[escrow_authority_bump]];
-    let signer_seeds = &[&escrow_seeds[..]];
-
-    transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow_account.to_account_info(),
-                to: ctx.accounts.requester_usdc_account.to_account_info(),
-                authority: ctx.accounts.escrow_authority.to_account_info(),
+            token_program.clone(),
+            TransferChecked {
+                from: vault.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority,
             },
             signer_seeds,
         ),
         amount,
-    )?;
+        mint.decimals,
+    )
+}
+
+/// Pays `amount` out of the request's own escrow, signed by the contact
+/// request PDA.
+pub(crate) fn release_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    contact_request: &Account<'info, ContactRequest>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let nonce = contact_request.nonce.to_le_bytes();
+    let seeds = &[
+        b"contact".as_ref(),
+        contact_request.requester.as_ref(),
+        contact_request.target_profile.as_ref(),
+        nonce.as_ref(),
+        &[contact_request.bump],
+    ];
+
+    transfer_from_vault(
+        token_program,
+        mint,
+        escrow,
+        contact_request.to_account_info(),
+        &[&seeds[..]],
+        to,
+        amount,
+    )
+}
+
+/// Pays `amount` out of a credits vault, signed by the credits PDA.
+pub(crate) fn release_credits<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    credits: &Account<'info, ContactCredits>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        b"contact_credits".as_ref(),
+        credits.owner.as_ref(),
+        credits.mint.as_ref(),
+        &[credits.bump],
+    ];
+
+    transfer_from_vault(
+        token_program,
+        mint,
+        vault,
+        credits.to_account_info(),
+        &[&seeds[..]],
+        to,
+        amount,
+    )
+}
 
-    msg!("Payment of {} USDC refunded to requester", amount);
-    Ok(())
+/// Pays `amount` out of a campaign vault, signed by the campaign PDA.
+pub(crate) fn release_campaign<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    campaign: &Account<'info, OutreachCampaign>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let campaign_id = campaign.campaign_id.to_le_bytes();
+    let seeds = &[
+        b"outreach_campaign".as_ref(),
+        campaign.owner.as_ref(),
+        campaign_id.as_ref(),
+        &[campaign.bump],
+    ];
+
+    transfer_from_vault(
+        token_program,
+        mint,
+        vault,
+        campaign.to_account_info(),
+        &[&seeds[..]],
+        to,
+        amount,
+    )
 }
 
-#[derive(Accounts)]
-pub struct RefundPayment<'info> {
-    #[account(
-        seeds = [
-            b"contact_request",
-            requester.key().as_ref(),
-            target_profile.key().as_ref()
-        ],
-        bump = contact_request.bump,
-        has_one = requester,
-    )]
-    pub contact_request: Account<'info, ContactRequest>,
+/// Pays `amount` out of a subscription vault, signed by the subscription PDA.
+pub(crate) fn release_subscription<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    subscription: &Account<'info, Subscription>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        b"subscription".as_ref(),
+        subscription.subscriber.as_ref(),
+        subscription.target_profile.as_ref(),
+        &[subscription.bump],
+    ];
+
+    transfer_from_vault(
+        token_program,
+        mint,
+        vault,
+        subscription.to_account_info(),
+        &[&seeds[..]],
+        to,
+        amount,
+    )
+}
 
-    pub target_profile: Account<'info, Profile>,
+/// Settles a contact request's deposit wherever it is held. `credits` and
+/// `campaign` must be the requester's accounts for requests funded by them.
+pub(crate) struct Settlement<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub contact_request: &'a Account<'info, ContactRequest>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub credits: Option<&'a mut Account<'info, ContactCredits>>,
+    pub campaign: Option<&'a mut Account<'info, OutreachCampaign>>,
+}
 
-    #[account(mut)]
-    pub requester: Signer<'info>,
+impl<'info> Settlement<'_, 'info> {
+    fn credits(&mut self) -> Result<&mut Account<'info, ContactCredits>> {
+        match self.credits.as_deref_mut() {
+            Some(credits) => Ok(credits),
+            None => err!(ProfileManagerError::MissingContactCredits),
+        }
+    }
 
-    // USDC Mint
-    #[account(
-        constraint = usdc_mint.key() == get_usdc_mint_pubkey() @ ProfileManagerError::InvalidUSDCMint
-    )]
-    pub usdc_mint: Account<'info, Mint>,
+    fn campaign(&mut self) -> Result<&mut Account<'info, OutreachCampaign>> {
+        match self.campaign.as_deref_mut() {
+            Some(campaign) => Ok(campaign),
+            None => err!(ProfileManagerError::MissingOutreachCampaign),
+        }
+    }
 
-    // Requester's USDC token account
-    #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = requester,
-    )]
-    pub requester_usdc_account: Account<'info, TokenAccount>,
+    /// Pays `amount` of the deposit to someone other than the requester.
+    pub fn pay(&mut self, to: &InterfaceAccount<'info, TokenAccount>, amount: u64) -> Result<()> {
+        match self.contact_request.funding {
+            FundingSource::Escrow => release_escrow(
+                &self.token_program,
+                self.mint,
+                self.contact_request,
+                self.vault,
+                to,
+                amount,
+            ),
+            FundingSource::Credits => {
+                let (token_program, mint, vault) = (self.token_program.clone(), self.mint, self.vault);
+                let credits = self.credits()?;
+                release_credits(&token_program, mint, credits, vault, to, amount)?;
+                credits.spend(amount);
+                Ok(())
+            }
+            FundingSource::Campaign => {
+                let (token_program, mint, vault) = (self.token_program.clone(), self.mint, self.vault);
+                let campaign = self.campaign()?;
+                release_campaign(&token_program, mint, campaign, vault, to, amount)?;
+                campaign.spend(amount);
+                Ok(())
+            }
+            FundingSource::Subscription => {
+                require!(amount == 0, ProfileManagerError::InvalidFundingSource);
+                Ok(())
+            }
+        }
+    }
 
-    // Escrow account
-    #[account(
-        mut,
-        seeds = [
-            b"escrow",
-            contact_request.key().as_ref(),
-        ],
-        bump,
-        token::mint = usdc_mint,
-        token::authority = escrow_authority,
-    )]
-    pub escrow_account: Account<'info, TokenAccount>,
+    /// Returns `amount` of the deposit to the requester: to their token
+    /// account for escrowed requests, to the funding balance otherwise.
+    pub fn refund(&mut self, requester_token_account: &InterfaceAccount<'info, TokenAccount>, amount: u64) -> Result<()> {
+        match self.contact_request.funding {
+            FundingSource::Escrow | FundingSource::Subscription => self.pay(requester_token_account, amount),
+            FundingSource::Credits | FundingSource::Campaign => self.refund_reserved(amount),
+        }
+    }
 
-    /// CHECK: PDA authority for the escrow account
-    #[account(
-        seeds = [b"escrow_authority"],
-        bump
-    )]
-    pub escrow_authority: UncheckedAccount<'info>,
+    /// Returns `amount` of a credit- or campaign-funded deposit to the free
+    /// balance it was reserved from.
+    pub fn refund_reserved(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        match self.contact_request.funding {
+            FundingSource::Escrow | FundingSource::Subscription => err!(ProfileManagerError::InvalidFundingSource),
+            FundingSource::Credits => {
+                let contact_request = self.contact_request.key();
+                let credits = self.credits()?;
+                credits.refund(amount);
+                emit!(ContactCreditsRefunded {
+                    contact_credits: credits.key(),
+                    contact_request,
+                    amount,
+                    balance: credits.balance,
+                });
+                Ok(())
+            }
+            FundingSource::Campaign => {
+                self.campaign()?.refund(amount);
+                Ok(())
+            }
+        }
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// Refunds the deposit to the requester, less the platform's contact fee
+    /// (see `ContactRequest::refund_fee`) which goes to the treasury. Returns
+    /// the fee taken.
+    pub fn refund_less_fee(
+        &mut self,
+        requester_token_account: &InterfaceAccount<'info, TokenAccount>,
+        treasury_token_account: &InterfaceAccount<'info, TokenAccount>,
+        platform_config: &PlatformConfig,
+    ) -> Result<u64> {
+        let amount = self.contact_request.amount;
+        let platform_fee = self.contact_request.refund_fee(platform_config.contact_fee_bps);
+
+        self.pay(treasury_token_account, platform_fee)?;
+        self.refund(requester_token_account, amount - platform_fee)?;
+
+        Ok(platform_fee)
+    }
 }
 
-// Helper function to get USDC mint pubkey
-// In localnet, we'll use a mock USDC mint
-pub fn get_usdc_mint_pubkey() -> Pubkey {
-    // For localnet testing, we can use a mock USDC mint
-    // In production, this would be the actual USDC mint address
-    // Devnet USDC: 4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU
-    // Mainnet USDC: EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v
-
-    // For now, using a placeholder - should be set to actual USDC mint
-    "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU".parse().unwrap()
-}
\ No newline at end of file
+/// Closes the emptied escrow, returning its rent to `destination`.
+pub(crate) fn close_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    contact_request: &Account<'info, ContactRequest>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let nonce = contact_request.nonce.to_le_bytes();
+    let seeds = &[
+        b"contact".as_ref(),
+        contact_request.requester.as_ref(),
+        contact_request.target_profile.as_ref(),
+        nonce.as_ref(),
+        &[contact_request.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: destination.clone(),
+            authority: contact_request.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::program::ProfileManager;
+
+pub fn initialize_platform_config(
+    ctx: Context<InitializePlatformConfig>,
+    treasury: Pubkey,
+    contact_fee_bps: u16,
+    bounty_fee_bps: u16,
+    marketplace_fee_bps: u16,
+) -> Result<()> {
+    PlatformConfig::validate_fees(contact_fee_bps, bounty_fee_bps, marketplace_fee_bps)?;
+
+    let config = &mut ctx.accounts.platform_config;
+    config.admin = ctx.accounts.authority.key();
+    config.treasury = treasury;
+    config.contact_fee_bps = contact_fee_bps;
+    config.bounty_fee_bps = bounty_fee_bps;
+    config.marketplace_fee_bps = marketplace_fee_bps;
+    config.dispute_window_hours = 0;
+    config.arbitrators = Vec::new();
+    config.keeper_reward_lamports = 0;
+    config.bump = ctx.bumps.platform_config;
+
+    emit_config_updated(config)
+}
+
+pub fn update_platform_config(
+    ctx: Context<UpdatePlatformConfig>,
+    new_admin: Option<Pubkey>,
+    treasury: Option<Pubkey>,
+    contact_fee_bps: Option<u16>,
+    bounty_fee_bps: Option<u16>,
+    marketplace_fee_bps: Option<u16>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.platform_config;
+
+    let contact_fee_bps = contact_fee_bps.unwrap_or(config.contact_fee_bps);
+    let bounty_fee_bps = bounty_fee_bps.unwrap_or(config.bounty_fee_bps);
+    let marketplace_fee_bps = marketplace_fee_bps.unwrap_or(config.marketplace_fee_bps);
+    PlatformConfig::validate_fees(contact_fee_bps, bounty_fee_bps, marketplace_fee_bps)?;
+
+    if let Some(new_admin) = new_admin {
+        config.admin = new_admin;
+    }
+    if let Some(treasury) = treasury {
+        config.treasury = treasury;
+    }
+    config.contact_fee_bps = contact_fee_bps;
+    config.bounty_fee_bps = bounty_fee_bps;
+    config.marketplace_fee_bps = marketplace_fee_bps;
+
+    emit_config_updated(config)
+}
+
+pub fn set_arbitration_config(
+    ctx: Context<UpdatePlatformConfig>,
+    dispute_window_hours: u16,
+    arbitrators: Vec<Pubkey>,
+) -> Result<()> {
+    require!(dispute_window_hours <= MAX_DISPUTE_WINDOW_HOURS, ProfileManagerError::InvalidArbitrationConfig);
+    require!(arbitrators.len() <= MAX_ARBITRATORS, ProfileManagerError::InvalidArbitrationConfig);
+    // Disputes can only be opened during the window, and someone has to decide them
+    require!(
+        dispute_window_hours == 0 || !arbitrators.is_empty(),
+        ProfileManagerError::InvalidArbitrationConfig
+    );
+
+    let config = &mut ctx.accounts.platform_config;
+    config.dispute_window_hours = dispute_window_hours;
+    config.arbitrators = arbitrators;
+
+    emit!(ArbitrationConfigUpdated {
+        dispute_window_hours,
+        arbitrators: config.arbitrators.clone(),
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn set_keeper_reward(ctx: Context<UpdatePlatformConfig>, keeper_reward_lamports: u64) -> Result<()> {
+    require!(keeper_reward_lamports <= MAX_KEEPER_REWARD_LAMPORTS, ProfileManagerError::FeeTooHigh);
+
+    ctx.accounts.platform_config.keeper_reward_lamports = keeper_reward_lamports;
+
+    emit!(KeeperRewardUpdated {
+        keeper_reward_lamports,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn add_accepted_mint(ctx: Context<AddAcceptedMint>) -> Result<()> {
+    let clock = Clock::get()?;
+    let accepted_mint = &mut ctx.accounts.accepted_mint;
+
+    accepted_mint.mint = ctx.accounts.mint.key();
+    accepted_mint.decimals = ctx.accounts.mint.decimals;
+    accepted_mint.is_active = true;
+    accepted_mint.added_at = clock.unix_timestamp;
+    accepted_mint.bump = ctx.bumps.accepted_mint;
+
+    emit!(AcceptedMintUpdated {
+        mint: accepted_mint.mint,
+        is_active: true,
+        updated_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn set_accepted_mint_active(ctx: Context<SetAcceptedMintActive>, is_active: bool) -> Result<()> {
+    let accepted_mint = &mut ctx.accounts.accepted_mint;
+    accepted_mint.is_active = is_active;
+
+    emit!(AcceptedMintUpdated {
+        mint: accepted_mint.mint,
+        is_active,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn emit_config_updated(config: &PlatformConfig) -> Result<()> {
+    emit!(PlatformConfigUpdated {
+        admin: config.admin,
+        treasury: config.treasury,
+        contact_fee_bps: config.contact_fee_bps,
+        bounty_fee_bps: config.bounty_fee_bps,
+        marketplace_fee_bps: config.marketplace_fee_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializePlatformConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [b"platform_config"],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    // Only the upgrade authority can bootstrap the config
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ProfileManagerError::UnauthorizedAdmin
+    )]
+    pub program: Program<'info, ProfileManager>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ProfileManagerError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ ProfileManagerError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddAcceptedMint<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AcceptedMint::INIT_SPACE,
+        seeds = [b"accepted_mint", mint.key().as_ref()],
+        bump
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ ProfileManagerError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAcceptedMintActive<'info> {
+    #[account(
+        mut,
+        seeds = [b"accepted_mint", accepted_mint.mint.as_ref()],
+        bump = accepted_mint.bump
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ ProfileManagerError::UnauthorizedAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
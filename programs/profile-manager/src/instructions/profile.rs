@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use super::handle::assign_handle;
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_profile(
     ctx: Context<CreateProfile>,
     skills: Vec<String>,
@@ -12,7 +14,7 @@ pub fn create_profile(
     handle: String,
     contact_prices: Vec<ContactPriceTier>,
     response_time_hours: u16,
-    resume_link: Option<String>, // Optional resume link for zk-compression
+    _resume_link: Option<String>, // Optional resume link for zk-compression
 ) -> Result<()> {
     let profile = &mut ctx.accounts.profile;
     let clock = Clock::get()?;
@@ -20,8 +22,8 @@ pub fn create_profile(
     // Validation for public indexable data
     require!(skills.len() <= 10, ProfileManagerError::TooManySkills);
     require!(bio.len() <= 280, ProfileManagerError::BioTooLong);
-    require!(handle.len() >= 3 && handle.len() <= 30, ProfileManagerError::InvalidHandle);
     require!(response_time_hours > 0 && response_time_hours <= 168, ProfileManagerError::InvalidResponseTime);
+    validate_contact_prices(&contact_prices)?;
 
     // Public indexable data (searchable by Helius)
     profile.owner = ctx.accounts.owner.key();
@@ -29,14 +31,24 @@ pub fn create_profile(
     profile.experience_years = experience_years;
     profile.region = region.clone();
     profile.bio = bio;
-    profile.handle = handle.to_lowercase();
     profile.contact_prices = contact_prices;
     profile.response_time_hours = response_time_hours;
+    profile.quota_day = 0;
+    profile.tier_requests_today = [0; MAX_CONTACT_TIERS];
+    profile.cancellation_policy = None;
+    profile.contact_policy = None;
+    profile.encryption_key = None;
+    profile.availability = Availability::Open;
+    profile.max_pending_requests = 0;
+    profile.pending_request_count = 0;
 
     // Private data (will be set separately via compress_resume instruction)
     profile.resume_merkle_tree = None;
     profile.resume_leaf_index = None;
     profile.resume_root_hash = None;
+    profile.resume_leaf_data_hash = None;
+    profile.resume_leaf_nonce = None;
+    profile.resume_metadata_uri = String::new();
 
     // Metadata
     profile.is_public = true; // Default to public, can be changed later
@@ -44,13 +56,22 @@ pub fn create_profile(
     profile.updated_at = clock.unix_timestamp;
     profile.bump = ctx.bumps.profile;
 
+    // Reserve the handle so no other profile can use the same URL
+    assign_handle(
+        &mut ctx.accounts.handle_record,
+        profile,
+        handle,
+        ctx.bumps.handle_record,
+        clock.unix_timestamp,
+    )?;
+
     // Emit event for Helius indexing (only public data)
     emit!(ProfileCreated {
         owner: profile.owner,
         handle: profile.handle.clone(),
-        skills: skills,
-        region: region,
-        experience_years: experience_years,
+        skills,
+        region,
+        experience_years,
         is_public: profile.is_public,
         created_at: profile.created_at,
     });
@@ -83,7 +104,10 @@ pub fn update_profile(
     }
 
     if let Some(contact_prices) = contact_prices {
+        validate_contact_prices(&contact_prices)?;
         profile.contact_prices = contact_prices;
+        // Indices may now point at different tiers
+        profile.tier_requests_today = [0; MAX_CONTACT_TIERS];
     }
 
     if let Some(response_time_hours) = response_time_hours {
@@ -94,6 +118,118 @@ pub fn update_profile(
     Ok(())
 }
 
+fn validate_contact_prices(contact_prices: &[ContactPriceTier]) -> Result<()> {
+    require!(contact_prices.len() <= MAX_CONTACT_TIERS, ProfileManagerError::TooManyContactTiers);
+    for (i, tier) in contact_prices.iter().enumerate() {
+        tier.validate()?;
+        require!(
+            !contact_prices[..i]
+                .iter()
+                .any(|other| other.channel == tier.channel && other.price == tier.price),
+            ProfileManagerError::DuplicateContactTier
+        );
+    }
+    Ok(())
+}
+
+pub fn set_cancellation_policy(
+    ctx: Context<UpdateProfile>,
+    cancellation_policy: Option<CancellationPolicy>,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+
+    if let Some(policy) = &cancellation_policy {
+        require!(
+            policy.cancellation_fee_bps <= MAX_CANCELLATION_FEE_BPS,
+            ProfileManagerError::FeeTooHigh
+        );
+    }
+
+    profile.cancellation_policy = cancellation_policy;
+    profile.updated_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn set_encryption_key(
+    ctx: Context<UpdateProfile>,
+    encryption_key: Option<[u8; 32]>,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let clock = Clock::get()?;
+
+    // Requests already sent stay sealed to the key they were sent with
+    require!(encryption_key != Some([0u8; 32]), ProfileManagerError::InvalidEncryptionKey);
+    profile.encryption_key = encryption_key;
+    profile.updated_at = clock.unix_timestamp;
+
+    emit!(EncryptionKeyUpdated {
+        profile: profile.key(),
+        encryption_key,
+        updated_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn set_availability(
+    ctx: Context<UpdateProfile>,
+    availability: Availability,
+    max_pending_requests: u16,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let clock = Clock::get()?;
+
+    // Lowering the cap below the current count only blocks new requests;
+    // requests already pending are unaffected
+    profile.availability = availability;
+    profile.max_pending_requests = max_pending_requests;
+    profile.updated_at = clock.unix_timestamp;
+
+    emit!(AvailabilityUpdated {
+        profile: profile.key(),
+        availability,
+        max_pending_requests,
+        pending_request_count: profile.pending_request_count,
+        updated_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn update_contact_policy(
+    ctx: Context<UpdateContactPolicy>,
+    updates: Vec<ContactPolicyUpdate>,
+) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let policy = &mut ctx.accounts.contact_policy;
+    let clock = Clock::get()?;
+
+    if profile.contact_policy.is_none() {
+        policy.profile = profile.key();
+        policy.bump = ctx.bumps.contact_policy;
+        profile.contact_policy = Some(policy.key());
+    }
+
+    for update in updates {
+        match update {
+            ContactPolicyUpdate::Block(sender) => policy.block(sender)?,
+            ContactPolicyUpdate::Allow(sender) => policy.allow(sender)?,
+            ContactPolicyUpdate::Clear(sender) => policy.clear(&sender),
+        }
+    }
+    profile.updated_at = clock.unix_timestamp;
+
+    emit!(ContactPolicyUpdated {
+        profile: profile.key(),
+        blocked_count: policy.blocked.len() as u16,
+        allowed_count: policy.allowed.len() as u16,
+        updated_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(skills: Vec<String>, experience_years: u16, region: String, bio: String, handle: String)]
 pub struct CreateProfile<'info> {
@@ -106,6 +242,15 @@ pub struct CreateProfile<'info> {
     )]
     pub profile: Account<'info, Profile>,
 
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + HandleRecord::INIT_SPACE,
+        seeds = [b"handle", HandleRecord::normalize(&handle).as_bytes()],
+        bump
+    )]
+    pub handle_record: Account<'info, HandleRecord>,
+
     #[account(mut)]
     pub owner: Signer<'info>,
 
@@ -123,4 +268,29 @@ pub struct UpdateProfile<'info> {
     pub profile: Account<'info, Profile>,
 
     pub owner: Signer<'info>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct UpdateContactPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ContactPolicy::INIT_SPACE,
+        seeds = [b"contact_policy", profile.key().as_ref()],
+        bump
+    )]
+    pub contact_policy: Account<'info, ContactPolicy>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -2,11 +2,65 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use crate::merkle;
+use crate::bubblegum::{self, Bubblegum, Noop, SplAccountCompression};
 
-pub fn compress_resume(
-    ctx: Context<CompressResume>,
-    resume_data_hash: [u8; 32], // Hash of the resume link/data
-    metadata_uri: String,       // IPFS URI containing encrypted resume metadata
+pub fn create_resume_tree(
+    ctx: Context<CreateResumeTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let owner_key = ctx.accounts.owner.key();
+    let profile_key = ctx.accounts.profile.key();
+    let merkle_tree_key = ctx.accounts.merkle_tree.key();
+
+    // The profile PDA is the tree creator, so only this program can mint into it
+    let seeds = &[b"profile".as_ref(), owner_key.as_ref(), &[ctx.accounts.profile.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let profile_info = ctx.accounts.profile.to_account_info();
+    let owner_info = ctx.accounts.owner.to_account_info();
+    bubblegum::CreateTreeConfig {
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        payer: &owner_info,
+        tree_creator: &profile_info,
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .invoke_signed(max_depth, max_buffer_size, signer_seeds)?;
+
+    // A new tree starts empty; any resume in a previous tree stays there untouched
+    let profile = &mut ctx.accounts.profile;
+    profile.resume_merkle_tree = Some(merkle_tree_key);
+    profile.resume_leaf_index = None;
+    profile.resume_root_hash = None;
+    profile.resume_leaf_data_hash = None;
+    profile.resume_leaf_nonce = None;
+    profile.resume_metadata_uri = String::new();
+    profile.updated_at = clock.unix_timestamp;
+
+    emit!(ResumeTreeCreated {
+        owner: owner_key,
+        profile: profile_key,
+        merkle_tree: merkle_tree_key,
+        max_depth,
+        max_buffer_size,
+        created_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Mints the resume as a compressed NFT into the profile's tree. When the
+/// profile already has a resume leaf, it is burned first so only the latest
+/// version stays live; the proof for that leaf goes in `remaining_accounts`.
+pub fn compress_resume<'info>(
+    ctx: Context<'_, '_, '_, 'info, CompressResume<'info>>,
+    metadata_uri: String, // IPFS URI containing encrypted resume metadata
 ) -> Result<()> {
     let clock = Clock::get()?;
 
@@ -15,38 +69,96 @@ pub fn compress_resume(
     require!(metadata_uri.len() <= 200, ProfileManagerError::MetadataUriTooLong);
 
     // Store keys before mutable borrow
+    let owner_key = ctx.accounts.owner.key();
     let profile_key = ctx.accounts.profile.key();
     let merkle_tree_key = ctx.accounts.merkle_tree.key();
 
-    let profile = &mut ctx.accounts.profile;
+    let profile_info = ctx.accounts.profile.to_account_info();
+    let owner_info = ctx.accounts.owner.to_account_info();
+    let merkle_tree_info = ctx.accounts.merkle_tree.to_account_info();
+    let tree_config_info = ctx.accounts.tree_config.to_account_info();
+    let bubblegum_program_info = ctx.accounts.bubblegum_program.to_account_info();
+    let log_wrapper_info = ctx.accounts.log_wrapper.to_account_info();
+    let compression_program_info = ctx.accounts.compression_program.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+
+    let metadata = resume_metadata(owner_key, metadata_uri.clone());
+
+    // Replace the previous version, if any
+    let profile = &ctx.accounts.profile;
+    let replaced_leaf_index = match (profile.resume_leaf_index, profile.resume_leaf_nonce, profile.resume_leaf_data_hash) {
+        (Some(index), Some(nonce), Some(data_hash)) => {
+            bubblegum::Burn {
+                bubblegum_program: &bubblegum_program_info,
+                tree_config: &tree_config_info,
+                leaf_owner: &owner_info,
+                leaf_delegate: &owner_info,
+                merkle_tree: &merkle_tree_info,
+                log_wrapper: &log_wrapper_info,
+                compression_program: &compression_program_info,
+                system_program: &system_program_info,
+            }
+            .invoke(
+                &bubblegum::BurnArgs {
+                    root: merkle::read_tree_root(&merkle_tree_info)?,
+                    data_hash,
+                    creator_hash: metadata.creator_hash(),
+                    nonce,
+                    index,
+                },
+                ctx.remaining_accounts,
+            )?;
+            Some(index)
+        }
+        _ => None,
+    };
 
-    // TODO: Implement zk-compressed NFT creation using mpl-bubblegum
-    // For now, we'll store the metadata references
+    // Bubblegum assigns the tree config's mint count as the new leaf's nonce
+    let nonce = bubblegum::read_num_minted(&tree_config_info)?;
 
-    // Calculate next leaf index (in real implementation, this would come from the merkle tree)
-    let next_leaf_index = 0u32; // Placeholder - should be calculated dynamically
+    let seeds = &[b"profile".as_ref(), owner_key.as_ref(), &[ctx.accounts.profile.bump]];
+    let signer_seeds = &[&seeds[..]];
 
-    // Calculate new root hash (in real implementation, this would be computed from merkle tree)
-    let mut new_root_hash = [0u8; 32];
-    new_root_hash[..32].copy_from_slice(&resume_data_hash[..32]);
+    bubblegum::MintV1 {
+        bubblegum_program: &bubblegum_program_info,
+        tree_config: &tree_config_info,
+        leaf_owner: &owner_info,
+        leaf_delegate: &owner_info,
+        merkle_tree: &merkle_tree_info,
+        payer: &owner_info,
+        tree_creator_or_delegate: &profile_info,
+        log_wrapper: &log_wrapper_info,
+        compression_program: &compression_program_info,
+        system_program: &system_program_info,
+    }
+    .invoke_signed(&metadata, signer_seeds)?;
+
+    // Read the true position and root back from the tree
+    let leaf_index = merkle::read_tree_leaf_count(&merkle_tree_info)?
+        .checked_sub(1)
+        .ok_or(ProfileManagerError::InvalidMerkleTree)?;
+    let root_hash = merkle::read_tree_root(&merkle_tree_info)?;
 
     // Update profile with compressed resume information
-    profile.resume_merkle_tree = Some(merkle_tree_key);
-    profile.resume_leaf_index = Some(next_leaf_index);
-    profile.resume_root_hash = Some(new_root_hash);
+    let data_hash = metadata.data_hash()?;
+    let profile = &mut ctx.accounts.profile;
+    profile.resume_leaf_index = Some(leaf_index);
+    profile.resume_root_hash = Some(root_hash);
+    profile.resume_leaf_data_hash = Some(data_hash);
+    profile.resume_leaf_nonce = Some(nonce);
+    profile.resume_metadata_uri = metadata_uri.clone();
     profile.updated_at = clock.unix_timestamp;
 
-    // Store values for event emission
-    let profile_owner = profile.owner;
-
     // Emit event for resume compression
     emit!(ResumeCompressed {
-        owner: profile_owner,
+        owner: owner_key,
         profile: profile_key,
         merkle_tree: merkle_tree_key,
-        leaf_index: next_leaf_index,
-        data_hash: resume_data_hash,
-        metadata_uri: metadata_uri,
+        leaf_index,
+        replaced_leaf_index,
+        root_hash,
+        data_hash,
+        metadata_uri,
         compressed_at: clock.unix_timestamp,
     });
 
@@ -55,34 +167,60 @@ pub fn compress_resume(
     Ok(())
 }
 
+// Metadata for a resume cNFT. Must stay stable: the burn on re-compression
+// recomputes the creator hash from it.
+fn resume_metadata(owner: Pubkey, uri: String) -> bubblegum::MetadataArgs {
+    bubblegum::MetadataArgs {
+        name: "CardPass Resume".to_string(),
+        symbol: "RESUME".to_string(),
+        uri,
+        seller_fee_basis_points: 0,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(bubblegum::TokenStandard::NonFungible),
+        collection: None,
+        uses: None,
+        token_program_version: bubblegum::TokenProgramVersion::Original,
+        creators: vec![bubblegum::Creator {
+            address: owner,
+            verified: false,
+            share: 100,
+        }],
+    }
+}
+
+/// Checks `leaf` at the resume's index against the tree's current root, so
+/// the proof must be fetched from an indexer after the tree's latest change.
+/// `Profile::resume_root_hash` is only the root right after compression and
+/// goes stale once the tree changes again.
 pub fn verify_resume_access(
     ctx: Context<VerifyResumeAccess>,
-    merkle_proof: Vec<[u8; 32]>, // Merkle proof for verification
+    merkle_proof: Vec<[u8; 32]>, // Sibling nodes from leaf to root
+    leaf: [u8; 32],              // Leaf hash as stored in the tree
 ) -> Result<String> {
     let profile = &ctx.accounts.profile;
 
     // Verify that resume exists
-    require!(profile.resume_merkle_tree.is_some(), ProfileManagerError::NoResumeData);
-    require!(profile.resume_leaf_index.is_some(), ProfileManagerError::NoResumeData);
-    require!(profile.resume_root_hash.is_some(), ProfileManagerError::NoResumeData);
-
-    // TODO: Implement merkle proof verification
-    // For now, we'll just return a success message
+    let Some(leaf_index) = profile.resume_leaf_index else {
+        return err!(ProfileManagerError::NoResumeData);
+    };
+    require!(!profile.resume_metadata_uri.is_empty(), ProfileManagerError::NoResumeData);
 
-    // Verify merkle proof (placeholder implementation)
-    let is_valid = verify_merkle_proof(
+    // Proofs must cover the full depth of the tree the resume lives in
+    let merkle_tree_info = ctx.accounts.merkle_tree.to_account_info();
+    let tree_header = merkle::read_tree_header(&merkle_tree_info)?;
+    let root_hash = merkle::read_tree_root(&merkle_tree_info)?;
+    merkle::verify_proof(
+        &leaf,
         &merkle_proof,
-        &profile.resume_root_hash.unwrap(),
-        profile.resume_leaf_index.unwrap(),
-    );
-
-    require!(is_valid, ProfileManagerError::InvalidResumeProof);
+        &root_hash,
+        leaf_index,
+        tree_header.max_depth,
+    )?;
 
     // Return metadata URI for accessing the resume
-    let metadata_uri = format!("ipfs://resume-{}-{}",
-        profile.owner.to_string(),
-        profile.resume_leaf_index.unwrap()
-    );
+    let metadata_uri = profile.resume_metadata_uri.clone();
 
     emit!(ResumeAccessed {
         requester: ctx.accounts.requester.key(),
@@ -94,15 +232,36 @@ pub fn verify_resume_access(
     Ok(metadata_uri)
 }
 
-// Placeholder function for merkle proof verification
-fn verify_merkle_proof(
-    proof: &Vec<[u8; 32]>,
-    root: &[u8; 32],
-    leaf_index: u32,
-) -> bool {
-    // TODO: Implement actual merkle proof verification
-    // This is a placeholder that always returns true for development
-    true
+#[derive(Accounts)]
+pub struct CreateResumeTree<'info> {
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Pre-allocated tree account, initialized by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config PDA, initialized by mpl-bubblegum
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -111,25 +270,30 @@ pub struct CompressResume<'info> {
         mut,
         seeds = [b"profile", owner.key().as_ref()],
         bump = profile.bump,
-        has_one = owner
+        has_one = owner,
+        constraint = profile.resume_merkle_tree == Some(merkle_tree.key()) @ ProfileManagerError::NoResumeTree
     )]
     pub profile: Account<'info, Profile>,
 
     #[account(mut)]
     pub owner: Signer<'info>,
 
-    /// CHECK: Merkle tree for compressed resume storage
-    /// In real implementation, this would be created via mpl-bubblegum
+    /// CHECK: Profile's resume tree, owned by spl-account-compression
     #[account(mut)]
     pub merkle_tree: UncheckedAccount<'info>,
 
-    /// CHECK: Tree config account for mpl-bubblegum
+    /// CHECK: Bubblegum tree config PDA
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
     pub tree_config: UncheckedAccount<'info>,
 
-    // TODO: Add compression program and log wrapper when implementing mpl-bubblegum
-    // pub compression_program: Program<'info, SplAccountCompression>,
-    // pub log_wrapper: Program<'info, Noop>,
-
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
     pub system_program: Program<'info, System>,
 }
 
@@ -143,6 +307,9 @@ pub struct VerifyResumeAccess<'info> {
 
     pub requester: Signer<'info>,
 
-    /// CHECK: Merkle tree containing the resume data
+    /// CHECK: Merkle tree containing the resume data, parsed in merkle::read_tree_header
+    #[account(
+        constraint = profile.resume_merkle_tree == Some(merkle_tree.key()) @ ProfileManagerError::InvalidMerkleTree
+    )]
     pub merkle_tree: UncheckedAccount<'info>,
 }
\ No newline at end of file
@@ -0,0 +1,432 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use super::contact::check_contact_policy;
+use super::payment::{fund_escrow, release_subscription};
+
+/// Prepays `periods` of the subscription offered on `tier_index`. Requests
+/// at that tier are then sent with `send_contact_request`, passing the
+/// subscription, and escrow nothing.
+pub fn subscribe(ctx: Context<Subscribe>, tier_index: u8, periods: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let target_profile = &ctx.accounts.target_profile;
+
+    require!(target_profile.availability != Availability::Paused, ProfileManagerError::ProfileUnavailable);
+    // Blocked senders cannot buy their way in either
+    check_contact_policy(
+        target_profile,
+        ctx.accounts.contact_policy.as_ref(),
+        &ctx.accounts.subscriber.key(),
+    )?;
+    require!((tier_index as usize) < target_profile.contact_prices.len(), ProfileManagerError::InvalidTierIndex);
+    let tier = &target_profile.contact_prices[tier_index as usize];
+    let Some(terms) = tier.subscription else {
+        return err!(ProfileManagerError::SubscriptionNotOffered);
+    };
+    require!(
+        periods > 0 && periods <= MAX_SUBSCRIPTION_PERIODS,
+        ProfileManagerError::InvalidSubscriptionPeriods
+    );
+
+    let received = fund_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.subscriber_token_account,
+        &mut ctx.accounts.vault,
+        &ctx.accounts.subscriber.to_account_info(),
+        terms.period_price * periods as u64,
+    )?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.subscriber = ctx.accounts.subscriber.key();
+    subscription.target_profile = target_profile.key();
+    subscription.target = target_profile.owner;
+    subscription.mint = ctx.accounts.mint.key();
+    subscription.vault = ctx.accounts.vault.key();
+    subscription.tier_index = tier_index;
+    subscription.channel = tier.channel;
+    subscription.tier_price = tier.price;
+    subscription.period_price = terms.period_price;
+    subscription.period_secs = terms.period_days as i64 * 86_400;
+    subscription.quota = terms.quota;
+    subscription.started_at = clock.unix_timestamp;
+    subscription.periods = periods;
+    subscription.periods_released = 0;
+    subscription.usage_period = 0;
+    subscription.used = 0;
+    subscription.requests_sent = 0;
+    subscription.open_requests = 0;
+    subscription.balance = received;
+    subscription.total_deposited = received;
+    subscription.total_released = 0;
+    subscription.total_refunded = 0;
+    subscription.status = SubscriptionStatus::Active;
+    subscription.cancelled_at = 0;
+    subscription.bump = ctx.bumps.subscription;
+
+    emit!(SubscriptionStarted {
+        subscription: subscription.key(),
+        subscriber: subscription.subscriber,
+        target: subscription.target_profile,
+        tier_index,
+        period_price: subscription.period_price,
+        period_secs: subscription.period_secs,
+        quota: subscription.quota,
+        periods,
+        deposited: received,
+        started_at: subscription.started_at,
+        ends_at: subscription.ends_at(),
+    });
+
+    Ok(())
+}
+
+/// Prepays `periods` more of a running subscription at its original terms.
+pub fn renew_subscription(ctx: Context<RenewSubscription>, periods: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let subscription = &ctx.accounts.subscription;
+
+    require!(subscription.is_active(clock.unix_timestamp), ProfileManagerError::SubscriptionInactive);
+    require!(
+        periods > 0 && periods <= MAX_SUBSCRIPTION_PERIODS,
+        ProfileManagerError::InvalidSubscriptionPeriods
+    );
+
+    let received = fund_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &ctx.accounts.subscriber_token_account,
+        &mut ctx.accounts.vault,
+        &ctx.accounts.subscriber.to_account_info(),
+        subscription.period_price * periods as u64,
+    )?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.periods += periods;
+    subscription.balance += received;
+    subscription.total_deposited += received;
+
+    emit!(SubscriptionRenewed {
+        subscription: subscription.key(),
+        subscriber: subscription.subscriber,
+        periods,
+        deposited: received,
+        ends_at: subscription.ends_at(),
+        renewed_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Pays the target for every finished period not yet paid out.
+/// Permissionless, so targets or keepers can settle.
+pub fn release_subscription_payout(ctx: Context<ReleaseSubscriptionPayout>) -> Result<()> {
+    let clock = Clock::get()?;
+    let subscription = &ctx.accounts.subscription;
+
+    require!(subscription.status == SubscriptionStatus::Active, ProfileManagerError::SubscriptionInactive);
+    let (periods, amount) = subscription.releasable(clock.unix_timestamp);
+    // Nothing due until the current period finishes
+    if periods == 0 {
+        return Ok(());
+    }
+
+    release_subscription(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        subscription,
+        &ctx.accounts.vault,
+        &ctx.accounts.target_token_account,
+        amount,
+    )?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.record_release(periods, amount);
+
+    emit!(SubscriptionPayoutReleased {
+        subscription: subscription.key(),
+        target: subscription.target_profile,
+        periods,
+        amount,
+        periods_released: subscription.periods_released,
+        released_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Ends a subscription early. The target is paid for finished periods and a
+/// prorated share of the current one; the rest is refunded.
+pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+    let clock = Clock::get()?;
+    let subscription = &ctx.accounts.subscription;
+
+    require!(subscription.is_active(clock.unix_timestamp), ProfileManagerError::SubscriptionInactive);
+
+    let (periods, owed) = subscription.releasable(clock.unix_timestamp);
+    let prorated = subscription.prorated_share(clock.unix_timestamp).min(subscription.balance - owed);
+    let target_amount = owed + prorated;
+    let refunded = subscription.balance - target_amount;
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    release_subscription(
+        &token_program,
+        &ctx.accounts.mint,
+        subscription,
+        &ctx.accounts.vault,
+        &ctx.accounts.target_token_account,
+        target_amount,
+    )?;
+    release_subscription(
+        &token_program,
+        &ctx.accounts.mint,
+        subscription,
+        &ctx.accounts.vault,
+        &ctx.accounts.subscriber_token_account,
+        refunded,
+    )?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.record_release(periods, target_amount);
+    subscription.balance -= refunded;
+    subscription.total_refunded += refunded;
+    subscription.status = SubscriptionStatus::Cancelled;
+    subscription.cancelled_at = clock.unix_timestamp;
+
+    emit!(SubscriptionCancelled {
+        subscription: subscription.key(),
+        subscriber: subscription.subscriber,
+        target: subscription.target_profile,
+        released: target_amount,
+        prorated,
+        refunded,
+        cancelled_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Closes a cancelled or fully paid-out subscription and its vault,
+/// returning both rents to the subscriber. Closing frees the pair to
+/// subscribe again.
+pub fn close_subscription(ctx: Context<CloseSubscription>) -> Result<()> {
+    let subscription = &ctx.accounts.subscription;
+    require!(subscription.is_settled(), ProfileManagerError::SubscriptionNotSettled);
+    require!(subscription.open_requests == 0, ProfileManagerError::SubscriptionHasOpenRequests);
+
+    // Stray deposits go back to the subscriber so they cannot block the close
+    let token_program = ctx.accounts.token_program.to_account_info();
+    release_subscription(
+        &token_program,
+        &ctx.accounts.mint,
+        subscription,
+        &ctx.accounts.vault,
+        &ctx.accounts.subscriber_token_account,
+        ctx.accounts.vault.amount,
+    )?;
+
+    let seeds = &[
+        b"subscription".as_ref(),
+        subscription.subscriber.as_ref(),
+        subscription.target_profile.as_ref(),
+        &[subscription.bump],
+    ];
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            destination: ctx.accounts.subscriber.to_account_info(),
+            authority: subscription.to_account_info(),
+        },
+        &[&seeds[..]],
+    ))?;
+
+    emit!(SubscriptionClosed {
+        subscription: subscription.key(),
+        subscriber: subscription.subscriber,
+        target: subscription.target_profile,
+        requests_sent: subscription.requests_sent,
+        total_released: subscription.total_released,
+        total_refunded: subscription.total_refunded,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    // The subscription account itself is closed by the `close` constraint
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(
+        init,
+        payer = subscriber,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", subscriber.key().as_ref(), target_profile.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        init,
+        payer = subscriber,
+        token::mint = mint,
+        token::authority = subscription,
+        token::token_program = token_program,
+        seeds = [b"subscription_vault", subscription.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub target_profile: Account<'info, Profile>,
+
+    /// Required when `target_profile.contact_policy` is set
+    pub contact_policy: Option<Account<'info, ContactPolicy>>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscriber,
+        token::token_program = token_program
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"accepted_mint", mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        constraint = accepted_mint.is_active @ ProfileManagerError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), subscription.target_profile.as_ref()],
+        bump = subscription.bump,
+        has_one = subscriber,
+        has_one = vault,
+        has_one = mint
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub subscriber: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscriber,
+        token::token_program = token_program
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseSubscriptionPayout<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.subscriber.as_ref(), subscription.target_profile.as_ref()],
+        bump = subscription.bump,
+        has_one = vault,
+        has_one = mint
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscription.target
+    )]
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), subscription.target_profile.as_ref()],
+        bump = subscription.bump,
+        has_one = subscriber,
+        has_one = vault,
+        has_one = mint
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub subscriber: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscriber
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscription.target
+    )]
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), subscription.target_profile.as_ref()],
+        bump = subscription.bump,
+        has_one = subscriber,
+        has_one = vault,
+        has_one = mint,
+        close = subscriber
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscriber
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,14 +1,17 @@
+// The generated CPI client mirrors instruction arity, so per-fn allows don't reach it
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 
 pub mod instructions;
 pub mod state;
 pub mod errors;
 pub mod events;
+pub mod merkle;
+pub mod bubblegum;
 
 use instructions::*;
 use state::*;
-use errors::*;
-use events::*;
 
 declare_id!("9KWbRGWmoX7JVKkeR5XGQhJDGxki15NeFZdkqb5U1MFu");
 
@@ -16,6 +19,7 @@ declare_id!("9KWbRGWmoX7JVKkeR5XGQhJDGxki15NeFZdkqb5U1MFu");
 pub mod profile_manager {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_profile(
         ctx: Context<CreateProfile>,
         skills: Vec<String>,
@@ -58,12 +62,108 @@ pub mod profile_manager {
         )
     }
 
+    pub fn set_cancellation_policy(
+        ctx: Context<UpdateProfile>,
+        cancellation_policy: Option<CancellationPolicy>,
+    ) -> Result<()> {
+        instructions::profile::set_cancellation_policy(ctx, cancellation_policy)
+    }
+
+    pub fn set_encryption_key(
+        ctx: Context<UpdateProfile>,
+        encryption_key: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::profile::set_encryption_key(ctx, encryption_key)
+    }
+
+    pub fn set_availability(
+        ctx: Context<UpdateProfile>,
+        availability: Availability,
+        max_pending_requests: u16,
+    ) -> Result<()> {
+        instructions::profile::set_availability(ctx, availability, max_pending_requests)
+    }
+
+    pub fn update_contact_policy(
+        ctx: Context<UpdateContactPolicy>,
+        updates: Vec<ContactPolicyUpdate>,
+    ) -> Result<()> {
+        instructions::profile::update_contact_policy(ctx, updates)
+    }
+
+    pub fn claim_handle(ctx: Context<ClaimHandle>, handle: String) -> Result<()> {
+        instructions::handle::claim_handle(ctx, handle)
+    }
+
+    pub fn release_handle(ctx: Context<ReleaseHandle>) -> Result<()> {
+        instructions::handle::release_handle(ctx)
+    }
+
+    pub fn change_handle(ctx: Context<ChangeHandle>, new_handle: String) -> Result<()> {
+        instructions::handle::change_handle(ctx, new_handle)
+    }
+
+    pub fn initialize_platform_config(
+        ctx: Context<InitializePlatformConfig>,
+        treasury: Pubkey,
+        contact_fee_bps: u16,
+        bounty_fee_bps: u16,
+        marketplace_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::platform::initialize_platform_config(
+            ctx,
+            treasury,
+            contact_fee_bps,
+            bounty_fee_bps,
+            marketplace_fee_bps,
+        )
+    }
+
+    pub fn update_platform_config(
+        ctx: Context<UpdatePlatformConfig>,
+        new_admin: Option<Pubkey>,
+        treasury: Option<Pubkey>,
+        contact_fee_bps: Option<u16>,
+        bounty_fee_bps: Option<u16>,
+        marketplace_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::platform::update_platform_config(
+            ctx,
+            new_admin,
+            treasury,
+            contact_fee_bps,
+            bounty_fee_bps,
+            marketplace_fee_bps,
+        )
+    }
+
+    pub fn set_arbitration_config(
+        ctx: Context<UpdatePlatformConfig>,
+        dispute_window_hours: u16,
+        arbitrators: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::platform::set_arbitration_config(ctx, dispute_window_hours, arbitrators)
+    }
+
+    pub fn set_keeper_reward(ctx: Context<UpdatePlatformConfig>, keeper_reward_lamports: u64) -> Result<()> {
+        instructions::platform::set_keeper_reward(ctx, keeper_reward_lamports)
+    }
+
+    pub fn add_accepted_mint(ctx: Context<AddAcceptedMint>) -> Result<()> {
+        instructions::platform::add_accepted_mint(ctx)
+    }
+
+    pub fn set_accepted_mint_active(ctx: Context<SetAcceptedMintActive>, is_active: bool) -> Result<()> {
+        instructions::platform::set_accepted_mint_active(ctx, is_active)
+    }
+
     pub fn send_contact_request(
         ctx: Context<SendContactRequest>,
-        message: String,
+        message: EncryptedMessage,
         tier_index: u8,
+        job: Option<Pubkey>,
     ) -> Result<()> {
-        instructions::contact::send_contact_request(ctx, message, tier_index)
+        instructions::contact::send_contact_request(ctx, message, tier_index, job)
     }
 
     pub fn respond_to_contact(
@@ -77,6 +177,111 @@ pub mod profile_manager {
         instructions::contact::handle_expired_contact(ctx)
     }
 
+    pub fn crank_expired_contacts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankExpiredContacts<'info>>,
+    ) -> Result<()> {
+        instructions::crank::crank_expired_contacts(ctx)
+    }
+
+    pub fn cancel_contact_request(ctx: Context<CancelContactRequest>) -> Result<()> {
+        instructions::contact::cancel_contact_request(ctx)
+    }
+
+    pub fn counter_contact_request(
+        ctx: Context<CounterContactRequest>,
+        tier_index: u8,
+    ) -> Result<()> {
+        instructions::contact::counter_contact_request(ctx, tier_index)
+    }
+
+    pub fn accept_counter_offer(ctx: Context<AcceptCounterOffer>) -> Result<()> {
+        instructions::contact::accept_counter_offer(ctx)
+    }
+
+    pub fn decline_counter_offer(ctx: Context<DeclineCounterOffer>) -> Result<()> {
+        instructions::contact::decline_counter_offer(ctx)
+    }
+
+    pub fn open_message_thread(ctx: Context<OpenMessageThread>) -> Result<()> {
+        instructions::message::open_message_thread(ctx)
+    }
+
+    pub fn post_message(ctx: Context<PostMessage>, message_hash: [u8; 32]) -> Result<()> {
+        instructions::message::post_message(ctx, message_hash)
+    }
+
+    pub fn close_message_thread(ctx: Context<CloseMessageThread>) -> Result<()> {
+        instructions::message::close_message_thread(ctx)
+    }
+
+    pub fn release_accepted_contact(ctx: Context<ReleaseAcceptedContact>) -> Result<()> {
+        instructions::contact::release_accepted_contact(ctx)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::dispute::open_dispute(ctx, reason_hash)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, target_bps: u16) -> Result<()> {
+        instructions::dispute::resolve_dispute(ctx, target_bps)
+    }
+
+    pub fn close_dispute(ctx: Context<CloseDispute>) -> Result<()> {
+        instructions::dispute::close_dispute(ctx)
+    }
+
+    pub fn top_up_credits(ctx: Context<TopUpCredits>, amount: u64) -> Result<()> {
+        instructions::credits::top_up_credits(ctx, amount)
+    }
+
+    pub fn withdraw_credits(ctx: Context<WithdrawCredits>, amount: u64) -> Result<()> {
+        instructions::credits::withdraw_credits(ctx, amount)
+    }
+
+    pub fn create_outreach_campaign(
+        ctx: Context<CreateOutreachCampaign>,
+        campaign_id: u64,
+        budget: u64,
+        max_price_per_request: u64,
+    ) -> Result<()> {
+        instructions::campaign::create_outreach_campaign(ctx, campaign_id, budget, max_price_per_request)
+    }
+
+    pub fn spawn_campaign_contacts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SpawnCampaignContacts<'info>>,
+        contacts: Vec<CampaignContact>,
+    ) -> Result<()> {
+        instructions::campaign::spawn_campaign_contacts(ctx, contacts)
+    }
+
+    pub fn close_outreach_campaign(ctx: Context<CloseOutreachCampaign>) -> Result<()> {
+        instructions::campaign::close_outreach_campaign(ctx)
+    }
+
+    pub fn subscribe(ctx: Context<Subscribe>, tier_index: u8, periods: u32) -> Result<()> {
+        instructions::subscription::subscribe(ctx, tier_index, periods)
+    }
+
+    pub fn renew_subscription(ctx: Context<RenewSubscription>, periods: u32) -> Result<()> {
+        instructions::subscription::renew_subscription(ctx, periods)
+    }
+
+    pub fn release_subscription_payout(ctx: Context<ReleaseSubscriptionPayout>) -> Result<()> {
+        instructions::subscription::release_subscription_payout(ctx)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::subscription::cancel_subscription(ctx)
+    }
+
+    pub fn close_subscription(ctx: Context<CloseSubscription>) -> Result<()> {
+        instructions::subscription::close_subscription(ctx)
+    }
+
+    pub fn close_contact_request(ctx: Context<CloseContactRequest>) -> Result<()> {
+        instructions::contact::close_contact_request(ctx)
+    }
+
     pub fn create_profile_nft(
         ctx: Context<CreateProfileNFT>,
         name: String,
@@ -86,30 +291,26 @@ pub mod profile_manager {
         instructions::nft::create_profile_nft(ctx, name, symbol, uri)
     }
 
-    pub fn process_payment(ctx: Context<ProcessPayment>) -> Result<()> {
-        instructions::payment::process_payment(ctx)
-    }
-
-    pub fn complete_payment(ctx: Context<CompletePayment>, accept: bool) -> Result<()> {
-        instructions::payment::complete_payment(ctx, accept)
-    }
-
-    pub fn refund_payment(ctx: Context<RefundPayment>) -> Result<()> {
-        instructions::payment::refund_payment(ctx)
+    pub fn create_resume_tree(
+        ctx: Context<CreateResumeTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::resume::create_resume_tree(ctx, max_depth, max_buffer_size)
     }
 
-    pub fn compress_resume(
-        ctx: Context<CompressResume>,
-        resume_data_hash: [u8; 32],
+    pub fn compress_resume<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompressResume<'info>>,
         metadata_uri: String,
     ) -> Result<()> {
-        instructions::resume::compress_resume(ctx, resume_data_hash, metadata_uri)
+        instructions::resume::compress_resume(ctx, metadata_uri)
     }
 
     pub fn verify_resume_access(
         ctx: Context<VerifyResumeAccess>,
         merkle_proof: Vec<[u8; 32]>,
+        leaf: [u8; 32],
     ) -> Result<String> {
-        instructions::resume::verify_resume_access(ctx, merkle_proof)
+        instructions::resume::verify_resume_access(ctx, merkle_proof, leaf)
     }
 }
\ No newline at end of file
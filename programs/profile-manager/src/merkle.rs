@@ -0,0 +1,274 @@
+use anchor_lang::prelude::*;
+use solana_keccak_hasher::hashv;
+use crate::errors::ProfileManagerError;
+
+// spl-account-compression program that owns concurrent merkle tree accounts
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+// ConcurrentMerkleTreeHeader layout (V1):
+// account_type (u8) | header version (u8) | max_buffer_size (u32) | max_depth (u32)
+// | authority (Pubkey) | creation_slot (u64) | padding ([u8; 6])
+pub const TREE_HEADER_SIZE: usize = 56;
+const ACCOUNT_TYPE_CONCURRENT_MERKLE_TREE: u8 = 1;
+const HEADER_VERSION_V1: u8 = 0;
+
+/// Parsed header of an spl-account-compression tree account.
+pub struct TreeHeader {
+    pub max_buffer_size: u32,
+    pub max_depth: u32,
+    pub authority: Pubkey,
+}
+
+pub fn read_tree_header(merkle_tree: &AccountInfo) -> Result<TreeHeader> {
+    require_keys_eq!(
+        *merkle_tree.owner,
+        SPL_ACCOUNT_COMPRESSION_ID,
+        ProfileManagerError::InvalidMerkleTree
+    );
+
+    let data = merkle_tree.try_borrow_data()?;
+    require!(data.len() >= TREE_HEADER_SIZE, ProfileManagerError::InvalidMerkleTree);
+    require!(
+        data[0] == ACCOUNT_TYPE_CONCURRENT_MERKLE_TREE && data[1] == HEADER_VERSION_V1,
+        ProfileManagerError::InvalidMerkleTree
+    );
+
+    Ok(TreeHeader {
+        max_buffer_size: u32::from_le_bytes(data[2..6].try_into().unwrap()),
+        max_depth: u32::from_le_bytes(data[6..10].try_into().unwrap()),
+        authority: Pubkey::try_from(&data[10..42]).unwrap(),
+    })
+}
+
+// ConcurrentMerkleTree body that follows the header:
+// sequence_number (u64) | active_index (u64) | buffer_size (u64)
+// | change_logs ([ChangeLog; max_buffer_size]) | rightmost_proof (Path)
+const TREE_BODY_PREFIX_SIZE: usize = 24;
+
+// ChangeLog: root | path ([Node; max_depth]) | index (u32) | padding (u32)
+fn change_log_size(max_depth: u32) -> usize {
+    32 + 32 * max_depth as usize + 8
+}
+
+/// Current root of the tree, i.e. the root of the active change log.
+pub fn read_tree_root(merkle_tree: &AccountInfo) -> Result<[u8; 32]> {
+    let header = read_tree_header(merkle_tree)?;
+    let data = merkle_tree.try_borrow_data()?;
+
+    let active_index_offset = TREE_HEADER_SIZE + 8;
+    let active_index = u64::from_le_bytes(
+        data[active_index_offset..active_index_offset + 8].try_into().unwrap(),
+    ) as usize;
+    require!(
+        active_index < header.max_buffer_size as usize,
+        ProfileManagerError::InvalidMerkleTree
+    );
+
+    let root_offset = TREE_HEADER_SIZE + TREE_BODY_PREFIX_SIZE
+        + active_index * change_log_size(header.max_depth);
+    require!(data.len() >= root_offset + 32, ProfileManagerError::InvalidMerkleTree);
+
+    Ok(data[root_offset..root_offset + 32].try_into().unwrap())
+}
+
+/// Number of leaves appended so far, taken from the rightmost proof.
+pub fn read_tree_leaf_count(merkle_tree: &AccountInfo) -> Result<u32> {
+    let header = read_tree_header(merkle_tree)?;
+    let data = merkle_tree.try_borrow_data()?;
+
+    // Path: proof ([Node; max_depth]) | leaf | index (u32) | padding (u32)
+    let rightmost_proof_offset = TREE_HEADER_SIZE + TREE_BODY_PREFIX_SIZE
+        + header.max_buffer_size as usize * change_log_size(header.max_depth);
+    let index_offset = rightmost_proof_offset + 32 * header.max_depth as usize + 32;
+    require!(data.len() >= index_offset + 4, ProfileManagerError::InvalidMerkleTree);
+
+    Ok(u32::from_le_bytes(data[index_offset..index_offset + 4].try_into().unwrap()))
+}
+
+/// Recomputes the root from `leaf` at `leaf_index` the same way
+/// spl-concurrent-merkle-tree does: keccak256(left || right) at every level,
+/// with bit `i` of the index selecting whether the node is the right child.
+pub fn compute_root(leaf: &[u8; 32], proof: &[[u8; 32]], leaf_index: u32) -> [u8; 32] {
+    let mut node = *leaf;
+    for (depth, sibling) in proof.iter().enumerate() {
+        node = if (leaf_index >> depth) & 1 == 0 {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    node
+}
+
+/// Verifies a full-depth proof (no canopy) against `root`.
+pub fn verify_proof(
+    leaf: &[u8; 32],
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+    leaf_index: u32,
+    max_depth: u32,
+) -> Result<()> {
+    require!(proof.len() == max_depth as usize, ProfileManagerError::InvalidProofLength);
+    require!(
+        max_depth >= 32 || leaf_index < (1u32 << max_depth),
+        ProfileManagerError::InvalidResumeProof
+    );
+    require!(
+        compute_root(leaf, proof, leaf_index) == *root,
+        ProfileManagerError::InvalidResumeProof
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTH: u32 = 3;
+    const BUFFER: u32 = 2;
+
+    fn leaf(i: u8) -> [u8; 32] {
+        hashv(&[b"leaf", &[i]]).to_bytes()
+    }
+
+    // Every level of a full tree of 2^DEPTH leaves, leaves first
+    fn levels() -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![(0..1u8 << DEPTH).map(leaf).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hashv(&[&pair[0], &pair[1]]).to_bytes())
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    fn proof(index: usize) -> Vec<[u8; 32]> {
+        let levels = levels();
+        (0..DEPTH as usize).map(|depth| levels[depth][(index >> depth) ^ 1]).collect()
+    }
+
+    fn root() -> [u8; 32] {
+        levels().last().unwrap()[0]
+    }
+
+    #[test]
+    fn empty_subtree_matches_known_keccak() {
+        // keccak256 of 64 zero bytes, the first empty-node hash of
+        // spl-concurrent-merkle-tree
+        let expected = "ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5";
+        let root = compute_root(&[0; 32], &[[0; 32]], 0);
+        let hex: String = root.iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(hex, expected);
+    }
+
+    #[test]
+    fn index_bit_selects_right_child() {
+        let (a, b) = (leaf(0), leaf(1));
+        assert_eq!(compute_root(&a, &[b], 0), hashv(&[&a, &b]).to_bytes());
+        assert_eq!(compute_root(&b, &[a], 1), hashv(&[&a, &b]).to_bytes());
+        assert_ne!(compute_root(&b, &[a], 0), compute_root(&b, &[a], 1));
+    }
+
+    #[test]
+    fn accepts_valid_proofs() {
+        for index in 0..1u32 << DEPTH {
+            let proof = proof(index as usize);
+            verify_proof(&leaf(index as u8), &proof, &root(), index, DEPTH).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_index() {
+        let proof = proof(2);
+        assert!(verify_proof(&leaf(2), &proof, &root(), 3, DEPTH).is_err());
+        // Out of range indexes alias a valid one in their low bits
+        assert!(verify_proof(&leaf(2), &proof, &root(), 2 + (1 << DEPTH), DEPTH).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_proof_length() {
+        let mut proof = proof(5);
+        proof.pop();
+        assert_eq!(
+            verify_proof(&leaf(5), &proof, &root(), 5, DEPTH).unwrap_err(),
+            ProfileManagerError::InvalidProofLength.into()
+        );
+        assert!(verify_proof(&leaf(5), &self::proof(5), &root(), 5, DEPTH + 1).is_err());
+    }
+
+    #[test]
+    fn rejects_tampered_sibling_or_leaf() {
+        for depth in 0..DEPTH as usize {
+            let mut proof = proof(6);
+            proof[depth][0] ^= 1;
+            assert!(verify_proof(&leaf(6), &proof, &root(), 6, DEPTH).is_err());
+        }
+        assert!(verify_proof(&leaf(7), &proof(6), &root(), 6, DEPTH).is_err());
+    }
+
+    // Serializes a ConcurrentMerkleTree<DEPTH, BUFFER> account as
+    // spl-account-compression lays it out
+    fn tree_account_data(active_index: u64, roots: &[[u8; 32]], leaf_count: u32) -> Vec<u8> {
+        let mut data = vec![ACCOUNT_TYPE_CONCURRENT_MERKLE_TREE, HEADER_VERSION_V1];
+        data.extend_from_slice(&BUFFER.to_le_bytes());
+        data.extend_from_slice(&DEPTH.to_le_bytes());
+        data.extend_from_slice(&[7; 32]); // authority
+        data.extend_from_slice(&42u64.to_le_bytes()); // creation_slot
+        data.extend_from_slice(&[0; 6]);
+        assert_eq!(data.len(), TREE_HEADER_SIZE);
+
+        data.extend_from_slice(&9u64.to_le_bytes()); // sequence_number
+        data.extend_from_slice(&active_index.to_le_bytes());
+        data.extend_from_slice(&(BUFFER as u64).to_le_bytes());
+        for root in roots {
+            data.extend_from_slice(root);
+            data.extend_from_slice(&vec![0xaa; 32 * DEPTH as usize]);
+            data.extend_from_slice(&[0xbb; 8]);
+        }
+        // Rightmost proof: proof | leaf | index | padding
+        data.extend_from_slice(&vec![0xcc; 32 * DEPTH as usize]);
+        data.extend_from_slice(&[0xdd; 32]);
+        data.extend_from_slice(&leaf_count.to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data
+    }
+
+    fn with_tree<T>(data: &mut [u8], owner: Pubkey, f: impl FnOnce(&AccountInfo) -> T) -> T {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, data, &owner, false, 0);
+        f(&info)
+    }
+
+    #[test]
+    fn reads_header_root_and_leaf_count() {
+        let mut data = tree_account_data(1, &[[1; 32], [2; 32]], 5);
+        with_tree(&mut data, SPL_ACCOUNT_COMPRESSION_ID, |info| {
+            let header = read_tree_header(info).unwrap();
+            assert_eq!((header.max_buffer_size, header.max_depth), (BUFFER, DEPTH));
+            assert_eq!(header.authority, Pubkey::new_from_array([7; 32]));
+            assert_eq!(read_tree_root(info).unwrap(), [2; 32]);
+            assert_eq!(read_tree_leaf_count(info).unwrap(), 5);
+        });
+    }
+
+    #[test]
+    fn rejects_foreign_or_malformed_trees() {
+        let mut data = tree_account_data(0, &[[1; 32], [2; 32]], 5);
+        with_tree(&mut data, Pubkey::new_unique(), |info| assert!(read_tree_header(info).is_err()));
+
+        let mut data = tree_account_data(BUFFER as u64, &[[1; 32], [2; 32]], 5);
+        with_tree(&mut data, SPL_ACCOUNT_COMPRESSION_ID, |info| assert!(read_tree_root(info).is_err()));
+
+        let mut data = tree_account_data(0, &[[1; 32], [2; 32]], 5);
+        data.truncate(data.len() - 8);
+        with_tree(&mut data, SPL_ACCOUNT_COMPRESSION_ID, |info| {
+            assert!(read_tree_leaf_count(info).is_err())
+        });
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::errors::ProfileManagerError;
+use super::ContactStatus;
+
+/// A recruiter's bulk outreach budget at
+/// `[b"outreach_campaign", owner, campaign_id]`. The budget is escrowed once
+/// in a vault at `[b"campaign_vault", outreach_campaign]` owned by this PDA;
+/// each `send_contact_request` made against the campaign reserves the
+/// target's tier price from it instead of transferring from the wallet.
+///
+/// Refunds of campaign-funded requests return to `available`, so the budget
+/// can be spent on further candidates. Closing the campaign refunds whatever
+/// is left and closes the vault, so every request it funded must have been
+/// closed first; the account itself is kept for its stats.
+#[account]
+#[derive(InitSpace)]
+pub struct OutreachCampaign {
+    pub owner: Pubkey,
+    pub campaign_id: u64,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub budget: u64,                   // Escrowed at creation, net of transfer fees
+    pub available: u64,                // Free to spend on new requests
+    pub reserved: u64,                 // Backing the campaign's unsettled requests
+    pub spent: u64,                    // Paid out to targets and the treasury
+    pub max_price_per_request: u64,    // Targets whose chosen tier costs more are skipped
+    pub sent_count: u32,
+    pub pending_count: u32,            // Requests still awaiting a response
+    pub open_requests: u32,            // Requests not yet closed; they settle against the vault
+    pub accepted_count: u32,
+    pub rejected_count: u32,
+    pub expired_count: u32,
+    pub cancelled_count: u32,
+    pub declined_count: u32,
+    pub status: CampaignStatus,
+    pub created_at: i64,
+    pub closed_at: i64,
+    pub bump: u8,
+}
+
+impl OutreachCampaign {
+    pub fn is_active(&self) -> bool {
+        self.status == CampaignStatus::Active
+    }
+
+    /// Moves `amount` from the free budget to back a request.
+    pub fn reserve(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.max_price_per_request, ProfileManagerError::CampaignPriceCapExceeded);
+        self.available = self
+            .available
+            .checked_sub(amount)
+            .ok_or(ProfileManagerError::InsufficientCampaignBudget)?;
+        self.reserved += amount;
+        Ok(())
+    }
+
+    /// Records `amount` of a request's backing leaving the vault.
+    pub fn spend(&mut self, amount: u64) {
+        self.reserved -= amount;
+        self.spent += amount;
+    }
+
+    /// Returns `amount` of a request's backing to the free budget.
+    pub fn refund(&mut self, amount: u64) {
+        self.reserved -= amount;
+        self.available += amount;
+    }
+
+    pub fn record_sent(&mut self) {
+        self.sent_count += 1;
+        self.pending_count += 1;
+        self.open_requests += 1;
+    }
+
+    /// Releases a funded request once it is closed. Allowlisted requests
+    /// reserve nothing, so `reserved` alone cannot tell they are still open.
+    pub fn record_closed(&mut self) {
+        self.open_requests = self.open_requests.saturating_sub(1);
+    }
+
+    /// Counts a resolved request towards the campaign's stats.
+    pub fn record_resolution(&mut self, status: ContactStatus) {
+        self.pending_count = self.pending_count.saturating_sub(1);
+        match status {
+            ContactStatus::Responded | ContactStatus::Accepted => self.accepted_count += 1,
+            ContactStatus::Rejected => self.rejected_count += 1,
+            ContactStatus::Expired => self.expired_count += 1,
+            ContactStatus::Cancelled => self.cancelled_count += 1,
+            ContactStatus::Declined => self.declined_count += 1,
+            ContactStatus::Pending
+            | ContactStatus::Countered
+            | ContactStatus::Disputed
+            | ContactStatus::Arbitrated => {}
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum CampaignStatus {
+    Active,
+    Closed,    // Unused budget refunded, vault closed
+}
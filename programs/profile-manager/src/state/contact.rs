@@ -0,0 +1,314 @@
+use anchor_lang::prelude::*;
+use crate::errors::ProfileManagerError;
+use super::{PlatformConfig, BPS_DENOMINATOR};
+
+// job-application, whose per-job `[b"bounty_authority", job]` PDA may sponsor
+// contact requests out of the job's bounty escrow. It depends on this
+// program, so its ID is pinned here rather than imported.
+pub const JOB_APPLICATION_ID: Pubkey = pubkey!("2qABiq2mqKPrp8H2eFqshFZ4EjTYMHPcmepnHD4TuwgN");
+
+/// A paid contact request at `[b"contact", requester, target_profile, nonce]`,
+/// where `nonce` is the pair's `ContactThread::request_count` at send time.
+/// Lifecycle:
+///
+/// 1. `send_contact_request` creates the request and its escrow token account
+///    and funds the escrow in the same instruction, so a request is never
+///    observable unfunded. Status starts as `Pending`. Requests may instead
+///    reserve the deposit from the requester's `ContactCredits` or
+///    `OutreachCampaign` (see `FundingSource`); "escrow" below then means
+///    that account's vault, and refunds go back to its balance. A request
+///    about a `Job` may be funded from the job's bounty escrow through
+///    job-application; it is linked via `job` and refunds go back to the
+///    bounty escrow, its `refund_authority`. A request at the tier of the
+///    requester's active `Subscription` to the target draws on its
+///    per-period quota instead, with no escrow and nothing held; such
+///    requests cannot be countered.
+/// 2. While `Pending` and before `expires_at`, the target calls
+///    `respond_to_contact`: accepting pays the escrow out to the target
+///    (`Responded`), less any late-answer refund from the tier's SLA curve;
+///    rejecting refunds the requester (`Rejected`). The target may instead
+///    call `counter_contact_request` to propose another tier (`Countered`),
+///    which restarts the response window for the requester, who either
+///    accepts with `accept_counter_offer` (the escrow is topped up or the
+///    difference refunded, and the target is paid, `Responded`) or declines
+///    with `decline_counter_offer` (full refund, `Declined`).
+///
+///    When `PlatformConfig::dispute_window_hours` is set, either acceptance
+///    holds the target's share in escrow (`Accepted`) instead. Anyone may
+///    release it with `release_accepted_contact` once the window closes
+///    (`Responded`); before that the requester may `open_dispute`
+///    (`Disputed`) and an arbitrator splits it with `resolve_dispute`
+///    (`Arbitrated`). The dispute snapshots the request's `MessageThread`;
+///    `has_message_thread` proves whether there is one to read.
+/// 3. After `expires_at`, anyone may call `handle_expired_contact` to refund
+///    the requester (`Expired`), less the platform's contact fee. A request
+///    that expires while `Countered` was answered by the target and lapses
+///    like `decline_counter_offer`, so it is refunded in full. Before that, the requester may withdraw a
+///    `Pending` request with `cancel_contact_request` (`Cancelled`), paying
+///    the target's cancellation fee once the free-cancel window has passed.
+/// 4. Once resolved, the requester calls `close_contact_request` to close the
+///    escrow token account and the request, reclaiming both rents. The
+///    shared vault of a credit-, campaign- or subscription-funded request
+///    stays open; campaigns and subscriptions count their open requests and
+///    cannot be closed until each has been closed.
+///
+/// Every settlement moves the request out of the state it pays from, so each
+/// escrow is paid out exactly once. `resolved_at` is non-zero once the escrow
+/// is fully settled.
+#[account]
+#[derive(InitSpace)]
+pub struct ContactRequest {
+    pub requester: Pubkey,
+    pub target_profile: Pubkey,
+    pub nonce: u64,                    // Position in the pair's ContactThread
+    pub message: EncryptedMessage,     // Sealed to the target's encryption key
+    pub amount: u64,
+    pub funding: FundingSource,
+    pub vault: Pubkey,                 // Token account holding the deposit
+    pub job: Option<Pubkey>,           // job-application Job that sponsored the fee
+    pub subscription: Option<Pubkey>,  // Subscription whose quota the request drew on
+    pub refund_authority: Pubkey,      // Owner of the token account refunds go to
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub resolved_at: i64,              // When the escrow was settled, 0 while pending
+    pub free_cancel_until: i64,        // Snapshot of the target's policy at send time
+    pub cancellation_fee_bps: u16,
+    pub tier_index: u8,
+    pub counter_tier_index: u8,        // Set while `Countered`
+    pub counter_amount: u64,           // Price of the counter tier
+    pub full_payout_until: i64,        // Snapshot of the tier's SLA curve at send time
+    pub min_payout_bps: u16,
+    pub payout_amount: u64,            // Target's share once accepted
+    pub refunded: u64,                 // Returned to the requester's side so far
+    pub dispute_deadline: i64,         // End of the dispute window while `Accepted`
+    pub has_message_thread: bool,      // Whether `open_message_thread` has created its MessageThread
+    pub status: ContactStatus,
+    pub bump: u8,
+}
+
+impl ContactRequest {
+    /// Still awaiting a response from either side.
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, ContactStatus::Pending | ContactStatus::Countered)
+    }
+
+    /// The escrow has been fully settled.
+    pub fn is_resolved(&self) -> bool {
+        !self.is_open() && !matches!(self.status, ContactStatus::Accepted | ContactStatus::Disputed)
+    }
+
+    /// Platform fee kept when an unanswered request is rejected or expires.
+    /// A lapsed counter-offer is a decline and refunds in full.
+    pub fn refund_fee(&self, contact_fee_bps: u16) -> u64 {
+        if self.status == ContactStatus::Countered {
+            return 0;
+        }
+        PlatformConfig::fee_for(self.amount, contact_fee_bps)
+    }
+
+    /// Records `amount` returned to the requester's side. A job reads this
+    /// to credit the refunds of requests its bounty sponsored.
+    pub fn record_refund(&mut self, amount: u64) {
+        self.refunded += amount;
+    }
+
+    pub fn was_accepted(&self) -> bool {
+        matches!(
+            self.status,
+            ContactStatus::Accepted | ContactStatus::Disputed | ContactStatus::Responded | ContactStatus::Arbitrated
+        )
+    }
+
+    /// Records an acceptance. With a dispute window the target's share stays
+    /// in escrow until `dispute_deadline`; without one it has been paid.
+    pub fn mark_accepted(&mut self, target_amount: u64, dispute_window_secs: i64, now: i64) {
+        self.payout_amount = target_amount;
+        if dispute_window_secs == 0 {
+            self.status = ContactStatus::Responded;
+            self.dispute_deadline = 0;
+            self.resolved_at = now;
+        } else {
+            self.status = ContactStatus::Accepted;
+            self.dispute_deadline = now + dispute_window_secs;
+        }
+    }
+
+    /// The target's share of `amount` when accepting at `now`, following the
+    /// SLA curve snapshotted at send time.
+    pub fn target_payout(&self, now: i64) -> u64 {
+        if now <= self.full_payout_until || self.expires_at <= self.full_payout_until {
+            return self.amount;
+        }
+        let window = (self.expires_at - self.full_payout_until) as u128;
+        let late = ((now.min(self.expires_at) - self.full_payout_until) as u128).min(window);
+        let max_bps = BPS_DENOMINATOR as u128;
+        let slide_bps = max_bps - self.min_payout_bps as u128;
+        let payout_bps = max_bps - slide_bps * late / window;
+        (self.amount as u128 * payout_bps / max_bps) as u64
+    }
+}
+
+pub const MESSAGE_TAG_LEN: usize = 16;
+pub const MAX_MESSAGE_CIPHERTEXT_LEN: usize = 1000 + MESSAGE_TAG_LEN;
+
+/// An X25519 + XChaCha20-Poly1305 envelope, as produced by the
+/// `contact-envelope` crate. Only the target can read it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EncryptedMessage {
+    pub recipient_key: [u8; 32],       // Target's `encryption_key` at send time
+    pub ephemeral_key: [u8; 32],       // Sender's one-time X25519 public key
+    pub nonce: [u8; 24],
+    #[max_len(1016)]
+    pub ciphertext: Vec<u8>,           // Up to 1000 bytes of plaintext plus the Poly1305 tag
+}
+
+impl EncryptedMessage {
+    /// Checks the envelope's shape and that it was sealed to `recipient_key`.
+    pub fn validate(&self, recipient_key: &[u8; 32]) -> Result<()> {
+        require!(
+            self.recipient_key == *recipient_key,
+            ProfileManagerError::EncryptionKeyMismatch
+        );
+        require!(
+            self.ciphertext.len() >= MESSAGE_TAG_LEN,
+            ProfileManagerError::InvalidEncryptedMessage
+        );
+        require!(
+            self.ciphertext.len() <= MAX_MESSAGE_CIPHERTEXT_LEN,
+            ProfileManagerError::MessageTooLong
+        );
+        Ok(())
+    }
+}
+
+/// History between a requester and a target profile, at
+/// `[b"contact_thread", requester, target_profile]`. Created with the first
+/// request and kept after requests are closed.
+#[account]
+#[derive(InitSpace)]
+pub struct ContactThread {
+    pub requester: Pubkey,
+    pub target_profile: Pubkey,
+    pub request_count: u64,            // Requests ever sent; also the next nonce
+    pub pending_count: u32,            // Requests still awaiting a response
+    pub accepted_count: u32,
+    pub rejected_count: u32,
+    pub expired_count: u32,
+    pub cancelled_count: u32,
+    pub declined_count: u32,           // Counter-offers turned down by the requester
+    pub disputed_count: u32,           // Acceptances the requester disputed
+    pub last_request: Pubkey,          // Most recently sent request
+    pub created_at: i64,
+    pub last_activity_at: i64,
+    pub bump: u8,
+}
+
+impl ContactThread {
+    /// Counts a resolved request towards the thread's history.
+    pub fn record_resolution(&mut self, status: ContactStatus, now: i64) {
+        self.pending_count = self.pending_count.saturating_sub(1);
+        match status {
+            ContactStatus::Responded | ContactStatus::Accepted => self.accepted_count += 1,
+            ContactStatus::Rejected => self.rejected_count += 1,
+            ContactStatus::Expired => self.expired_count += 1,
+            ContactStatus::Cancelled => self.cancelled_count += 1,
+            ContactStatus::Declined => self.declined_count += 1,
+            ContactStatus::Pending
+            | ContactStatus::Countered
+            | ContactStatus::Disputed
+            | ContactStatus::Arbitrated => {}
+        }
+        self.last_activity_at = now;
+    }
+
+    pub fn record_dispute(&mut self, now: i64) {
+        self.disputed_count += 1;
+        self.last_activity_at = now;
+    }
+}
+
+pub const MAX_CONTACT_POLICY_ENTRIES: usize = 64;
+
+/// Per-profile sender lists at `[b"contact_policy", profile]`, referenced from
+/// `Profile::contact_policy` so senders cannot omit it. Blocked senders are
+/// refused outright; allowed senders contact the profile for free.
+#[account]
+#[derive(InitSpace)]
+pub struct ContactPolicy {
+    pub profile: Pubkey,
+    #[max_len(64)]
+    pub blocked: Vec<Pubkey>,
+    #[max_len(64)]
+    pub allowed: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ContactPolicy {
+    pub fn is_blocked(&self, sender: &Pubkey) -> bool {
+        self.blocked.contains(sender)
+    }
+
+    pub fn is_allowed(&self, sender: &Pubkey) -> bool {
+        self.allowed.contains(sender)
+    }
+
+    /// Blocking a sender drops them from the allowlist and vice versa.
+    pub fn block(&mut self, sender: Pubkey) -> Result<()> {
+        self.allowed.retain(|k| *k != sender);
+        if !self.blocked.contains(&sender) {
+            require!(
+                self.blocked.len() < MAX_CONTACT_POLICY_ENTRIES,
+                ProfileManagerError::ContactPolicyFull
+            );
+            self.blocked.push(sender);
+        }
+        Ok(())
+    }
+
+    pub fn allow(&mut self, sender: Pubkey) -> Result<()> {
+        self.blocked.retain(|k| *k != sender);
+        if !self.allowed.contains(&sender) {
+            require!(
+                self.allowed.len() < MAX_CONTACT_POLICY_ENTRIES,
+                ProfileManagerError::ContactPolicyFull
+            );
+            self.allowed.push(sender);
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self, sender: &Pubkey) {
+        self.blocked.retain(|k| k != sender);
+        self.allowed.retain(|k| k != sender);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ContactPolicyUpdate {
+    Block(Pubkey),
+    Allow(Pubkey),
+    Clear(Pubkey),                     // Remove from whichever list holds it
+}
+
+/// Where a contact request's deposit is held.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum FundingSource {
+    Escrow,    // Own escrow token account at [b"escrow", contact_request]
+    Credits,   // Requester's ContactCredits vault; refunds return to the balance
+    Campaign,  // Requester's OutreachCampaign vault; refunds return to the budget
+    Subscription, // Drawn on the requester's Subscription; its vault, nothing held
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ContactStatus {
+    Pending,   // Funded, awaiting a response
+    Responded, // Accepted, escrow paid to the target
+    Rejected,  // Declined, escrow refunded to the requester
+    Expired,   // No response in time, escrow refunded to the requester
+    Cancelled, // Withdrawn by the requester, escrow refunded less any cancellation fee
+    Countered, // Target proposed another tier, awaiting the requester
+    Declined,  // Requester turned down the counter-offer, escrow refunded in full
+    Accepted,  // Accepted, target's share held in escrow for the dispute window
+    Disputed,  // Requester disputed the acceptance, awaiting an arbitrator
+    Arbitrated, // Arbitrator split the held share between target and requester
+}
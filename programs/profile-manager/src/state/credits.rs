@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::errors::ProfileManagerError;
+
+/// A requester's prepaid balance in one mint, at
+/// `[b"contact_credits", owner, mint]`. The tokens sit in a single vault at
+/// `[b"credits_vault", contact_credits]` owned by this PDA, so credit-funded
+/// contact requests need no escrow token account of their own.
+///
+/// `balance` is free to spend or withdraw; `reserved` backs the owner's
+/// unsettled credit-funded requests. Payouts leave the vault, refunds move
+/// back from `reserved` to `balance`. The running totals reconcile with the
+/// per-request `ContactCreditsDebited` / `ContactCreditsRefunded` events.
+#[account]
+#[derive(InitSpace)]
+pub struct ContactCredits {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub balance: u64,
+    pub reserved: u64,
+    pub total_deposited: u64,          // Net of transfer fees
+    pub total_withdrawn: u64,
+    pub total_spent: u64,              // Paid out to targets and the treasury
+    pub total_refunded: u64,           // Returned to `balance` by settled requests
+    pub requests_funded: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl ContactCredits {
+    /// Moves `amount` from the free balance to back a request.
+    pub fn reserve(&mut self, amount: u64) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or(ProfileManagerError::InsufficientCredits)?;
+        self.reserved += amount;
+        Ok(())
+    }
+
+    /// Records `amount` of a request's backing leaving the vault.
+    pub fn spend(&mut self, amount: u64) {
+        self.reserved -= amount;
+        self.total_spent += amount;
+    }
+
+    /// Returns `amount` of a request's backing to the free balance.
+    pub fn refund(&mut self, amount: u64) {
+        self.reserved -= amount;
+        self.balance += amount;
+        self.total_refunded += amount;
+    }
+}
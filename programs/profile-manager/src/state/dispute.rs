@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// A requester's challenge to an accepted contact request, at
+/// `[b"dispute", contact_request]`. Kept after settlement as the record of the
+/// arbitrator's decision until the contact request is closed; then the
+/// requester may close it with `close_dispute` and reclaim the rent.
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,                // Target profile owner
+    pub amount: u64,                   // Target's held share under dispute
+    pub reason_hash: [u8; 32],         // Hash of the requester's off-chain statement
+    pub target_replied: bool,          // The MessageThread had a target reply when opened
+    pub opened_at: i64,
+    pub arbitrator: Option<Pubkey>,    // Set on resolution
+    pub target_bps: u16,               // Share of `amount` awarded to the target
+    pub resolved_at: i64,              // 0 while open
+    pub bump: u8,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::errors::ProfileManagerError;
+
+pub const HANDLE_MIN_LEN: usize = 3;
+pub const HANDLE_MAX_LEN: usize = 30;
+pub const HANDLE_RELEASE_COOLDOWN_SECONDS: i64 = 7 * 24 * 60 * 60; // 7 days
+
+// Handles that would collide with app routes or impersonate the platform
+pub const RESERVED_HANDLES: [&str; 16] = [
+    "admin", "api", "app", "cardpass", "contact", "help", "jobs", "login",
+    "null", "profile", "root", "settings", "signup", "support", "system", "www",
+];
+
+#[account]
+#[derive(InitSpace)]
+pub struct HandleRecord {
+    #[max_len(30)]
+    pub handle: String,                // Normalized handle, also the PDA seed
+    pub profile: Pubkey,               // Profile holding (or last holding) the handle
+    pub owner: Pubkey,                 // Wallet that owns that profile
+    pub is_active: bool,               // False once released
+    pub claimed_at: i64,
+    pub released_at: i64,              // Start of the re-claim cooldown
+    pub bump: u8,
+}
+
+impl HandleRecord {
+    /// The normalized form of a submitted handle, used for the PDA seed and
+    /// stored on the record. Handles are case-insensitive, so `Alice` and
+    /// `alice` claim the same record.
+    pub fn normalize(handle: &str) -> String {
+        handle.to_ascii_lowercase()
+    }
+
+    /// Validates a normalized `handle`: 3-30 chars of `a-z`, `0-9` and `_`,
+    /// starting with a letter, not ending with `_`, and not a reserved word.
+    pub fn validate(handle: &str) -> Result<()> {
+        require!(
+            handle.len() >= HANDLE_MIN_LEN && handle.len() <= HANDLE_MAX_LEN,
+            ProfileManagerError::InvalidHandle
+        );
+
+        let bytes = handle.as_bytes();
+        require!(
+            bytes.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'_'),
+            ProfileManagerError::InvalidHandleCharacters
+        );
+        require!(bytes[0].is_ascii_lowercase(), ProfileManagerError::InvalidHandleCharacters);
+        require!(bytes[bytes.len() - 1] != b'_', ProfileManagerError::InvalidHandleCharacters);
+
+        require!(!RESERVED_HANDLES.contains(&handle), ProfileManagerError::HandleReserved);
+
+        Ok(())
+    }
+
+    /// A record can be claimed when it is fresh, or when it was released and
+    /// either the cooldown has passed or the previous owner is taking it back.
+    pub fn assert_claimable(&self, owner: &Pubkey, now: i64) -> Result<()> {
+        if self.handle.is_empty() {
+            return Ok(());
+        }
+
+        require!(!self.is_active, ProfileManagerError::HandleTaken);
+        require!(
+            self.owner == *owner || now >= self.released_at + HANDLE_RELEASE_COOLDOWN_SECONDS,
+            ProfileManagerError::HandleCooldownActive
+        );
+
+        Ok(())
+    }
+}
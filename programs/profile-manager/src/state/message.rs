@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use solana_keccak_hasher::hashv;
+
+/// Post-acceptance conversation for an accepted contact request (`Accepted`,
+/// `Disputed`, `Responded` or `Arbitrated`), at
+/// `[b"message_thread", contact_request]`. Payloads stay off-chain, encrypted
+/// with `contact-envelope`; each post appends its hash to a keccak chain and
+/// is logged as a `MessagePosted` event, so either side can later prove what
+/// was sent and that the target replied. Outlives the contact request; once
+/// that is closed, `close_message_thread` returns the rent to `payer` and the
+/// events remain the record.
+#[account]
+#[derive(InitSpace)]
+pub struct MessageThread {
+    pub contact_request: Pubkey,
+    pub requester: Pubkey,
+    pub target: Pubkey,                // Target profile owner
+    pub target_profile: Pubkey,
+    pub payer: Pubkey,                 // Paid the rent; refunded on close
+    pub message_count: u32,
+    pub requester_message_count: u32,
+    pub target_message_count: u32,
+    pub first_target_reply_at: i64,    // 0 until the target posts
+    pub head: [u8; 32],                // Hash chain over every post, zero when empty
+    pub opened_at: i64,
+    pub last_message_at: i64,
+    pub bump: u8,
+}
+
+impl MessageThread {
+    pub fn is_participant(&self, key: &Pubkey) -> bool {
+        *key == self.requester || *key == self.target
+    }
+
+    pub fn has_target_replied(&self) -> bool {
+        self.target_message_count > 0
+    }
+
+    /// Appends a post: `head = keccak(head || sender || message_hash || posted_at)`.
+    pub fn append(&mut self, sender: &Pubkey, message_hash: &[u8; 32], now: i64) {
+        self.head = hashv(&[&self.head, sender.as_ref(), message_hash, &now.to_le_bytes()]).to_bytes();
+        self.message_count += 1;
+        if *sender == self.target {
+            if self.target_message_count == 0 {
+                self.first_target_reply_at = now;
+            }
+            self.target_message_count += 1;
+        } else {
+            self.requester_message_count += 1;
+        }
+        self.last_message_at = now;
+    }
+}
@@ -1,3 +1,19 @@
 pub mod profile;
+pub mod handle;
+pub mod contact;
+pub mod platform;
+pub mod message;
+pub mod dispute;
+pub mod credits;
+pub mod campaign;
+pub mod subscription;
 
-pub use profile::*;
\ No newline at end of file
+pub use profile::*;
+pub use handle::*;
+pub use contact::*;
+pub use platform::*;
+pub use message::*;
+pub use dispute::*;
+pub use credits::*;
+pub use campaign::*;
+pub use subscription::*;
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::errors::ProfileManagerError;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Hard caps; the admin can only tune fees within these
+pub const MAX_CONTACT_FEE_BPS: u16 = 2_000;     // 20% of rejected/expired deposits
+pub const MAX_BOUNTY_FEE_BPS: u16 = 1_000;      // 10% of hiring bounties
+pub const MAX_MARKETPLACE_FEE_BPS: u16 = 1_000; // 10% of resume sales
+
+pub const MAX_DISPUTE_WINDOW_HOURS: u16 = 720;  // 30 days
+pub const MAX_ARBITRATORS: usize = 10;
+pub const MAX_KEEPER_REWARD_LAMPORTS: u64 = 2_000_000; // 0.002 SOL per cranked request
+
+/// Global fee settings shared by every CardPass program. Lives at
+/// `[b"platform_config"]` under profile-manager; the other programs read it
+/// with `seeds::program = profile_manager::ID`.
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub admin: Pubkey,                 // Can update fees and rotate admin/treasury
+    pub treasury: Pubkey,              // Wallet that owns the fee token accounts
+    pub contact_fee_bps: u16,          // Cut of rejected/expired contact deposits
+    pub bounty_fee_bps: u16,           // Cut of distributed hiring rewards
+    pub marketplace_fee_bps: u16,      // Cut of resume marketplace sales
+    pub dispute_window_hours: u16,     // Hold on accepted contact payouts, 0 = pay at once
+    #[max_len(10)]
+    pub arbitrators: Vec<Pubkey>,      // May settle contact disputes
+    pub keeper_reward_lamports: u64,   // Paid per cranked request out of its reclaimed rent
+    pub bump: u8,
+}
+
+/// Registry entry for a mint accepted as payment, at `[b"accepted_mint", mint]`.
+/// Checked wherever funds enter an escrow, pool or sale; payouts from funds
+/// already held keep working after a mint is deactivated.
+#[account]
+#[derive(InitSpace)]
+pub struct AcceptedMint {
+    pub mint: Pubkey,
+    pub decimals: u8,                  // Cached from the mint for off-chain display
+    pub is_active: bool,               // Admin can pause new payments in this mint
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl PlatformConfig {
+    pub fn validate_fees(contact_fee_bps: u16, bounty_fee_bps: u16, marketplace_fee_bps: u16) -> Result<()> {
+        require!(contact_fee_bps <= MAX_CONTACT_FEE_BPS, ProfileManagerError::FeeTooHigh);
+        require!(bounty_fee_bps <= MAX_BOUNTY_FEE_BPS, ProfileManagerError::FeeTooHigh);
+        require!(marketplace_fee_bps <= MAX_MARKETPLACE_FEE_BPS, ProfileManagerError::FeeTooHigh);
+        Ok(())
+    }
+
+    pub fn is_arbitrator(&self, key: &Pubkey) -> bool {
+        self.arbitrators.contains(key)
+    }
+
+    pub fn dispute_window_secs(&self) -> i64 {
+        self.dispute_window_hours as i64 * 3600
+    }
+
+    /// Fee owed on `amount` at `fee_bps`, rounded down.
+    pub fn fee_for(amount: u64, fee_bps: u16) -> u64 {
+        (amount as u128 * fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::ProfileManagerError;
+use super::{SubscriptionTerms, BPS_DENOMINATOR};
 
 #[account]
 #[derive(InitSpace)]
@@ -14,15 +16,27 @@ pub struct Profile {
     #[max_len(280)]
     pub bio: String,                   // Brief description (Twitter-like limit)
     #[max_len(30)]
-    pub handle: String,                // Unique handle for profile URL
+    pub handle: String,                // Unique handle for profile URL, empty once released
     #[max_len(5)]
     pub contact_prices: Vec<ContactPriceTier>, // Contact pricing tiers
-    pub response_time_hours: u16,      // Expected response time in hours
+    pub response_time_hours: u16,      // Headline response time; each tier sets its own window
+    pub quota_day: i64,                // Day (unix days) the counts below refer to
+    pub tier_requests_today: [u16; MAX_CONTACT_TIERS], // Requests sent per tier on `quota_day`
+    pub cancellation_policy: Option<CancellationPolicy>, // None = requesters may always cancel for free
+    pub contact_policy: Option<Pubkey>, // ContactPolicy PDA, once the owner has created one
+    pub encryption_key: Option<[u8; 32]>, // X25519 public key contact messages are sealed to
+    pub availability: Availability,    // Whether new contact requests are welcome
+    pub max_pending_requests: u16,     // 0 = no cap on simultaneously pending requests
+    pub pending_request_count: u16,    // Requests sent and not yet resolved
 
     // 🔐 Decentralized private data (zk-compressed)
     pub resume_merkle_tree: Option<Pubkey>, // Merkle tree address for resume
     pub resume_leaf_index: Option<u32>,     // Position in the tree
-    pub resume_root_hash: Option<[u8; 32]>, // Root hash for verification
+    pub resume_root_hash: Option<[u8; 32]>, // Tree root right after compression; verification reads the live root
+    pub resume_leaf_data_hash: Option<[u8; 32]>, // Bubblegum data hash, needed to burn on replace
+    pub resume_leaf_nonce: Option<u64>,      // Bubblegum nonce the leaf was minted with, needed to burn on replace
+    #[max_len(200)]
+    pub resume_metadata_uri: String,         // Encrypted resume metadata (IPFS)
 
     // NFT metadata
     pub nft_mint: Option<Pubkey>,      // NFT mint address for digital business card
@@ -34,31 +48,109 @@ pub struct Profile {
     pub bump: u8,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct ContactRequest {
-    pub requester: Pubkey,
-    pub target_profile: Pubkey,
-    #[max_len(1000)]
-    pub message: String,
-    pub amount: u64,
-    pub created_at: i64,
-    pub expires_at: i64,
-    pub status: ContactStatus,
-    pub bump: u8,
+impl Profile {
+    /// Counts a request against the tier's daily quota, starting a fresh count
+    /// on the first request of each UTC day.
+    pub fn consume_tier_quota(&mut self, tier_index: u8, now: i64) -> Result<()> {
+        let today = now.div_euclid(86_400);
+        if self.quota_day != today {
+            self.quota_day = today;
+            self.tier_requests_today = [0; MAX_CONTACT_TIERS];
+        }
+
+        let count = &mut self.tier_requests_today[tier_index as usize];
+        if let Some(quota) = self.contact_prices[tier_index as usize].daily_quota {
+            require!(*count < quota, ProfileManagerError::DailyQuotaReached);
+        }
+        *count = count.saturating_add(1);
+        Ok(())
+    }
+
+    /// Frees a pending slot once one of the profile's requests resolves.
+    pub fn release_pending_slot(&mut self) {
+        self.pending_request_count = self.pending_request_count.saturating_sub(1);
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
-pub enum ContactStatus {
-    Pending,
-    Responded,
-    Rejected,
-    Expired,
+/// Busy is informational; only Paused turns new requests away.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum Availability {
+    Open,
+    Busy,
+    Paused,
 }
 
+pub const MAX_CONTACT_TIERS: usize = 5;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct ContactPriceTier {
     pub price: u64,
     #[max_len(50)]
     pub description: String,
-}
\ No newline at end of file
+    pub channel: ContactChannel,       // What the requester gets on acceptance
+    pub response_time_hours: u16,      // Response window for requests at this tier
+    pub daily_quota: Option<u16>,      // Max requests per UTC day, None = unlimited
+    pub sla: Option<SlaPayoutCurve>,   // None = accepting always pays the full price
+    pub subscription: Option<SubscriptionTerms>, // None = pay per contact only
+}
+
+impl ContactPriceTier {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.price > 0, ProfileManagerError::InvalidContactTier);
+        require!(self.description.len() <= 50, ProfileManagerError::InvalidContactTier);
+        require!(
+            self.response_time_hours > 0 && self.response_time_hours <= 168,
+            ProfileManagerError::InvalidResponseTime
+        );
+        require!(self.daily_quota != Some(0), ProfileManagerError::InvalidContactTier);
+        if let Some(sla) = &self.sla {
+            sla.validate()?;
+            require!(
+                sla.full_payout_hours <= self.response_time_hours,
+                ProfileManagerError::InvalidPayoutCurve
+            );
+        }
+        if let Some(terms) = &self.subscription {
+            terms.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ContactChannel {
+    Message,
+    EmailReveal,
+    Call15,
+    Call30,
+    Call60,
+}
+
+/// Pays the target in full for answers within `full_payout_hours` of sending,
+/// then slides linearly down to `min_payout_bps` at `expires_at`. The rest of
+/// the deposit goes back to the requester.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct SlaPayoutCurve {
+    pub full_payout_hours: u16,
+    pub min_payout_bps: u16,           // Target's share for an answer right at expiry
+}
+
+impl SlaPayoutCurve {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.full_payout_hours > 0 && self.min_payout_bps as u64 <= BPS_DENOMINATOR,
+            ProfileManagerError::InvalidPayoutCurve
+        );
+        Ok(())
+    }
+}
+
+pub const MAX_CANCELLATION_FEE_BPS: u16 = 5_000; // At most half the deposit
+
+/// Lets requesters withdraw for free shortly after sending, and compensates
+/// the target for the slot they held afterwards.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct CancellationPolicy {
+    pub free_cancel_minutes: u16,      // Grace period after sending
+    pub cancellation_fee_bps: u16,     // Share of the deposit paid to the target after the grace period
+}
@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use crate::errors::ProfileManagerError;
+use super::{ContactChannel, ContactPriceTier};
+
+pub const MAX_SUBSCRIPTION_PERIOD_DAYS: u16 = 366;
+pub const MAX_SUBSCRIPTION_PERIODS: u32 = 12;
+
+/// A recurring package offered on a contact tier, e.g. "up to 4 calls a
+/// month" at the tier's channel.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct SubscriptionTerms {
+    pub period_price: u64,
+    pub period_days: u16,
+    pub quota: u16,                    // Requests per period
+}
+
+impl SubscriptionTerms {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.period_price > 0
+                && self.period_days > 0
+                && self.period_days <= MAX_SUBSCRIPTION_PERIOD_DAYS
+                && self.quota > 0,
+            ProfileManagerError::InvalidSubscriptionTerms
+        );
+        Ok(())
+    }
+}
+
+/// A requester's prepaid subscription to one of a profile's tiers, at
+/// `[b"subscription", subscriber, target_profile]`. The prepaid periods sit
+/// in a vault at `[b"subscription_vault", subscription]` owned by this PDA.
+///
+/// Periods run back to back from `started_at`. Each period's price is
+/// released to the target once the period has passed; requests sent at the
+/// subscribed tier draw on the period's quota instead of escrowing a price,
+/// and once it is used up are paid for as usual.
+/// Cancelling pays the target for finished periods plus a prorated share of
+/// the current one, the larger of the time elapsed and the quota used, and
+/// refunds the rest. Terms are snapshotted, so later edits to the tier's
+/// package do not change a running subscription. The tier itself is
+/// matched by its index, channel and price: once the target moves or
+/// reprices it, requests are no longer covered and are paid for as usual,
+/// and the subscriber can cancel for the unused periods. Drawn requests
+/// record the vault, so the subscription cannot be closed until they are.
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub subscriber: Pubkey,
+    pub target_profile: Pubkey,
+    pub target: Pubkey,                // Profile owner at subscribe time, paid each period
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub tier_index: u8,
+    pub channel: ContactChannel,       // Snapshot of the subscribed tier
+    pub tier_price: u64,
+    pub period_price: u64,
+    pub period_secs: i64,
+    pub quota: u16,
+    pub started_at: i64,
+    pub periods: u32,                  // Prepaid periods from started_at
+    pub periods_released: u32,         // Periods paid out to the target
+    pub usage_period: u32,             // Period `used` counts for
+    pub used: u16,
+    pub requests_sent: u32,
+    pub open_requests: u32,            // Drawn requests not yet closed; they settle against the vault
+    pub balance: u64,                  // Still held for the target or a refund
+    pub total_deposited: u64,          // Net of transfer fees
+    pub total_released: u64,
+    pub total_refunded: u64,
+    pub status: SubscriptionStatus,
+    pub cancelled_at: i64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub fn ends_at(&self) -> i64 {
+        self.started_at + self.periods as i64 * self.period_secs
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.status == SubscriptionStatus::Active && now < self.ends_at()
+    }
+
+    /// Index of the period `now` falls in, counted from `started_at`.
+    pub fn current_period(&self, now: i64) -> u32 {
+        ((now - self.started_at) / self.period_secs) as u32
+    }
+
+    /// Requests already drawn in `period`.
+    pub fn used_in(&self, period: u32) -> u16 {
+        if self.usage_period == period { self.used } else { 0 }
+    }
+
+    /// Whether `tier`, at `tier_index` on the target's profile, is still the
+    /// tier subscribed to.
+    pub fn is_tier(&self, tier_index: u8, tier: &ContactPriceTier) -> bool {
+        tier_index == self.tier_index && tier.channel == self.channel && tier.price == self.tier_price
+    }
+
+    /// Whether a request at `tier_index` can draw on this subscription at
+    /// `now`, i.e. whether `draw` would succeed.
+    pub fn covers(&self, tier_index: u8, tier: &ContactPriceTier, now: i64) -> bool {
+        self.is_active(now) && self.is_tier(tier_index, tier) && self.used_in(self.current_period(now)) < self.quota
+    }
+
+    /// Counts a request at `tier_index` against the current period's quota.
+    pub fn draw(&mut self, tier_index: u8, tier: &ContactPriceTier, now: i64) -> Result<()> {
+        require!(self.is_active(now), ProfileManagerError::SubscriptionInactive);
+        require!(self.is_tier(tier_index, tier), ProfileManagerError::SubscriptionTierMismatch);
+
+        let period = self.current_period(now);
+        if self.usage_period != period {
+            self.usage_period = period;
+            self.used = 0;
+        }
+        require!(self.used < self.quota, ProfileManagerError::SubscriptionQuotaReached);
+        self.used += 1;
+        self.requests_sent += 1;
+        self.open_requests += 1;
+        Ok(())
+    }
+
+    /// Releases a drawn request once it is closed.
+    pub fn record_closed(&mut self) {
+        self.open_requests = self.open_requests.saturating_sub(1);
+    }
+
+    /// Finished periods not yet paid out, and what they are owed.
+    pub fn releasable(&self, now: i64) -> (u32, u64) {
+        let finished = self.current_period(now).min(self.periods);
+        let periods = finished - self.periods_released;
+        // Transfer-fee mints can leave the vault short of the list price
+        let amount = (periods as u64 * self.period_price).min(self.balance);
+        (periods, amount)
+    }
+
+    /// The target's share of the current, unfinished period when cancelling
+    /// at `now`: the larger of the time elapsed and the quota used.
+    pub fn prorated_share(&self, now: i64) -> u64 {
+        let period = self.current_period(now);
+        if period >= self.periods {
+            return 0;
+        }
+        let elapsed = (now - self.started_at - period as i64 * self.period_secs) as u128;
+        let price = self.period_price as u128;
+        let by_time = price * elapsed / self.period_secs as u128;
+        let by_usage = price * self.used_in(period) as u128 / self.quota as u128;
+        (by_time.max(by_usage) as u64).min(self.balance)
+    }
+
+    pub fn record_release(&mut self, periods: u32, amount: u64) {
+        self.periods_released += periods;
+        self.balance -= amount;
+        self.total_released += amount;
+    }
+
+    /// Whether every prepaid token has gone to the target or back to the
+    /// subscriber.
+    pub fn is_settled(&self) -> bool {
+        self.status == SubscriptionStatus::Cancelled || self.periods_released == self.periods
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum SubscriptionStatus {
+    Active,
+    Cancelled, // Current period prorated, unused periods refunded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn subscription() -> Subscription {
+        Subscription {
+            subscriber: Pubkey::new_unique(),
+            target_profile: Pubkey::new_unique(),
+            target: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            tier_index: 1,
+            channel: ContactChannel::Call30,
+            tier_price: 500,
+            period_price: 1_000,
+            period_secs: 30 * DAY,
+            quota: 2,
+            started_at: 1_000,
+            periods: 2,
+            periods_released: 0,
+            usage_period: 0,
+            used: 0,
+            requests_sent: 0,
+            open_requests: 0,
+            balance: 2_000,
+            total_deposited: 2_000,
+            total_released: 0,
+            total_refunded: 0,
+            status: SubscriptionStatus::Active,
+            cancelled_at: 0,
+            bump: 255,
+        }
+    }
+
+    fn tier() -> ContactPriceTier {
+        ContactPriceTier {
+            price: 500,
+            description: "30 minute call".to_string(),
+            channel: ContactChannel::Call30,
+            response_time_hours: 48,
+            daily_quota: None,
+            sla: None,
+            subscription: None,
+        }
+    }
+
+    #[test]
+    fn covers_its_tier_until_the_quota_is_used() {
+        let mut subscription = subscription();
+        let now = subscription.started_at + DAY;
+        assert!(!subscription.covers(0, &tier(), now));
+
+        for _ in 0..2 {
+            assert!(subscription.covers(1, &tier(), now));
+            subscription.draw(1, &tier(), now).unwrap();
+        }
+        assert!(!subscription.covers(1, &tier(), now));
+        assert_eq!(subscription.draw(1, &tier(), now).unwrap_err(), ProfileManagerError::SubscriptionQuotaReached.into());
+        assert_eq!((subscription.requests_sent, subscription.open_requests), (2, 2));
+    }
+
+    #[test]
+    fn quota_resets_each_period() {
+        let mut subscription = subscription();
+        let first = subscription.started_at + DAY;
+        subscription.draw(1, &tier(), first).unwrap();
+        subscription.draw(1, &tier(), first).unwrap();
+
+        let second = first + subscription.period_secs;
+        assert_eq!(subscription.used_in(1), 0);
+        assert!(subscription.covers(1, &tier(), second));
+        subscription.draw(1, &tier(), second).unwrap();
+        assert_eq!(subscription.used_in(1), 1);
+        assert_eq!(subscription.used_in(0), 0);
+    }
+
+    #[test]
+    fn stops_covering_once_ended_or_cancelled() {
+        let mut subscription = subscription();
+        assert!(!subscription.covers(1, &tier(), subscription.ends_at()));
+        assert_eq!(
+            subscription.draw(1, &tier(), subscription.ends_at()).unwrap_err(),
+            ProfileManagerError::SubscriptionInactive.into()
+        );
+
+        subscription.status = SubscriptionStatus::Cancelled;
+        assert!(!subscription.covers(1, &tier(), subscription.started_at));
+    }
+
+    #[test]
+    fn closing_drawn_requests_releases_them() {
+        let mut subscription = subscription();
+        let now = subscription.started_at;
+        subscription.draw(1, &tier(), now).unwrap();
+        subscription.draw(1, &tier(), now).unwrap();
+
+        subscription.record_closed();
+        assert_eq!(subscription.open_requests, 1);
+        subscription.record_closed();
+        subscription.record_closed();
+        assert_eq!(subscription.open_requests, 0);
+        // Closing does not give quota back
+        assert!(!subscription.covers(1, &tier(), now));
+    }
+
+    #[test]
+    fn stops_covering_a_moved_or_repriced_tier() {
+        let mut subscription = subscription();
+        let now = subscription.started_at;
+
+        let repriced = ContactPriceTier { price: 900, ..tier() };
+        assert!(!subscription.covers(1, &repriced, now));
+        let moved = ContactPriceTier { channel: ContactChannel::Message, ..tier() };
+        assert!(!subscription.covers(1, &moved, now));
+        assert_eq!(
+            subscription.draw(1, &repriced, now).unwrap_err(),
+            ProfileManagerError::SubscriptionTierMismatch.into()
+        );
+        assert_eq!(subscription.used, 0);
+    }
+}
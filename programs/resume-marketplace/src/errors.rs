@@ -4,4 +4,8 @@ use anchor_lang::prelude::*;
 pub enum ResumeMarketplaceError {
     #[msg("This resume is not for sale.")]
     NotForSale,
+    #[msg("Royalty and platform fee exceed the sale price.")]
+    FeesExceedPrice,
+    #[msg("Mint is not accepted for payments.")]
+    MintNotAccepted,
 }
@@ -6,3 +6,14 @@ pub struct RoyaltyPaid {
     pub original_creator: Pubkey,
     pub amount: u64,
 }
+
+#[event]
+pub struct ResumePurchased {
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub seller_amount: u64,
+    pub royalty_amount: u64,
+    pub platform_fee: u64,
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use profile_manager::state::AcceptedMint;
 use crate::state::ResumeNft;
+use crate::errors::ResumeMarketplaceError;
 
 #[derive(Accounts)]
 pub struct ListResume<'info> {
@@ -16,13 +18,23 @@ pub struct ListResume<'info> {
     )]
     pub resume_nft: Account<'info, ResumeNft>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         associated_token::mint = mint,
         associated_token::authority = owner,
     )]
-    pub owner_token_account: Account<'info, TokenAccount>,
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"accepted_mint", payment_mint.key().as_ref()],
+        bump = accepted_mint.bump,
+        seeds::program = profile_manager::ID,
+        constraint = accepted_mint.is_active @ ResumeMarketplaceError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
 
     pub system_program: Program<'info, System>,
 }
@@ -33,6 +45,7 @@ pub fn list_resume(ctx: Context<ListResume>, price: u64, royalty_percentage: u8)
     resume_nft.owner = ctx.accounts.owner.key();
     resume_nft.mint = ctx.accounts.mint.key();
     resume_nft.price = price;
+    resume_nft.payment_mint = ctx.accounts.payment_mint.key();
     resume_nft.is_for_sale = true;
     resume_nft.royalty_percentage = royalty_percentage;
     resume_nft.bump = ctx.bumps.resume_nft;
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use profile_manager::state::{AcceptedMint, PlatformConfig};
 use crate::state::ResumeNft;
 use crate::errors::ResumeMarketplaceError;
-use crate::events::RoyaltyPaid;
+use crate::events::{ResumePurchased, RoyaltyPaid};
 
 #[derive(Accounts)]
 pub struct PurchaseResume<'info> {
@@ -21,33 +22,76 @@ pub struct PurchaseResume<'info> {
     /// CHECK: This is not dangerous because we are checking this in the instruction
     pub owner: AccountInfo<'info>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = owner,
+        associated_token::token_program = token_program,
     )]
-    pub owner_token_account: Account<'info, TokenAccount>,
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = buyer,
         associated_token::mint = mint,
         associated_token::authority = buyer,
+        associated_token::token_program = token_program,
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub buyer_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"accepted_mint", resume_nft.payment_mint.as_ref()],
+        bump = accepted_mint.bump,
+        seeds::program = profile_manager::ID,
+        constraint = accepted_mint.is_active @ ResumeMarketplaceError::MintNotAccepted
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
 
-    #[account(mut)]
-    pub owner_usdc_account: Account<'info, TokenAccount>,
+    #[account(address = resume_nft.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
 
-    #[account(mut)]
-    pub original_creator_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = buyer,
+        token::token_program = payment_token_program
+    )]
+    pub buyer_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = owner
+    )]
+    pub owner_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = resume_nft.original_creator
+    )]
+    pub original_creator_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        seeds::program = profile_manager::ID
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = platform_config.treasury
+    )]
+    pub treasury_usdc_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    /// Token program of the resume NFT mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program of the payment mint, which may differ (e.g. Token-2022 stablecoins)
+    pub payment_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
 }
@@ -56,30 +100,51 @@ pub fn purchase_resume(ctx: Context<PurchaseResume>) -> Result<()> {
     let resume_nft = &mut ctx.accounts.resume_nft;
     require!(resume_nft.is_for_sale, ResumeMarketplaceError::NotForSale);
 
+    let platform_fee = PlatformConfig::fee_for(resume_nft.price, ctx.accounts.platform_config.marketplace_fee_bps);
     let royalty_amount = resume_nft.price * resume_nft.royalty_percentage as u64 / 100;
-    let seller_amount = resume_nft.price - royalty_amount;
+    let seller_amount = resume_nft.price
+        .checked_sub(royalty_amount)
+        .and_then(|amount| amount.checked_sub(platform_fee))
+        .ok_or(ResumeMarketplaceError::FeesExceedPrice)?;
+    let payment_decimals = ctx.accounts.payment_mint.decimals;
+
+    // Transfer platform fee to treasury
+    if platform_fee > 0 {
+        let transfer_fee_ctx = CpiContext::new(
+            ctx.accounts.payment_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.buyer_usdc_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: ctx.accounts.treasury_usdc_account.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(transfer_fee_ctx, platform_fee, payment_decimals)?;
+    }
 
     // Transfer USDC from buyer to owner
     let transfer_to_seller_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        ctx.accounts.payment_token_program.to_account_info(),
+        TransferChecked {
             from: ctx.accounts.buyer_usdc_account.to_account_info(),
+            mint: ctx.accounts.payment_mint.to_account_info(),
             to: ctx.accounts.owner_usdc_account.to_account_info(),
             authority: ctx.accounts.buyer.to_account_info(),
         },
     );
-    token::transfer(transfer_to_seller_ctx, seller_amount)?;
+    token_interface::transfer_checked(transfer_to_seller_ctx, seller_amount, payment_decimals)?;
 
     // Transfer royalty to original creator
     let transfer_royalty_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        ctx.accounts.payment_token_program.to_account_info(),
+        TransferChecked {
             from: ctx.accounts.buyer_usdc_account.to_account_info(),
+            mint: ctx.accounts.payment_mint.to_account_info(),
             to: ctx.accounts.original_creator_usdc_account.to_account_info(),
             authority: ctx.accounts.buyer.to_account_info(),
         },
     );
-    token::transfer(transfer_royalty_ctx, royalty_amount)?;
+    token_interface::transfer_checked(transfer_royalty_ctx, royalty_amount, payment_decimals)?;
 
     emit!(RoyaltyPaid {
         mint: ctx.accounts.mint.key(),
@@ -96,14 +161,25 @@ pub fn purchase_resume(ctx: Context<PurchaseResume>) -> Result<()> {
     let signer = &[&seeds[..]];
     let transfer_nft_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.owner_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.buyer_token_account.to_account_info(),
             authority: resume_nft.to_account_info(),
         },
         signer,
     );
-    token::transfer(transfer_nft_ctx, 1)?;
+    token_interface::transfer_checked(transfer_nft_ctx, 1, ctx.accounts.mint.decimals)?;
+
+    emit!(ResumePurchased {
+        mint: ctx.accounts.mint.key(),
+        seller: resume_nft.owner,
+        buyer: ctx.accounts.buyer.key(),
+        price: resume_nft.price,
+        seller_amount,
+        royalty_amount,
+        platform_fee,
+    });
 
     resume_nft.owner = ctx.accounts.buyer.key();
     resume_nft.is_for_sale = false;
@@ -6,9 +6,6 @@ pub mod errors;
 pub mod events;
 
 use instructions::*;
-use state::*;
-use errors::*;
-use events::*;
 
 declare_id!("9DWZHxCyaPDRj6mWAKP5nMLVBcSL7thyb38RjnEzmnE8");
 
@@ -7,6 +7,7 @@ pub struct ResumeNft {
     pub owner: Pubkey,
     pub mint: Pubkey,
     pub price: u64,
+    pub payment_mint: Pubkey,          // Mint the price is denominated in
     pub is_for_sale: bool,
     pub verified: bool,
     pub royalty_percentage: u8,
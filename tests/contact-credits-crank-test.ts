@@ -0,0 +1,342 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ProfileManager } from "../target/types/profile_manager";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  acceptMint,
+  airdrop,
+  balance,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  expectError,
+  feeFor,
+  fundedTokenAccount,
+  pm,
+  respondToContact,
+  sendContact,
+  treasuryTokenAccount,
+  USDC,
+} from "./helpers";
+
+describe("Contact credits and the expiry crank", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ProfileManager as Program<ProfileManager>;
+  const pdas = pm(program);
+
+  const talent = Keypair.generate();
+  const recruiter = Keypair.generate(); // Prepays credits
+  const keeper = Keypair.generate();
+
+  let mint: PublicKey;
+  let profile: PublicKey;
+  let encryptionKey: number[];
+  let recruiterTokenAccount: PublicKey;
+  let talentTokenAccount: PublicKey;
+  let treasury: PublicKey;
+  let contactCredits: PublicKey;
+  let creditsVault: PublicKey;
+  let contactFeeBps: number;
+
+  const sendWithCredits = (tierIndex = 0) =>
+    sendContact(program, {
+      requester: recruiter,
+      targetProfile: profile,
+      recipientKey: encryptionKey,
+      requesterTokenAccount: recruiterTokenAccount,
+      mint,
+      tierIndex,
+      contactCredits,
+    });
+
+  const respond = (request: PublicKey, accept: boolean) =>
+    respondToContact(program, request, accept, {
+      target: talent,
+      requesterTokenAccount: recruiterTokenAccount,
+      targetTokenAccount: talentTokenAccount,
+      treasuryTokenAccount: treasury,
+      mint,
+      contactCredits,
+    });
+
+  const setKeeperReward = (lamports: number) =>
+    program.methods
+      .setKeeperReward(new anchor.BN(lamports))
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, [talent, recruiter, keeper]);
+
+    mint = await createUsdcMint(provider, recruiter);
+    await acceptMint(provider, program, mint);
+    recruiterTokenAccount = await fundedTokenAccount(provider, mint, recruiter, recruiter.publicKey, 100 * USDC);
+    talentTokenAccount = await fundedTokenAccount(provider, mint, recruiter, talent.publicKey);
+    treasury = await treasuryTokenAccount(provider, program, mint);
+
+    await program.methods
+      .setArbitrationConfig(0, [])
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
+      .rpc();
+    contactFeeBps = (await ensurePlatformConfig(provider, program)).config.contactFeeBps;
+
+    ({ profile, encryptionKey } = await createProfile(program, talent, {
+      tiers: [
+        contactTier(10 * USDC),
+        contactTier(25 * USDC, { description: "30 minute call", channel: { call30: {} } }),
+      ],
+    }));
+
+    contactCredits = pdas.contactCredits(recruiter.publicKey, mint);
+    creditsVault = pdas.creditsVault(contactCredits);
+  });
+
+  after(async () => {
+    await setKeeperReward(0);
+  });
+
+  describe("Credits", () => {
+    it("Tops up prepaid credits into the credits vault", async () => {
+      await program.methods
+        .topUpCredits(new anchor.BN(30 * USDC))
+        .accounts({
+          contactCredits,
+          vault: creditsVault,
+          owner: recruiter.publicKey,
+          ownerTokenAccount: recruiterTokenAccount,
+          mint,
+          acceptedMint: pdas.acceptedMint(mint),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([recruiter])
+        .rpc();
+
+      const credits = await program.account.contactCredits.fetch(contactCredits);
+      expect(credits.owner.toBase58()).to.equal(recruiter.publicKey.toBase58());
+      expect(credits.balance.toNumber()).to.equal(30 * USDC);
+      expect(credits.totalDeposited.toNumber()).to.equal(30 * USDC);
+      expect(await balance(provider, creditsVault)).to.equal(30 * USDC);
+      console.log("✅ 30 USDC of credits topped up");
+    });
+
+    it("Reserves credits for a request instead of opening an escrow", async () => {
+      const before = await balance(provider, recruiterTokenAccount);
+      const { contactRequest } = await sendWithCredits();
+
+      const request = await program.account.contactRequest.fetch(contactRequest);
+      expect(request.funding).to.deep.equal({ credits: {} });
+      expect(request.vault.toBase58()).to.equal(creditsVault.toBase58());
+      expect(request.amount.toNumber()).to.equal(10 * USDC);
+      expect(await provider.connection.getAccountInfo(pdas.escrow(contactRequest))).to.be.null;
+      expect(await balance(provider, recruiterTokenAccount)).to.equal(before);
+
+      const credits = await program.account.contactCredits.fetch(contactCredits);
+      expect(credits.balance.toNumber()).to.equal(20 * USDC);
+      expect(credits.reserved.toNumber()).to.equal(10 * USDC);
+      expect(credits.requestsFunded.toNumber()).to.equal(1);
+      console.log("✅ Request 0 reserved 10 USDC of credits");
+    });
+
+    it("Takes exactly one funding source", async () => {
+      const thread = pdas.contactThread(recruiter.publicKey, profile);
+      const nonce = (await program.account.contactThread.fetch(thread)).requestCount.toNumber();
+      const contactRequest = pdas.contactRequest(recruiter.publicKey, profile, nonce);
+
+      await expectError(
+        program.methods
+          .sendContactRequest(
+            {
+              recipientKey: encryptionKey,
+              ephemeralKey: Array(32).fill(1),
+              nonce: Array(24).fill(2),
+              ciphertext: Buffer.alloc(48, 3),
+            },
+            0,
+            null
+          )
+          .accounts({
+            contactThread: thread,
+            contactRequest,
+            targetProfile: profile,
+            contactPolicy: null,
+            requester: recruiter.publicKey,
+            requesterTokenAccount: recruiterTokenAccount,
+            jobSponsor: null,
+            sponsorTokenAccount: null,
+            escrowTokenAccount: pdas.escrow(contactRequest),
+            contactCredits,
+            outreachCampaign: null,
+            subscription: pdas.subscription(recruiter.publicKey, profile),
+            usdcMint: mint,
+            acceptedMint: pdas.acceptedMint(mint),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          })
+          .signers([recruiter])
+          .rpc(),
+        "InvalidFundingSource"
+      );
+
+      // 20 USDC left, so the 25 USDC call does not fit
+      await expectError(sendWithCredits(1), "InsufficientCredits");
+      console.log("✅ Double funding and overdrafts rejected");
+    });
+
+    it("Refunds a rejected request to the credit balance, less the fee", async () => {
+      const treasuryBefore = await balance(provider, treasury);
+      await respond(pdas.contactRequest(recruiter.publicKey, profile, 0), false);
+
+      const fee = feeFor(10 * USDC, contactFeeBps);
+      const credits = await program.account.contactCredits.fetch(contactCredits);
+      expect(credits.balance.toNumber()).to.equal(30 * USDC - fee);
+      expect(credits.reserved.toNumber()).to.equal(0);
+      expect(credits.totalSpent.toNumber()).to.equal(fee);
+      expect(credits.totalRefunded.toNumber()).to.equal(10 * USDC - fee);
+      expect(await balance(provider, treasury)).to.equal(treasuryBefore + fee);
+      expect(await balance(provider, creditsVault)).to.equal(30 * USDC - fee);
+      console.log(`✅ Request 0 rejected; ${fee / USDC} USDC fee paid from the vault`);
+    });
+
+    it("Pays the target from the credits vault on accept", async () => {
+      const { contactRequest } = await sendWithCredits();
+      const talentBefore = await balance(provider, talentTokenAccount);
+      await respond(contactRequest, true);
+
+      const fee = feeFor(10 * USDC, contactFeeBps);
+      const credits = await program.account.contactCredits.fetch(contactCredits);
+      expect(credits.balance.toNumber()).to.equal(20 * USDC - fee);
+      expect(credits.reserved.toNumber()).to.equal(0);
+      expect(credits.totalSpent.toNumber()).to.equal(10 * USDC + fee);
+      expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore + 10 * USDC);
+      console.log("✅ Request 1 accepted and paid from credits");
+    });
+
+    it("Closes credit-funded requests without token accounts", async () => {
+      for (const nonce of [0, 1]) {
+        const contactRequest = pdas.contactRequest(recruiter.publicKey, profile, nonce);
+        await program.methods
+          .closeContactRequest()
+          .accounts({
+            contactRequest,
+            requester: recruiter.publicKey,
+            requesterTokenAccount: null,
+            escrowTokenAccount: null,
+            usdcMint: null,
+            subscription: null,
+            outreachCampaign: null,
+            jobSponsor: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([recruiter])
+          .rpc();
+        expect(await provider.connection.getAccountInfo(contactRequest)).to.be.null;
+      }
+      console.log("✅ Credit-funded requests closed");
+    });
+
+    it("Withdraws only the free balance", async () => {
+      const withdraw = (amount: number) =>
+        program.methods
+          .withdrawCredits(new anchor.BN(amount))
+          .accounts({
+            contactCredits,
+            vault: creditsVault,
+            owner: recruiter.publicKey,
+            ownerTokenAccount: recruiterTokenAccount,
+            mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([recruiter])
+          .rpc();
+
+      const { balance: free } = await program.account.contactCredits.fetch(contactCredits);
+      await expectError(withdraw(free.toNumber() + 1), "InsufficientCredits");
+      await expectError(withdraw(0), "InsufficientCredits");
+
+      const before = await balance(provider, recruiterTokenAccount);
+      await withdraw(5 * USDC);
+
+      const credits = await program.account.contactCredits.fetch(contactCredits);
+      expect(credits.balance.toNumber()).to.equal(free.toNumber() - 5 * USDC);
+      expect(credits.totalWithdrawn.toNumber()).to.equal(5 * USDC);
+      expect(await balance(provider, recruiterTokenAccount)).to.equal(before + 5 * USDC);
+      console.log("✅ 5 USDC withdrawn from credits");
+    });
+  });
+
+  describe("Crank", () => {
+    const crank = (remainingAccounts: anchor.web3.AccountMeta[]) =>
+      program.methods
+        .crankExpiredContacts()
+        .accounts({
+          keeper: keeper.publicKey,
+          platformConfig: pdas.platformConfig(),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([keeper])
+        .rpc();
+
+    const writable = (pubkey: PublicKey) => ({ pubkey, isSigner: false, isWritable: true });
+    const readonly = (pubkey: PublicKey) => ({ pubkey, isSigner: false, isWritable: false });
+
+    // One group per request, in `CRANK_ACCOUNTS_PER_REQUEST` order
+    const group = (contactRequest: PublicKey, escrow: PublicKey) => [
+      writable(contactRequest),
+      writable(pdas.contactThread(recruiter.publicKey, profile)),
+      writable(profile),
+      writable(escrow),
+      readonly(mint),
+      writable(recruiterTokenAccount),
+      writable(recruiter.publicKey),
+      writable(treasury),
+    ];
+
+    it("Caps the keeper reward", async () => {
+      await expectError(setKeeperReward(2_000_001), "FeeTooHigh");
+      await setKeeperReward(5_000);
+
+      const config = await program.account.platformConfig.fetch(pdas.platformConfig());
+      expect(config.keeperRewardLamports.toNumber()).to.equal(5_000);
+      console.log("✅ Keeper reward set to 5000 lamports");
+    });
+
+    it("Rejects a batch with no complete account group", async () => {
+      const { contactRequest, escrow } = await sendContact(program, {
+        requester: recruiter,
+        targetProfile: profile,
+        recipientKey: encryptionKey,
+        requesterTokenAccount: recruiterTokenAccount,
+        mint,
+      });
+
+      await expectError(crank([]), "InvalidCrankAccounts");
+      await expectError(crank(group(contactRequest, escrow!).slice(0, 7)), "InvalidCrankAccounts");
+      console.log("✅ Malformed crank batches rejected");
+    });
+
+    it("Skips requests that have not expired yet", async () => {
+      const nonce = (await program.account.contactThread.fetch(pdas.contactThread(recruiter.publicKey, profile)))
+        .requestCount.toNumber() - 1;
+      const contactRequest = pdas.contactRequest(recruiter.publicKey, profile, nonce);
+      const escrow = pdas.escrow(contactRequest);
+
+      const keeperBefore = await provider.connection.getBalance(keeper.publicKey);
+      await crank(group(contactRequest, escrow));
+
+      const request = await program.account.contactRequest.fetch(contactRequest);
+      expect(request.status).to.deep.equal({ pending: {} });
+      expect(await balance(provider, escrow)).to.equal(10 * USDC);
+      // No reward, only the transaction fee
+      expect(await provider.connection.getBalance(keeper.publicKey)).to.be.lessThan(keeperBefore);
+      console.log("✅ Unexpired request left alone by the crank");
+    });
+  });
+});
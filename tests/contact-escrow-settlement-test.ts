@@ -0,0 +1,594 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ProfileManager } from "../target/types/profile_manager";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  acceptMint,
+  airdrop,
+  balance,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  expectError,
+  feeFor,
+  fundedTokenAccount,
+  pm,
+  respondToContact,
+  sealedMessage,
+  sendContact,
+  sleep,
+  treasuryTokenAccount,
+  USDC,
+} from "./helpers";
+
+describe("Contact escrow settlement", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ProfileManager as Program<ProfileManager>;
+  const pdas = pm(program);
+
+  const talent = Keypair.generate();     // Target profile owner
+  const recruiter = Keypair.generate();  // Requester
+  const arbitrator = Keypair.generate(); // Rules on the dispute
+  const stranger = Keypair.generate();
+
+  // Tier 0 pays out in full for 12h, then slides to half at the 24h expiry
+  const tiers = [
+    contactTier(10 * USDC, { sla: { fullPayoutHours: 12, minPayoutBps: 5_000 } }),
+    contactTier(25 * USDC, { description: "30 minute call", channel: { call30: {} }, responseTimeHours: 48 }),
+    contactTier(5 * USDC, { description: "Email reveal", channel: { emailReveal: {} } }),
+  ];
+
+  let mint: PublicKey;
+  let profile: PublicKey;
+  let encryptionKey: number[];
+  let recruiterTokenAccount: PublicKey;
+  let talentTokenAccount: PublicKey;
+  let treasury: PublicKey;
+  let contactFeeBps: number;
+
+  const send = (tierIndex: number) =>
+    sendContact(program, {
+      requester: recruiter,
+      targetProfile: profile,
+      recipientKey: encryptionKey,
+      requesterTokenAccount: recruiterTokenAccount,
+      mint,
+      tierIndex,
+    });
+
+  const respond = (request: PublicKey, accept: boolean) =>
+    respondToContact(program, request, accept, {
+      target: talent,
+      requesterTokenAccount: recruiterTokenAccount,
+      targetTokenAccount: talentTokenAccount,
+      treasuryTokenAccount: treasury,
+      mint,
+    });
+
+  const requestAt = (nonce: number) => pdas.contactRequest(recruiter.publicKey, profile, nonce);
+
+  // Accounts shared by the requester-side settlement instructions
+  const requesterAccounts = (request: PublicKey) => ({
+    contactRequest: request,
+    contactThread: pdas.contactThread(recruiter.publicKey, profile),
+    targetProfile: profile,
+    requester: recruiter.publicKey,
+    requesterTokenAccount: recruiterTokenAccount,
+    escrowTokenAccount: pdas.escrow(request),
+    contactCredits: null,
+    outreachCampaign: null,
+    usdcMint: mint,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  const cancel = (request: PublicKey) =>
+    program.methods
+      .cancelContactRequest()
+      .accounts({ ...requesterAccounts(request), targetTokenAccount: talentTokenAccount })
+      .signers([recruiter])
+      .rpc();
+
+  const counter = (request: PublicKey, tierIndex: number) =>
+    program.methods
+      .counterContactRequest(tierIndex)
+      .accounts({ contactRequest: request, targetProfile: profile, target: talent.publicKey })
+      .signers([talent])
+      .rpc();
+
+  // Thread and dispute of the disputed request, closable once it is closed
+  const closeMessageThread = (request: PublicKey) =>
+    program.methods
+      .closeMessageThread()
+      .accounts({ messageThread: pdas.messageThread(request), contactRequest: request, payer: recruiter.publicKey })
+      .signers([recruiter])
+      .rpc();
+
+  const closeDispute = (request: PublicKey) =>
+    program.methods
+      .closeDispute()
+      .accounts({ dispute: pdas.dispute(request), contactRequest: request, requester: recruiter.publicKey })
+      .signers([recruiter])
+      .rpc();
+
+  const setArbitration = (hours: number, arbitrators: PublicKey[]) =>
+    program.methods
+      .setArbitrationConfig(hours, arbitrators)
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, [talent, recruiter, arbitrator, stranger]);
+
+    mint = await createUsdcMint(provider, recruiter);
+    await acceptMint(provider, program, mint);
+    recruiterTokenAccount = await fundedTokenAccount(provider, mint, recruiter, recruiter.publicKey, 1_000 * USDC);
+    talentTokenAccount = await fundedTokenAccount(provider, mint, recruiter, talent.publicKey);
+    treasury = await treasuryTokenAccount(provider, program, mint);
+
+    // Payouts below assume accepted requests settle at once
+    await setArbitration(0, []);
+    contactFeeBps = (await ensurePlatformConfig(provider, program)).config.contactFeeBps;
+
+    ({ profile, encryptionKey } = await createProfile(program, talent, { tiers }));
+    console.log("👤 Talent profile created with 3 tiers");
+  });
+
+  after(async () => {
+    await setArbitration(0, []);
+  });
+
+  it("Escrows the tier price and snapshots the SLA curve", async () => {
+    const before = await balance(provider, recruiterTokenAccount);
+    const { contactRequest, escrow, nonce } = await send(0);
+    expect(nonce).to.equal(0);
+
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.status).to.deep.equal({ pending: {} });
+    expect(request.funding).to.deep.equal({ escrow: {} });
+    expect(request.vault.toBase58()).to.equal(escrow!.toBase58());
+    expect(request.amount.toNumber()).to.equal(10 * USDC);
+    expect(request.expiresAt.toNumber()).to.equal(request.createdAt.toNumber() + 24 * 3600);
+    expect(request.fullPayoutUntil.toNumber()).to.equal(request.createdAt.toNumber() + 12 * 3600);
+    expect(request.minPayoutBps).to.equal(5_000);
+    expect(await balance(provider, escrow!)).to.equal(10 * USDC);
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(before - 10 * USDC);
+
+    const thread = await program.account.contactThread.fetch(pdas.contactThread(recruiter.publicKey, profile));
+    expect(thread.requestCount.toNumber()).to.equal(1);
+    expect(thread.pendingCount).to.equal(1);
+    expect((await program.account.profile.fetch(profile)).pendingRequestCount).to.equal(1);
+    console.log("✅ Request 0 escrowed with its SLA curve");
+  });
+
+  it("Only takes messages sealed to the target's current key", async () => {
+    const args = {
+      requester: recruiter,
+      targetProfile: profile,
+      recipientKey: encryptionKey,
+      requesterTokenAccount: recruiterTokenAccount,
+      mint,
+    };
+    await expectError(
+      sendContact(program, { ...args, message: sealedMessage(Array(32).fill(7)) }),
+      "EncryptionKeyMismatch"
+    );
+    await expectError(
+      sendContact(program, { ...args, message: sealedMessage(encryptionKey, 8) }),
+      "InvalidEncryptedMessage"
+    );
+    await expectError(
+      sendContact(program, { ...args, message: sealedMessage(encryptionKey, 1_017) }),
+      "MessageTooLong"
+    );
+    console.log("✅ Malformed envelopes rejected");
+  });
+
+  it("Refuses to expire a request before its window closes", async () => {
+    await expectError(
+      program.methods
+        .handleExpiredContact()
+        .accounts({
+          contactRequest: requestAt(0),
+          contactThread: pdas.contactThread(recruiter.publicKey, profile),
+          targetProfile: profile,
+          requesterTokenAccount: recruiterTokenAccount,
+          escrowTokenAccount: pdas.escrow(requestAt(0)),
+          contactCredits: null,
+          outreachCampaign: null,
+          usdcMint: mint,
+          platformConfig: pdas.platformConfig(),
+          treasuryTokenAccount: treasury,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc(),
+      "ContactNotExpired"
+    );
+    console.log("✅ Early expiry rejected");
+  });
+
+  it("Pays the target in full for a timely accept, without a platform fee", async () => {
+    const talentBefore = await balance(provider, talentTokenAccount);
+    const recruiterBefore = await balance(provider, recruiterTokenAccount);
+    const treasuryBefore = await balance(provider, treasury);
+    await respond(requestAt(0), true);
+
+    const request = await program.account.contactRequest.fetch(requestAt(0));
+    expect(request.status).to.deep.equal({ responded: {} });
+    expect(request.payoutAmount.toNumber()).to.equal(10 * USDC);
+    expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore + 10 * USDC);
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(recruiterBefore);
+    expect(await balance(provider, treasury)).to.equal(treasuryBefore);
+    expect(await balance(provider, pdas.escrow(requestAt(0)))).to.equal(0);
+
+    await expectError(respond(requestAt(0), false), "ContactAlreadyProcessed");
+    console.log("✅ Request 0 accepted inside the full-payout window");
+  });
+
+  it("Refunds a rejected request less the platform fee", async () => {
+    const { contactRequest } = await send(1);
+    const recruiterBefore = await balance(provider, recruiterTokenAccount);
+    const treasuryBefore = await balance(provider, treasury);
+    await respond(contactRequest, false);
+
+    const fee = feeFor(25 * USDC, contactFeeBps);
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.status).to.deep.equal({ rejected: {} });
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(recruiterBefore + 25 * USDC - fee);
+    expect(await balance(provider, treasury)).to.equal(treasuryBefore + fee);
+
+    const thread = await program.account.contactThread.fetch(pdas.contactThread(recruiter.publicKey, profile));
+    expect(thread.acceptedCount).to.equal(1);
+    expect(thread.rejectedCount).to.equal(1);
+    expect(thread.pendingCount).to.equal(0);
+    console.log(`✅ Request 1 rejected; ${fee / USDC} USDC fee to the treasury`);
+  });
+
+  it("Cancels for free within the grace period", async () => {
+    const { contactRequest } = await send(2);
+    const recruiterBefore = await balance(provider, recruiterTokenAccount);
+    const talentBefore = await balance(provider, talentTokenAccount);
+    await cancel(contactRequest);
+
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.status).to.deep.equal({ cancelled: {} });
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(recruiterBefore + 5 * USDC);
+    expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore);
+    console.log("✅ Request 2 cancelled for free");
+  });
+
+  it("Pays the target a cancellation fee after the grace period", async () => {
+    const setPolicy = (policy: { freeCancelMinutes: number; cancellationFeeBps: number } | null) =>
+      program.methods
+        .setCancellationPolicy(policy)
+        .accounts({ profile, owner: talent.publicKey })
+        .signers([talent])
+        .rpc();
+
+    await expectError(setPolicy({ freeCancelMinutes: 0, cancellationFeeBps: 5_001 }), "FeeTooHigh");
+    await setPolicy({ freeCancelMinutes: 0, cancellationFeeBps: 2_000 });
+
+    const { contactRequest } = await send(2);
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.cancellationFeeBps).to.equal(2_000);
+    expect(request.freeCancelUntil.toNumber()).to.equal(request.createdAt.toNumber());
+
+    // Let the clock move past the zero-minute grace period
+    await sleep(3_000);
+    const recruiterBefore = await balance(provider, recruiterTokenAccount);
+    const talentBefore = await balance(provider, talentTokenAccount);
+    await cancel(contactRequest);
+
+    expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore + 1 * USDC);
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(recruiterBefore + 4 * USDC);
+
+    // Policy changes only apply to requests sent afterwards
+    await setPolicy(null);
+    console.log("✅ Request 3 cancelled late; 20% went to the talent");
+  });
+
+  it("Tops up and pays the target when the requester accepts a counter-offer", async () => {
+    const { contactRequest } = await send(2);
+
+    await expectError(counter(contactRequest, 2), "InvalidCounterOffer");
+    await counter(contactRequest, 1);
+
+    let request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.status).to.deep.equal({ countered: {} });
+    expect(request.counterTierIndex).to.equal(1);
+    expect(request.counterAmount.toNumber()).to.equal(25 * USDC);
+
+    // The target waits for the requester now
+    await expectError(respond(contactRequest, true), "CounterOfferPending");
+
+    const recruiterBefore = await balance(provider, recruiterTokenAccount);
+    const talentBefore = await balance(provider, talentTokenAccount);
+    await program.methods
+      .acceptCounterOffer()
+      .accounts({
+        ...requesterAccounts(contactRequest),
+        targetTokenAccount: talentTokenAccount,
+        platformConfig: pdas.platformConfig(),
+      })
+      .signers([recruiter])
+      .rpc();
+
+    request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.status).to.deep.equal({ responded: {} });
+    expect(request.tierIndex).to.equal(1);
+    expect(request.amount.toNumber()).to.equal(25 * USDC);
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(recruiterBefore - 20 * USDC);
+    expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore + 25 * USDC);
+    console.log("✅ Request 4 countered up to 25 USDC and accepted");
+  });
+
+  it("Refunds in full when the requester declines a counter-offer", async () => {
+    const { contactRequest } = await send(1);
+    await counter(contactRequest, 2);
+
+    const recruiterBefore = await balance(provider, recruiterTokenAccount);
+    await program.methods
+      .declineCounterOffer()
+      .accounts(requesterAccounts(contactRequest))
+      .signers([recruiter])
+      .rpc();
+
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.status).to.deep.equal({ declined: {} });
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(recruiterBefore + 25 * USDC);
+
+    const thread = await program.account.contactThread.fetch(pdas.contactThread(recruiter.publicKey, profile));
+    expect(thread.declinedCount).to.equal(1);
+    console.log("✅ Request 5 counter declined and refunded");
+  });
+
+  it("Counters only paid requests, within the counter tier's daily quota", async () => {
+    // A second target allowlists the stranger and takes one email reveal a day
+    const gatekeeper = Keypair.generate();
+    await airdrop(provider, [gatekeeper]);
+    const { profile: gated, encryptionKey: gatedKey } = await createProfile(program, gatekeeper, {
+      tiers: [tiers[0], contactTier(5 * USDC, { description: "Email reveal", channel: { emailReveal: {} }, dailyQuota: 1 })],
+    });
+    const contactPolicy = pdas.contactPolicy(gated);
+    await program.methods
+      .updateContactPolicy([{ allow: { 0: stranger.publicKey } }])
+      .accounts({ profile: gated, contactPolicy, owner: gatekeeper.publicKey, systemProgram: SystemProgram.programId })
+      .signers([gatekeeper])
+      .rpc();
+
+    const sendTo = async (requester: Keypair) =>
+      (
+        await sendContact(program, {
+          requester,
+          targetProfile: gated,
+          recipientKey: gatedKey,
+          requesterTokenAccount: await fundedTokenAccount(provider, mint, recruiter, requester.publicKey),
+          mint,
+          contactPolicy,
+        })
+      ).contactRequest;
+    const counterOn = (request: PublicKey) =>
+      program.methods
+        .counterContactRequest(1)
+        .accounts({ contactRequest: request, targetProfile: gated, target: gatekeeper.publicKey })
+        .signers([gatekeeper])
+        .rpc();
+
+    const free = await sendTo(stranger);
+    expect((await program.account.contactRequest.fetch(free)).amount.toNumber()).to.equal(0);
+    await expectError(counterOn(free), "AllowlistedCounterNotAllowed");
+
+    await counterOn(await sendTo(recruiter));
+    await expectError(counterOn(await sendTo(recruiter)), "DailyQuotaReached");
+    console.log("✅ Allowlisted request kept free and counter quota enforced");
+  });
+
+  describe("Dispute window", () => {
+    let contactRequest: PublicKey;
+
+    before(async () => {
+      await setArbitration(24, [arbitrator.publicKey]);
+      ({ contactRequest } = await send(0));
+    });
+
+    it("Holds the accepted payout in escrow until the deadline", async () => {
+      const talentBefore = await balance(provider, talentTokenAccount);
+      await respond(contactRequest, true);
+
+      const request = await program.account.contactRequest.fetch(contactRequest);
+      expect(request.status).to.deep.equal({ accepted: {} });
+      expect(request.payoutAmount.toNumber()).to.equal(10 * USDC);
+      expect(request.disputeDeadline.toNumber()).to.be.greaterThan(request.createdAt.toNumber() + 23 * 3600);
+      expect(await balance(provider, pdas.escrow(contactRequest))).to.equal(10 * USDC);
+      expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore);
+
+      await expectError(
+        program.methods
+          .releaseAcceptedContact()
+          .accounts({
+            contactRequest,
+            targetProfile: profile,
+            targetTokenAccount: talentTokenAccount,
+            escrowTokenAccount: pdas.escrow(contactRequest),
+            contactCredits: null,
+            outreachCampaign: null,
+            usdcMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc(),
+        "DisputeWindowOpen"
+      );
+      console.log("✅ Request 6 accepted with its payout held");
+    });
+
+    it("Opens a message thread for the accepted request", async () => {
+      const messageThread = pdas.messageThread(contactRequest);
+      await program.methods
+        .openMessageThread()
+        .accounts({
+          messageThread,
+          contactRequest,
+          targetProfile: profile,
+          payer: recruiter.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([recruiter])
+        .rpc();
+
+      const post = (sender: Keypair, fill: number) =>
+        program.methods
+          .postMessage(Array(32).fill(fill))
+          .accounts({ messageThread, sender: sender.publicKey })
+          .signers([sender])
+          .rpc();
+
+      await post(recruiter, 1);
+      await post(talent, 2);
+      await expectError(post(stranger, 3), "NotThreadParticipant");
+
+      const thread = await program.account.messageThread.fetch(messageThread);
+      expect(thread.messageCount).to.equal(2);
+      expect(thread.requesterMessageCount).to.equal(1);
+      expect(thread.targetMessageCount).to.equal(1);
+      expect(thread.firstTargetReplyAt.toNumber()).to.be.greaterThan(0);
+      expect((await program.account.contactRequest.fetch(contactRequest)).hasMessageThread).to.equal(true);
+      console.log("✅ Both parties posted to the thread");
+    });
+
+    it("Lets the requester dispute and an arbitrator split the held payout", async () => {
+      const dispute = pdas.dispute(contactRequest);
+      await program.methods
+        .openDispute(Array(32).fill(9))
+        .accounts({
+          dispute,
+          contactRequest,
+          contactThread: pdas.contactThread(recruiter.publicKey, profile),
+          targetProfile: profile,
+          messageThread: pdas.messageThread(contactRequest),
+          requester: recruiter.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([recruiter])
+        .rpc();
+
+      let opened = await program.account.dispute.fetch(dispute);
+      expect(opened.amount.toNumber()).to.equal(10 * USDC);
+      expect(opened.targetReplied).to.equal(true);
+      expect((await program.account.contactRequest.fetch(contactRequest)).status).to.deep.equal({ disputed: {} });
+
+      const resolve = (signer: Keypair, targetBps: number) =>
+        program.methods
+          .resolveDispute(targetBps)
+          .accounts({
+            dispute,
+            contactRequest,
+            arbitrator: signer.publicKey,
+            platformConfig: pdas.platformConfig(),
+            requesterTokenAccount: recruiterTokenAccount,
+            targetTokenAccount: talentTokenAccount,
+            escrowTokenAccount: pdas.escrow(contactRequest),
+            contactCredits: null,
+            outreachCampaign: null,
+            usdcMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([signer])
+          .rpc();
+
+      await expectError(resolve(stranger, 5_000), "NotArbitrator");
+      await expectError(resolve(arbitrator, 10_001), "InvalidDisputeRuling");
+
+      const recruiterBefore = await balance(provider, recruiterTokenAccount);
+      const talentBefore = await balance(provider, talentTokenAccount);
+      await resolve(arbitrator, 4_000);
+
+      const request = await program.account.contactRequest.fetch(contactRequest);
+      expect(request.status).to.deep.equal({ arbitrated: {} });
+      expect(request.payoutAmount.toNumber()).to.equal(4 * USDC);
+      expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore + 4 * USDC);
+      expect(await balance(provider, recruiterTokenAccount)).to.equal(recruiterBefore + 6 * USDC);
+
+      opened = await program.account.dispute.fetch(dispute);
+      expect(opened.arbitrator!.toBase58()).to.equal(arbitrator.publicKey.toBase58());
+      expect(opened.targetBps).to.equal(4_000);
+
+      await expectError(closeMessageThread(contactRequest), "ContactRequestNotClosed");
+      await expectError(closeDispute(contactRequest), "ContactRequestNotClosed");
+      console.log("✅ Dispute on request 6 split 40/60");
+    });
+  });
+
+  it("Closes resolved requests and returns their rent", async () => {
+    const thread = await program.account.contactThread.fetch(pdas.contactThread(recruiter.publicKey, profile));
+    const count = thread.requestCount.toNumber();
+    expect(count).to.equal(7);
+
+    for (let nonce = 0; nonce < count; nonce++) {
+      const request = requestAt(nonce);
+      await program.methods
+        .closeContactRequest()
+        .accounts({
+          contactRequest: request,
+          requester: recruiter.publicKey,
+          requesterTokenAccount: recruiterTokenAccount,
+          escrowTokenAccount: pdas.escrow(request),
+          usdcMint: mint,
+          subscription: null,
+          outreachCampaign: null,
+          jobSponsor: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([recruiter])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(request)).to.be.null;
+      expect(await provider.connection.getAccountInfo(pdas.escrow(request))).to.be.null;
+    }
+
+    // The thread keeps the pair's history
+    const after = await program.account.contactThread.fetch(pdas.contactThread(recruiter.publicKey, profile));
+    expect(after.requestCount.toNumber()).to.equal(count);
+    expect(after.disputedCount).to.equal(1);
+    console.log("✅ All 7 requests closed");
+  });
+
+  it("Closes the disputed request's message thread and dispute after the request", async () => {
+    const request = requestAt(6);
+    const rentBefore = await provider.connection.getBalance(recruiter.publicKey);
+    await closeMessageThread(request);
+    await closeDispute(request);
+
+    expect(await provider.connection.getAccountInfo(pdas.messageThread(request))).to.be.null;
+    expect(await provider.connection.getAccountInfo(pdas.dispute(request))).to.be.null;
+    expect(await provider.connection.getBalance(recruiter.publicKey)).to.be.greaterThan(rentBefore);
+    console.log("✅ Thread and dispute rent reclaimed");
+  });
+
+  it("Refuses to close a request that is still pending", async () => {
+    const { contactRequest } = await send(2);
+    await expectError(
+      program.methods
+        .closeContactRequest()
+        .accounts({
+          contactRequest,
+          requester: recruiter.publicKey,
+          requesterTokenAccount: recruiterTokenAccount,
+          escrowTokenAccount: pdas.escrow(contactRequest),
+          usdcMint: mint,
+          subscription: null,
+          outreachCampaign: null,
+          jobSponsor: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([recruiter])
+        .rpc(),
+      "ContactNotResolved"
+    );
+    await cancel(contactRequest);
+    console.log("✅ Pending request cannot be closed");
+  });
+});
@@ -0,0 +1,228 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ContactGate } from "../target/types/contact_gate";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  airdrop,
+  balance,
+  createUsdcMint,
+  expectError,
+  findPda,
+  fundedTokenAccount,
+  u64,
+  USDC,
+} from "./helpers";
+
+describe("Contact Gate: attention auctions", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const contactGate = anchor.workspace.ContactGate as Program<ContactGate>;
+
+  const owner = Keypair.generate(); // Inbox owner selling responses
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const carol = Keypair.generate();
+  const dave = Keypair.generate();
+
+  let mint: PublicKey;
+  let ownerTokenAccount: PublicKey;
+  const tokenAccounts = new Map<string, PublicKey>();
+  let inbox: PublicKey;
+  let auction: PublicKey;
+
+  const tokenAccountOf = (bidder: Keypair) => tokenAccounts.get(bidder.publicKey.toBase58())!;
+  const bidPda = (bidder: Keypair) =>
+    findPda([Buffer.from("bid"), auction.toBuffer(), bidder.publicKey.toBuffer()], contactGate.programId);
+  const bidEscrowPda = (bid: PublicKey) => findPda([Buffer.from("bid_escrow"), bid.toBuffer()], contactGate.programId);
+
+  const createAuction = (slots: number, minBid: number, windowHours: number) =>
+    contactGate.methods
+      .createAuction(slots, new anchor.BN(minBid), windowHours)
+      .accounts({
+        auction: findPda([Buffer.from("auction"), inbox.toBuffer(), u64(0)], contactGate.programId),
+        inbox,
+        owner: owner.publicKey,
+        payer: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+  const placeBid = (bidder: Keypair, amount: number) => {
+    const bid = bidPda(bidder);
+    return contactGate.methods
+      .placeBid(new anchor.BN(amount), Array(32).fill(1))
+      .accounts({
+        bid,
+        auction,
+        inbox,
+        bidder: bidder.publicKey,
+        payer: bidder.publicKey,
+        bidderTokenAccount: tokenAccountOf(bidder),
+        bidEscrow: bidEscrowPda(bid),
+        mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bidder])
+      .rpc();
+  };
+
+  const updateInbox = (isOpen: boolean) =>
+    contactGate.methods
+      .updateInbox(null, isOpen, null)
+      .accounts({ inbox, owner: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+  const leaderBids = async () =>
+    (await contactGate.account.attentionAuction.fetch(auction)).leaders.map((leader) => leader.bid.toBase58());
+
+  before(async () => {
+    await airdrop(provider, [owner, alice, bob, carol, dave], 3);
+
+    mint = await createUsdcMint(provider, owner);
+    ownerTokenAccount = await fundedTokenAccount(provider, mint, owner, owner.publicKey);
+    for (const bidder of [alice, bob, carol, dave]) {
+      tokenAccounts.set(
+        bidder.publicKey.toBase58(),
+        await fundedTokenAccount(provider, mint, owner, bidder.publicKey, 100 * USDC)
+      );
+    }
+
+    inbox = findPda([Buffer.from("inbox"), owner.publicKey.toBuffer()], contactGate.programId);
+    auction = findPda([Buffer.from("auction"), inbox.toBuffer(), u64(0)], contactGate.programId);
+
+    await contactGate.methods
+      .createInbox([{ price: new anchor.BN(25 * USDC), responseWindowHours: 24, label: "Intro call" }], 0)
+      .accounts({
+        inbox,
+        owner: owner.publicKey,
+        payer: owner.publicKey,
+        mint,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+    console.log("📬 Inbox created");
+  });
+
+  it("Opens an auction for a fixed number of responses", async () => {
+    await expectError(createAuction(0, 5 * USDC, 1), "InvalidAuctionConfig");
+    await expectError(createAuction(11, 5 * USDC, 1), "InvalidAuctionConfig");
+    await expectError(createAuction(2, 0, 1), "InvalidAuctionConfig");
+    await expectError(createAuction(2, 5 * USDC, 169), "InvalidAuctionConfig");
+
+    await createAuction(2, 5 * USDC, 1);
+
+    const account = await contactGate.account.attentionAuction.fetch(auction);
+    expect(account.slots).to.equal(2);
+    expect(account.minBid.toNumber()).to.equal(5 * USDC);
+    expect(account.status).to.deep.equal({ open: {} });
+    expect(account.biddingEndsAt.toNumber()).to.equal(account.createdAt.toNumber() + 3600);
+    expect((await contactGate.account.inbox.fetch(inbox)).auctionCount.toNumber()).to.equal(1);
+    console.log("✅ Auction 0 open for 2 slots, 5 USDC minimum");
+  });
+
+  it("Ranks bids by amount and keeps only the top slots", async () => {
+    await placeBid(alice, 10 * USDC);
+    await placeBid(bob, 6 * USDC);
+    expect(await leaderBids()).to.deep.equal([bidPda(alice).toBase58(), bidPda(bob).toBase58()]);
+
+    // Carol outranks Bob, who drops off the board
+    await placeBid(carol, 8 * USDC);
+    expect(await leaderBids()).to.deep.equal([bidPda(alice).toBase58(), bidPda(carol).toBase58()]);
+
+    await expectError(placeBid(dave, 4 * USDC), "BidTooLow");
+    // 7 USDC clears the minimum but does not outrank Carol
+    await expectError(placeBid(dave, 7 * USDC), "BidTooLow");
+
+    // Bob raises by 5 to 11 USDC and takes the top slot
+    await placeBid(bob, 5 * USDC);
+    expect(await leaderBids()).to.deep.equal([bidPda(bob).toBase58(), bidPda(alice).toBase58()]);
+
+    const bid = await contactGate.account.auctionBid.fetch(bidPda(bob));
+    expect(bid.amount.toNumber()).to.equal(11 * USDC);
+    expect(await balance(provider, bidEscrowPda(bidPda(bob)))).to.equal(11 * USDC);
+
+    const account = await contactGate.account.attentionAuction.fetch(auction);
+    expect(account.bidCount).to.equal(3);
+    expect(account.bidSequence.toNumber()).to.equal(4);
+    console.log("✅ Leaders: Bob 11, Alice 10; Carol outbid");
+  });
+
+  it("Keeps blocked senders out of the auction", async () => {
+    await contactGate.methods
+      .updateInboxPolicy([{ block: [dave.publicKey] }])
+      .accounts({ inbox, owner: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await expectError(placeBid(dave, 20 * USDC), "SenderBlocked");
+    console.log("✅ Blocked bidder rejected");
+  });
+
+  it("Settles only after bidding closes", async () => {
+    const group = (bidder: Keypair) => [
+      { pubkey: bidPda(bidder), isSigner: false, isWritable: true },
+      { pubkey: bidEscrowPda(bidPda(bidder)), isSigner: false, isWritable: true },
+      { pubkey: tokenAccountOf(bidder), isSigner: false, isWritable: true },
+    ];
+    const settle = (remainingAccounts: anchor.web3.AccountMeta[]) =>
+      contactGate.methods
+        .settleAuction()
+        .accounts({ auction, ownerTokenAccount, mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+
+    await expectError(settle(group(alice).slice(0, 2)), "InvalidSettlementAccounts");
+    await expectError(settle([...group(alice), ...group(bob), ...group(carol)]), "BiddingStillOpen");
+    console.log("✅ Settlement waits for the bidding window");
+  });
+
+  it("Refuses to close an active bid", async () => {
+    const bid = bidPda(carol);
+    await expectError(
+      contactGate.methods
+        .closeBid()
+        .accounts({
+          bid,
+          bidder: carol.publicKey,
+          bidderTokenAccount: tokenAccountOf(carol),
+          bidEscrow: bidEscrowPda(bid),
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([carol])
+        .rpc(),
+      "BidNotSettled"
+    );
+    console.log("✅ Outbid escrow stays until settlement");
+  });
+
+  it("Stops auctions and bids while the inbox is closed", async () => {
+    await updateInbox(false);
+
+    await expectError(placeBid(alice, 1 * USDC), "InboxClosed");
+    await expectError(
+      contactGate.methods
+        .createAuction(1, new anchor.BN(5 * USDC), 1)
+        .accounts({
+          auction: findPda([Buffer.from("auction"), inbox.toBuffer(), u64(1)], contactGate.programId),
+          inbox,
+          owner: owner.publicKey,
+          payer: owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc(),
+      "InboxClosed"
+    );
+
+    await updateInbox(true);
+    console.log("✅ Closed inbox takes no auctions or bids");
+  });
+});
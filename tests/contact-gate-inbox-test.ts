@@ -0,0 +1,393 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ContactGate } from "../target/types/contact_gate";
+import { JobApplication } from "../target/types/job_application";
+import { ProfileManager } from "../target/types/profile_manager";
+import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+describe("Contact Gate: inbox request lifecycle", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const contactGate = anchor.workspace.ContactGate as Program<ContactGate>;
+  const jobApplication = anchor.workspace.JobApplication as Program<JobApplication>;
+  const profileManager = anchor.workspace.ProfileManager as Program<ProfileManager>;
+
+  const recruiter = Keypair.generate(); // Inbox owner
+  const applicant = Keypair.generate(); // Pays to reach the recruiter
+  const stranger = Keypair.generate();  // Anyone, for the permissionless expire
+
+  const price = new anchor.BN(25 * 1_000_000);
+
+  let mint: PublicKey;
+  let recruiterTokenAccount: PublicKey;
+  let applicantTokenAccount: PublicKey;
+  let inboxPda: PublicKey;
+
+  const u64 = (n: number | anchor.BN) => new anchor.BN(n).toArrayLike(Buffer, "le", 8);
+  const requestPda = (nonce: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("request"), inboxPda.toBuffer(), u64(nonce)],
+      contactGate.programId
+    )[0];
+  const escrowPda = (request: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("escrow"), request.toBuffer()], contactGate.programId)[0];
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  async function sendRequest(nonce: number, memo: number): Promise<PublicKey> {
+    const request = requestPda(nonce);
+    await contactGate.methods
+      .sendRequest(0, Array(32).fill(memo))
+      .accounts({
+        request,
+        inbox: inboxPda,
+        requester: applicant.publicKey,
+        payer: applicant.publicKey,
+        requesterTokenAccount: applicantTokenAccount,
+        escrowTokenAccount: escrowPda(request),
+        mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([applicant])
+      .rpc();
+    return request;
+  }
+
+  async function respond(request: PublicKey, accept: boolean) {
+    await contactGate.methods
+      .respondToRequest(accept)
+      .accounts({
+        request,
+        inbox: inboxPda,
+        owner: recruiter.publicKey,
+        ownerTokenAccount: recruiterTokenAccount,
+        requesterTokenAccount: applicantTokenAccount,
+        escrowTokenAccount: escrowPda(request),
+        mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([recruiter])
+      .rpc();
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+      expect.fail(`expected ${code}`);
+    } catch (err: any) {
+      expect(err.toString()).to.include(code);
+    }
+  }
+
+  before(async () => {
+    await Promise.all(
+      [recruiter, applicant, stranger].map(async (kp) => {
+        const sig = await provider.connection.requestAirdrop(kp.publicKey, 3 * anchor.web3.LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig, "confirmed");
+      })
+    );
+
+    mint = await createMint(provider.connection, recruiter, recruiter.publicKey, null, 6);
+    recruiterTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, recruiter, mint, recruiter.publicKey
+    )).address;
+    applicantTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, applicant, mint, applicant.publicKey
+    )).address;
+    await mintTo(provider.connection, recruiter, mint, recruiterTokenAccount, recruiter, 1_000 * 1_000_000);
+    await mintTo(provider.connection, recruiter, mint, applicantTokenAccount, recruiter, 500 * 1_000_000);
+
+    [inboxPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox"), recruiter.publicKey.toBuffer()],
+      contactGate.programId
+    );
+
+    await contactGate.methods
+      .createInbox([{ price, responseWindowHours: 24, label: "Intro call" }], 0)
+      .accounts({
+        inbox: inboxPda,
+        owner: recruiter.publicKey,
+        payer: recruiter.publicKey,
+        mint,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([recruiter])
+      .rpc();
+
+    console.log("📬 Recruiter inbox created");
+  });
+
+  it("Escrows the tier price on send", async () => {
+    const before = await balance(applicantTokenAccount);
+    const request = await sendRequest(0, 1);
+
+    const account = await contactGate.account.inboxRequest.fetch(request);
+    expect(account.status).to.deep.equal({ pending: {} });
+    expect(account.amount.toString()).to.equal(price.toString());
+    expect(await balance(escrowPda(request))).to.equal(price.toNumber());
+    expect(await balance(applicantTokenAccount)).to.equal(before - price.toNumber());
+
+    const inbox = await contactGate.account.inbox.fetch(inboxPda);
+    expect(inbox.pendingCount).to.equal(1);
+    expect(inbox.requestCount.toNumber()).to.equal(1);
+    console.log("✅ Request 0 pending with escrow funded");
+  });
+
+  it("Refuses to expire a request before its window closes", async () => {
+    await expectError(
+      contactGate.methods
+        .expireRequest()
+        .accounts({
+          request: requestPda(0),
+          inbox: inboxPda,
+          caller: stranger.publicKey,
+          requesterTokenAccount: applicantTokenAccount,
+          escrowTokenAccount: escrowPda(requestPda(0)),
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([stranger])
+        .rpc(),
+      "RequestNotExpired"
+    );
+    console.log("✅ Early expiry rejected");
+  });
+
+  it("Pays the owner on accept", async () => {
+    const before = await balance(recruiterTokenAccount);
+    await respond(requestPda(0), true);
+
+    const account = await contactGate.account.inboxRequest.fetch(requestPda(0));
+    expect(account.status).to.deep.equal({ accepted: {} });
+    expect(account.resolvedAt.toNumber()).to.be.greaterThan(0);
+    expect(await balance(recruiterTokenAccount)).to.equal(before + price.toNumber());
+    expect(await balance(escrowPda(requestPda(0)))).to.equal(0);
+
+    // Answered requests can no longer be answered, cancelled or expired
+    await expectError(respond(requestPda(0), false), "RequestNotPending");
+    console.log("✅ Request 0 accepted and paid out");
+  });
+
+  it("Refunds the requester on reject", async () => {
+    const request = await sendRequest(1, 2);
+    const before = await balance(applicantTokenAccount);
+    await respond(request, false);
+
+    const account = await contactGate.account.inboxRequest.fetch(request);
+    expect(account.status).to.deep.equal({ rejected: {} });
+    expect(await balance(applicantTokenAccount)).to.equal(before + price.toNumber());
+
+    // A rejected request cannot be redeemed
+    await expectError(
+      contactGate.methods
+        .redeemRequest()
+        .accounts({ request, requester: applicant.publicKey })
+        .signers([applicant])
+        .rpc(),
+      "RequestNotAccepted"
+    );
+    console.log("✅ Request 1 rejected and refunded");
+  });
+
+  it("Refunds the requester on cancel", async () => {
+    const request = await sendRequest(2, 3);
+    const before = await balance(applicantTokenAccount);
+
+    await contactGate.methods
+      .cancelRequest()
+      .accounts({
+        request,
+        inbox: inboxPda,
+        caller: applicant.publicKey,
+        requesterTokenAccount: applicantTokenAccount,
+        escrowTokenAccount: escrowPda(request),
+        mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([applicant])
+      .rpc();
+
+    const account = await contactGate.account.inboxRequest.fetch(request);
+    expect(account.status).to.deep.equal({ cancelled: {} });
+    expect(await balance(applicantTokenAccount)).to.equal(before + price.toNumber());
+
+    const inbox = await contactGate.account.inbox.fetch(inboxPda);
+    expect(inbox.pendingCount).to.equal(0);
+    expect(inbox.acceptedCount).to.equal(1);
+    expect(inbox.rejectedCount).to.equal(1);
+    expect(inbox.cancelledCount).to.equal(1);
+    console.log("✅ Request 2 cancelled and refunded");
+  });
+
+  it("Prioritizes a job application by redeeming the accepted request via CPI", async () => {
+    // The job needs its mint on profile-manager's accepted list
+    const [platformConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("platform_config")],
+      profileManager.programId
+    );
+    const [acceptedMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("accepted_mint"), mint.toBuffer()],
+      profileManager.programId
+    );
+    if (!(await provider.connection.getAccountInfo(platformConfig))) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [profileManager.programId.toBuffer()],
+        BPF_LOADER_UPGRADEABLE_ID
+      );
+      await profileManager.methods
+        .initializePlatformConfig(provider.wallet.publicKey, 500, 500, 500)
+        .accounts({
+          platformConfig,
+          authority: provider.wallet.publicKey,
+          program: profileManager.programId,
+          programData,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+    await profileManager.methods
+      .addAcceptedMint()
+      .accounts({
+        acceptedMint,
+        mint,
+        platformConfig,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const jobId = new anchor.BN(Date.now());
+    const [jobPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("job"), recruiter.publicKey.toBuffer(), u64(jobId)],
+      jobApplication.programId
+    );
+    const [jobBounty] = PublicKey.findProgramAddressSync(
+      [Buffer.from("job_bounty"), jobPda.toBuffer()],
+      jobApplication.programId
+    );
+    const [bountyEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bounty_escrow"), jobPda.toBuffer()],
+      jobApplication.programId
+    );
+    const [bountyAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bounty_authority"), jobPda.toBuffer()],
+      jobApplication.programId
+    );
+    await jobApplication.methods
+      .createJob(
+        "Protocol Engineer",
+        "Build settlement programs",
+        ["Rust", "Anchor"],
+        new anchor.BN(100_000),
+        new anchor.BN(150_000),
+        30,
+        jobId,
+        new anchor.BN(100 * 1_000_000)
+      )
+      .accounts({
+        job: jobPda,
+        jobBounty,
+        recruiter: recruiter.publicKey,
+        recruiterTokenAccount,
+        bountyEscrowAccount: bountyEscrow,
+        bountyAuthority,
+        usdcMint: mint,
+        acceptedMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([recruiter])
+      .rpc();
+
+    const [applicationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("application"), jobPda.toBuffer(), applicant.publicKey.toBuffer()],
+      jobApplication.programId
+    );
+    await jobApplication.methods
+      .applyToJob("Paid for an intro call first", null)
+      .accounts({
+        application: applicationPda,
+        job: jobPda,
+        profile: applicant.publicKey,
+        applicant: applicant.publicKey,
+        referralLink: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([applicant])
+      .rpc();
+
+    const prioritize = (inboxRequest: PublicKey) =>
+      jobApplication.methods
+        .prioritizeApplication()
+        .accounts({
+          application: applicationPda,
+          job: jobPda,
+          recruiterInbox: inboxPda,
+          inboxRequest,
+          applicant: applicant.publicKey,
+          contactGateProgram: contactGate.programId,
+        })
+        .signers([applicant])
+        .rpc();
+
+    // Only an accepted request to the recruiter's inbox counts
+    await expectError(prioritize(requestPda(1)), "ContactNotAccepted");
+
+    await prioritize(requestPda(0));
+
+    const application = await jobApplication.account.application.fetch(applicationPda);
+    expect(application.priorityRequest.toBase58()).to.equal(requestPda(0).toBase58());
+    const request = await contactGate.account.inboxRequest.fetch(requestPda(0));
+    expect(request.redeemed).to.equal(true);
+
+    // Redeemed requests are spent
+    await expectError(
+      contactGate.methods
+        .redeemRequest()
+        .accounts({ request: requestPda(0), requester: applicant.publicKey })
+        .signers([applicant])
+        .rpc(),
+      "AlreadyRedeemed"
+    );
+    console.log("✅ Application prioritized; request 0 redeemed once");
+  });
+
+  it("Closes resolved requests and returns rent to the requester", async () => {
+    for (const nonce of [0, 1, 2]) {
+      const request = requestPda(nonce);
+      await contactGate.methods
+        .closeRequest()
+        .accounts({
+          request,
+          requester: applicant.publicKey,
+          requesterTokenAccount: applicantTokenAccount,
+          escrowTokenAccount: escrowPda(request),
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([applicant])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(request)).to.be.null;
+      expect(await provider.connection.getAccountInfo(escrowPda(request))).to.be.null;
+    }
+
+    // Inbox stats outlive the closed requests
+    const inbox = await contactGate.account.inbox.fetch(inboxPda);
+    expect(inbox.requestCount.toNumber()).to.equal(3);
+    console.log("✅ Resolved requests closed");
+  });
+});
@@ -0,0 +1,249 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ProfileManager } from "../target/types/profile_manager";
+import { JobApplication } from "../target/types/job_application";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  acceptMint,
+  airdrop,
+  balance,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  expectError,
+  feeFor,
+  findPda,
+  fundedTokenAccount,
+  pm,
+  respondToContact,
+  sealedMessage,
+  treasuryTokenAccount,
+  u64,
+  USDC,
+} from "./helpers";
+
+describe("Job-sponsored contact requests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const profileManager = anchor.workspace.ProfileManager as Program<ProfileManager>;
+  const jobApplication = anchor.workspace.JobApplication as Program<JobApplication>;
+  const pdas = pm(profileManager);
+
+  const recruiter = Keypair.generate();
+  const talent = Keypair.generate();
+
+  const jobId = new anchor.BN(Date.now());
+
+  let mint: PublicKey;
+  let recruiterTokenAccount: PublicKey;
+  let talentTokenAccount: PublicKey;
+  let treasury: PublicKey;
+  let contactFeeBps: number;
+  let profile: PublicKey;
+  let encryptionKey: number[];
+
+  let job: PublicKey;
+  let jobBounty: PublicKey;
+  let bountyEscrow: PublicKey;
+  let bountyAuthority: PublicKey;
+  let contactSponsorship: PublicKey;
+
+  const jobPda = (seed: string) => findPda([Buffer.from(seed), job.toBuffer()], jobApplication.programId);
+  const requestAt = (nonce: number) => pdas.contactRequest(recruiter.publicKey, profile, nonce);
+
+  const setCap = (cap: number) =>
+    jobApplication.methods
+      .setContactSponsorship(new anchor.BN(cap))
+      .accounts({
+        contactSponsorship,
+        job,
+        recruiter: recruiter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([recruiter])
+      .rpc();
+
+  const sponsor = (nonce: number, tierIndex = 0) =>
+    jobApplication.methods
+      .sponsorContactRequest(sealedMessage(encryptionKey), tierIndex)
+      .accounts({
+        contactSponsorship,
+        job,
+        jobBounty,
+        recruiter: recruiter.publicKey,
+        bountyAuthority,
+        bountyEscrowAccount: bountyEscrow,
+        recruiterTokenAccount,
+        contactThread: pdas.contactThread(recruiter.publicKey, profile),
+        contactRequest: requestAt(nonce),
+        targetProfile: profile,
+        contactPolicy: null,
+        escrowTokenAccount: pdas.escrow(requestAt(nonce)),
+        subscription: pdas.subscription(recruiter.publicKey, profile),
+        usdcMint: mint,
+        acceptedMint: pdas.acceptedMint(mint),
+        profileManagerProgram: profileManager.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([recruiter])
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, [recruiter, talent]);
+
+    mint = await createUsdcMint(provider, recruiter);
+    await acceptMint(provider, profileManager, mint);
+    recruiterTokenAccount = await fundedTokenAccount(provider, mint, recruiter, recruiter.publicKey, 200 * USDC);
+    talentTokenAccount = await fundedTokenAccount(provider, mint, recruiter, talent.publicKey);
+    treasury = await treasuryTokenAccount(provider, profileManager, mint);
+    contactFeeBps = (await ensurePlatformConfig(provider, profileManager)).config.contactFeeBps;
+
+    ({ profile, encryptionKey } = await createProfile(profileManager, talent, {
+      tiers: [contactTier(10 * USDC)],
+    }));
+
+    job = findPda([Buffer.from("job"), recruiter.publicKey.toBuffer(), u64(jobId)], jobApplication.programId);
+    jobBounty = jobPda("job_bounty");
+    bountyEscrow = jobPda("bounty_escrow");
+    bountyAuthority = jobPda("bounty_authority");
+    contactSponsorship = jobPda("contact_sponsorship");
+
+    await jobApplication.methods
+      .createJob(
+        "Senior Solana Developer",
+        "Build payment rails on Solana",
+        ["Rust", "Anchor"],
+        new anchor.BN(100_000),
+        new anchor.BN(150_000),
+        30,
+        jobId,
+        new anchor.BN(100 * USDC)
+      )
+      .accounts({
+        job,
+        jobBounty,
+        recruiter: recruiter.publicKey,
+        recruiterTokenAccount,
+        bountyEscrowAccount: bountyEscrow,
+        bountyAuthority,
+        usdcMint: mint,
+        acceptedMint: pdas.acceptedMint(mint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([recruiter])
+      .rpc();
+    console.log("💼 Job posted with a 100 USDC bounty");
+  });
+
+  it("Pays a contact fee from the job's bounty within the cap", async () => {
+    await setCap(15 * USDC);
+    const walletBefore = await balance(provider, recruiterTokenAccount);
+    await sponsor(0);
+
+    const request = await profileManager.account.contactRequest.fetch(requestAt(0));
+    expect(request.amount.toNumber()).to.equal(10 * USDC);
+    expect(request.job!.toBase58()).to.equal(job.toBase58());
+    expect(request.refundAuthority.toBase58()).to.equal(bountyAuthority.toBase58());
+
+    const sponsorship = await jobApplication.account.contactSponsorship.fetch(contactSponsorship);
+    expect(sponsorship.cap.toNumber()).to.equal(15 * USDC);
+    expect(sponsorship.spent.toNumber()).to.equal(10 * USDC);
+    expect(sponsorship.requestsSponsored).to.equal(1);
+    expect((await jobApplication.account.job.fetch(job)).hiringBounty.toNumber()).to.equal(90 * USDC);
+    expect((await jobApplication.account.jobBounty.fetch(jobBounty)).amount.toNumber()).to.equal(90 * USDC);
+    expect(await balance(provider, bountyEscrow)).to.equal(90 * USDC);
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(walletBefore);
+    console.log("✅ 10 USDC contact fee drawn from the bounty");
+  });
+
+  it("Keeps sponsored fees under the cap", async () => {
+    await expectError(setCap(5 * USDC), "InvalidSponsorshipCap");
+    await expectError(sponsor(1), "SponsorshipCapExceeded");
+    console.log("✅ Cap below spend and over-cap request rejected");
+  });
+
+  it("Refunds a rejected sponsored request into the bounty escrow", async () => {
+    await respondToContact(profileManager, requestAt(0), false, {
+      target: talent,
+      requesterTokenAccount: bountyEscrow,
+      targetTokenAccount: talentTokenAccount,
+      treasuryTokenAccount: treasury,
+      mint,
+    });
+
+    const fee = feeFor(10 * USDC, contactFeeBps);
+    const refunded = 10 * USDC - fee;
+    expect(await balance(provider, bountyEscrow)).to.equal(90 * USDC + refunded);
+    const request = await profileManager.account.contactRequest.fetch(requestAt(0));
+    expect(request.refunded.toNumber()).to.equal(refunded);
+
+    // Nothing is credited until the request is closed
+    const sponsorship = await jobApplication.account.contactSponsorship.fetch(contactSponsorship);
+    expect(sponsorship.spent.toNumber()).to.equal(10 * USDC);
+    console.log(`✅ ${refunded / USDC} USDC refunded to the bounty escrow`);
+  });
+
+  it("Only closes sponsored requests through the job", async () => {
+    await expectError(
+      profileManager.methods
+        .closeContactRequest()
+        .accounts({
+          contactRequest: requestAt(0),
+          requester: recruiter.publicKey,
+          requesterTokenAccount: bountyEscrow,
+          escrowTokenAccount: pdas.escrow(requestAt(0)),
+          usdcMint: mint,
+          subscription: null,
+          outreachCampaign: null,
+          jobSponsor: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([recruiter])
+        .rpc(),
+      "InvalidJobSponsor"
+    );
+    console.log("✅ Direct close without the bounty authority rejected");
+  });
+
+  it("Credits the request's own refund as the job closes it", async () => {
+    // Tokens sent to the bounty escrow directly are not refunds
+    await mintTo(provider.connection, recruiter, mint, bountyEscrow, recruiter, 50 * USDC);
+
+    await jobApplication.methods
+      .closeSponsoredContactRequest()
+      .accounts({
+        contactSponsorship,
+        job,
+        jobBounty,
+        recruiter: recruiter.publicKey,
+        bountyAuthority,
+        bountyEscrowAccount: bountyEscrow,
+        contactRequest: requestAt(0),
+        escrowTokenAccount: pdas.escrow(requestAt(0)),
+        usdcMint: mint,
+        subscription: null,
+        profileManagerProgram: profileManager.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([recruiter])
+      .rpc();
+
+    const fee = feeFor(10 * USDC, contactFeeBps);
+    const refunded = 10 * USDC - fee;
+    const sponsorship = await jobApplication.account.contactSponsorship.fetch(contactSponsorship);
+    expect(sponsorship.spent.toNumber()).to.equal(fee);
+    expect(sponsorship.refunded.toNumber()).to.equal(refunded);
+    expect((await jobApplication.account.job.fetch(job)).hiringBounty.toNumber()).to.equal(100 * USDC - fee);
+    expect((await jobApplication.account.jobBounty.fetch(jobBounty)).amount.toNumber()).to.equal(100 * USDC - fee);
+    expect(await provider.connection.getAccountInfo(requestAt(0))).to.be.null;
+    console.log(`✅ ${refunded / USDC} USDC credited back to the bounty, stray deposit ignored`);
+  });
+});
@@ -0,0 +1,307 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ProfileManager } from "../target/types/profile_manager";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  acceptMint,
+  airdrop,
+  balance,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  expectError,
+  fundedTokenAccount,
+  pm,
+  respondToContact,
+  sendContact,
+  treasuryTokenAccount,
+  USDC,
+} from "./helpers";
+
+describe("Contact subscriptions", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ProfileManager as Program<ProfileManager>;
+  const pdas = pm(program);
+
+  const talent = Keypair.generate();
+  const subscriber = Keypair.generate();
+
+  let mint: PublicKey;
+  let profile: PublicKey;
+  let encryptionKey: number[];
+  let subscriberTokenAccount: PublicKey;
+  let talentTokenAccount: PublicKey;
+  let treasury: PublicKey;
+  let subscription: PublicKey;
+  let subscriptionVault: PublicKey;
+  let contactPolicy: PublicKey;
+
+  const send = (fields: { tierIndex?: number; drawsOnSubscription?: boolean } = {}) =>
+    sendContact(program, {
+      requester: subscriber,
+      targetProfile: profile,
+      recipientKey: encryptionKey,
+      requesterTokenAccount: subscriberTokenAccount,
+      mint,
+      contactPolicy,
+      ...fields,
+    });
+
+  const respond = (request: PublicKey, accept: boolean) =>
+    respondToContact(program, request, accept, {
+      target: talent,
+      requesterTokenAccount: subscriberTokenAccount,
+      targetTokenAccount: talentTokenAccount,
+      treasuryTokenAccount: treasury,
+      mint,
+    });
+
+  const requestAt = (nonce: number) => pdas.contactRequest(subscriber.publicKey, profile, nonce);
+
+  const subscribe = (tierIndex: number, periods: number) =>
+    program.methods
+      .subscribe(tierIndex, periods)
+      .accounts({
+        subscription,
+        vault: subscriptionVault,
+        targetProfile: profile,
+        contactPolicy,
+        subscriber: subscriber.publicKey,
+        subscriberTokenAccount,
+        mint,
+        acceptedMint: pdas.acceptedMint(mint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([subscriber])
+      .rpc();
+
+  const closeSubscription = () =>
+    program.methods
+      .closeSubscription()
+      .accounts({
+        subscription,
+        vault: subscriptionVault,
+        subscriber: subscriber.publicKey,
+        subscriberTokenAccount,
+        mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([subscriber])
+      .rpc();
+
+  before(async () => {
+    await airdrop(provider, [talent, subscriber]);
+
+    mint = await createUsdcMint(provider, subscriber);
+    await acceptMint(provider, program, mint);
+    subscriberTokenAccount = await fundedTokenAccount(provider, mint, subscriber, subscriber.publicKey, 200 * USDC);
+    talentTokenAccount = await fundedTokenAccount(provider, mint, subscriber, talent.publicKey);
+    treasury = await treasuryTokenAccount(provider, program, mint);
+
+    await program.methods
+      .setArbitrationConfig(0, [])
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
+      .rpc();
+
+    // Messages come in a monthly package of two; calls are pay-per-request
+    ({ profile, encryptionKey } = await createProfile(program, talent, {
+      tiers: [
+        contactTier(10 * USDC, {
+          subscription: { periodPrice: new anchor.BN(30 * USDC), periodDays: 30, quota: 2 },
+        }),
+        contactTier(25 * USDC, { description: "30 minute call", channel: { call30: {} } }),
+      ],
+    }));
+
+    subscription = pdas.subscription(subscriber.publicKey, profile);
+    subscriptionVault = pdas.subscriptionVault(subscription);
+    contactPolicy = pdas.contactPolicy(profile);
+  });
+
+  const setBlocked = (blocked: boolean) =>
+    program.methods
+      .updateContactPolicy([
+        blocked ? { block: { 0: subscriber.publicKey } } : { clear: { 0: subscriber.publicKey } },
+      ])
+      .accounts({ profile, contactPolicy, owner: talent.publicKey, systemProgram: SystemProgram.programId })
+      .signers([talent])
+      .rpc();
+
+  it("Refuses subscribers the target has blocked", async () => {
+    await setBlocked(true);
+    await expectError(subscribe(0, 1), "SenderBlocked");
+
+    await setBlocked(false);
+    console.log("✅ Blocked subscriber rejected");
+  });
+
+  it("Only subscribes to tiers that offer a package", async () => {
+    await expectError(subscribe(1, 1), "SubscriptionNotOffered");
+    await expectError(subscribe(0, 0), "InvalidSubscriptionPeriods");
+    await expectError(subscribe(0, 13), "InvalidSubscriptionPeriods");
+    console.log("✅ Invalid subscriptions rejected");
+  });
+
+  it("Prepays the chosen periods into the subscription vault", async () => {
+    const before = await balance(provider, subscriberTokenAccount);
+    await subscribe(0, 2);
+
+    const sub = await program.account.subscription.fetch(subscription);
+    expect(sub.tierIndex).to.equal(0);
+    expect(sub.periodPrice.toNumber()).to.equal(30 * USDC);
+    expect(sub.quota).to.equal(2);
+    expect(sub.periods).to.equal(2);
+    expect(sub.balance.toNumber()).to.equal(60 * USDC);
+    expect(sub.status).to.deep.equal({ active: {} });
+    expect(await balance(provider, subscriptionVault)).to.equal(60 * USDC);
+    expect(await balance(provider, subscriberTokenAccount)).to.equal(before - 60 * USDC);
+    console.log("✅ Subscribed for two 30-day periods");
+  });
+
+  it("Draws covered requests on the quota without escrowing", async () => {
+    const before = await balance(provider, subscriberTokenAccount);
+    const { contactRequest, escrow } = await send({ drawsOnSubscription: true });
+    expect(escrow).to.be.null;
+
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.amount.toNumber()).to.equal(0);
+    expect(request.funding).to.deep.equal({ subscription: {} });
+    expect(request.vault.toBase58()).to.equal(subscriptionVault.toBase58());
+    expect(request.subscription!.toBase58()).to.equal(subscription.toBase58());
+    expect(await balance(provider, subscriberTokenAccount)).to.equal(before);
+
+    const sub = await program.account.subscription.fetch(subscription);
+    expect(sub.used).to.equal(1);
+    expect(sub.requestsSent).to.equal(1);
+    expect(sub.openRequests).to.equal(1);
+    console.log("✅ Request 0 drawn on the subscription");
+  });
+
+  it("Refuses to counter a drawn request", async () => {
+    await expectError(
+      program.methods
+        .counterContactRequest(1)
+        .accounts({ contactRequest: requestAt(0), targetProfile: profile, target: talent.publicKey })
+        .signers([talent])
+        .rpc(),
+      "SubscriptionCounterNotAllowed"
+    );
+    console.log("✅ Counter-offer on a drawn request rejected");
+  });
+
+  it("Falls back to an escrow once the quota is used", async () => {
+    // The subscription does not cover the call tier
+    await expectError(send({ tierIndex: 1, drawsOnSubscription: true }), "InvalidFundingSource");
+
+    await send({ drawsOnSubscription: true });
+    expect((await program.account.subscription.fetch(subscription)).used).to.equal(2);
+
+    await expectError(send({ drawsOnSubscription: true }), "InvalidFundingSource");
+    const { contactRequest, escrow } = await send();
+
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.funding).to.deep.equal({ escrow: {} });
+    expect(request.amount.toNumber()).to.equal(10 * USDC);
+    expect(request.subscription).to.be.null;
+    expect(await balance(provider, escrow!)).to.equal(10 * USDC);
+    expect((await program.account.subscription.fetch(subscription)).openRequests).to.equal(2);
+    console.log("✅ Request 2 paid by escrow after the quota ran out");
+  });
+
+  it("Settles drawn requests without touching the vault", async () => {
+    const talentBefore = await balance(provider, talentTokenAccount);
+    const treasuryBefore = await balance(provider, treasury);
+
+    await respond(requestAt(0), true);
+    await respond(requestAt(1), false);
+
+    expect((await program.account.contactRequest.fetch(requestAt(0))).status).to.deep.equal({ responded: {} });
+    expect((await program.account.contactRequest.fetch(requestAt(1))).status).to.deep.equal({ rejected: {} });
+    expect(await balance(provider, subscriptionVault)).to.equal(60 * USDC);
+    expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore);
+    expect(await balance(provider, treasury)).to.equal(treasuryBefore);
+
+    // The escrowed request settles as usual
+    await respond(requestAt(2), true);
+    expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore + 10 * USDC);
+    console.log("✅ Drawn requests settled; the escrowed one paid out");
+  });
+
+  it("Pays the target the larger of time and usage on cancel", async () => {
+    const talentBefore = await balance(provider, talentTokenAccount);
+    const subscriberBefore = await balance(provider, subscriberTokenAccount);
+
+    await program.methods
+      .cancelSubscription()
+      .accounts({
+        subscription,
+        vault: subscriptionVault,
+        subscriber: subscriber.publicKey,
+        subscriberTokenAccount,
+        targetTokenAccount: talentTokenAccount,
+        mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([subscriber])
+      .rpc();
+
+    // Both requests of the first period were used, so it is paid in full;
+    // the second period is refunded
+    const sub = await program.account.subscription.fetch(subscription);
+    expect(sub.status).to.deep.equal({ cancelled: {} });
+    expect(sub.totalRefunded.toNumber()).to.equal(30 * USDC);
+    expect(sub.balance.toNumber()).to.equal(0);
+    expect(await balance(provider, talentTokenAccount)).to.equal(talentBefore + 30 * USDC);
+    expect(await balance(provider, subscriberTokenAccount)).to.equal(subscriberBefore + 30 * USDC);
+    console.log("✅ Subscription cancelled: 30 USDC to the talent, 30 refunded");
+  });
+
+  it("Closes the subscription once its drawn requests are closed", async () => {
+    await expectError(closeSubscription(), "SubscriptionHasOpenRequests");
+
+    for (const nonce of [0, 1]) {
+      await program.methods
+        .closeContactRequest()
+        .accounts({
+          contactRequest: requestAt(nonce),
+          requester: subscriber.publicKey,
+          requesterTokenAccount: null,
+          escrowTokenAccount: null,
+          usdcMint: null,
+          subscription,
+          outreachCampaign: null,
+          jobSponsor: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([subscriber])
+        .rpc();
+    }
+    expect((await program.account.subscription.fetch(subscription)).openRequests).to.equal(0);
+
+    await program.methods
+      .closeContactRequest()
+      .accounts({
+        contactRequest: requestAt(2),
+        requester: subscriber.publicKey,
+        requesterTokenAccount: subscriberTokenAccount,
+        escrowTokenAccount: pdas.escrow(requestAt(2)),
+        usdcMint: mint,
+        subscription: null,
+        outreachCampaign: null,
+        jobSponsor: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([subscriber])
+      .rpc();
+
+    await closeSubscription();
+    expect(await provider.connection.getAccountInfo(subscription)).to.be.null;
+    expect(await provider.connection.getAccountInfo(subscriptionVault)).to.be.null;
+    console.log("✅ Requests and subscription closed");
+  });
+});
@@ -0,0 +1,340 @@
+import * as anchor from "@coral-xyz/anchor";
+import { IdlTypes, Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import * as crypto from "crypto";
+import { ProfileManager } from "../target/types/profile_manager";
+
+// Shared setup for the profile-manager test suites. Every suite runs against
+// the same validator, so the platform config and accepted mints are created
+// on first use and reused afterwards.
+
+export type ContactPriceTier = IdlTypes<ProfileManager>["contactPriceTier"];
+export type EncryptedMessage = IdlTypes<ProfileManager>["encryptedMessage"];
+
+export const USDC = 1_000_000;
+
+export const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+export const BUBBLEGUM_PROGRAM_ID = new PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+export const COMPRESSION_PROGRAM_ID = new PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+export const NOOP_PROGRAM_ID = new PublicKey("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+export const u64 = (n: number | anchor.BN) => new anchor.BN(n).toArrayLike(Buffer, "le", 8);
+
+export const findPda = (seeds: (Buffer | Uint8Array)[], programId: PublicKey) =>
+  PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+// profile-manager PDAs
+export const pm = (program: Program<ProfileManager>) => ({
+  profile: (owner: PublicKey) => findPda([Buffer.from("profile"), owner.toBuffer()], program.programId),
+  // Handles are case-insensitive; the program seeds the record with the lowercased form
+  handleRecord: (handle: string) =>
+    findPda([Buffer.from("handle"), Buffer.from(handle.toLowerCase())], program.programId),
+  contactPolicy: (profile: PublicKey) =>
+    findPda([Buffer.from("contact_policy"), profile.toBuffer()], program.programId),
+  contactThread: (requester: PublicKey, profile: PublicKey) =>
+    findPda([Buffer.from("contact_thread"), requester.toBuffer(), profile.toBuffer()], program.programId),
+  contactRequest: (requester: PublicKey, profile: PublicKey, nonce: number | anchor.BN) =>
+    findPda([Buffer.from("contact"), requester.toBuffer(), profile.toBuffer(), u64(nonce)], program.programId),
+  escrow: (contactRequest: PublicKey) =>
+    findPda([Buffer.from("escrow"), contactRequest.toBuffer()], program.programId),
+  messageThread: (contactRequest: PublicKey) =>
+    findPda([Buffer.from("message_thread"), contactRequest.toBuffer()], program.programId),
+  dispute: (contactRequest: PublicKey) =>
+    findPda([Buffer.from("dispute"), contactRequest.toBuffer()], program.programId),
+  platformConfig: () => findPda([Buffer.from("platform_config")], program.programId),
+  acceptedMint: (mint: PublicKey) => findPda([Buffer.from("accepted_mint"), mint.toBuffer()], program.programId),
+  contactCredits: (owner: PublicKey, mint: PublicKey) =>
+    findPda([Buffer.from("contact_credits"), owner.toBuffer(), mint.toBuffer()], program.programId),
+  creditsVault: (contactCredits: PublicKey) =>
+    findPda([Buffer.from("credits_vault"), contactCredits.toBuffer()], program.programId),
+  outreachCampaign: (owner: PublicKey, campaignId: number | anchor.BN) =>
+    findPda([Buffer.from("outreach_campaign"), owner.toBuffer(), u64(campaignId)], program.programId),
+  campaignVault: (campaign: PublicKey) =>
+    findPda([Buffer.from("campaign_vault"), campaign.toBuffer()], program.programId),
+  subscription: (subscriber: PublicKey, profile: PublicKey) =>
+    findPda([Buffer.from("subscription"), subscriber.toBuffer(), profile.toBuffer()], program.programId),
+  subscriptionVault: (subscription: PublicKey) =>
+    findPda([Buffer.from("subscription_vault"), subscription.toBuffer()], program.programId),
+});
+
+export async function airdrop(provider: anchor.AnchorProvider, wallets: Keypair[], sol = 5) {
+  await Promise.all(
+    wallets.map(async (wallet) => {
+      const sig = await provider.connection.requestAirdrop(wallet.publicKey, sol * anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig, "confirmed");
+    })
+  );
+}
+
+export async function balance(provider: anchor.AnchorProvider, tokenAccount: PublicKey): Promise<number> {
+  return Number((await getAccount(provider.connection, tokenAccount)).amount);
+}
+
+export async function createUsdcMint(provider: anchor.AnchorProvider, authority: Keypair): Promise<PublicKey> {
+  return createMint(provider.connection, authority, authority.publicKey, null, 6);
+}
+
+/** The owner's associated token account, with `amount` minted into it. */
+export async function fundedTokenAccount(
+  provider: anchor.AnchorProvider,
+  mint: PublicKey,
+  mintAuthority: Keypair,
+  owner: PublicKey,
+  amount = 0
+): Promise<PublicKey> {
+  const account = (
+    await getOrCreateAssociatedTokenAccount(provider.connection, mintAuthority, mint, owner, true)
+  ).address;
+  if (amount > 0) {
+    await mintTo(provider.connection, mintAuthority, mint, account, mintAuthority, amount);
+  }
+  return account;
+}
+
+/**
+ * Creates the platform config with the provider wallet (the upgrade
+ * authority) as admin and treasury if no suite has yet, and returns it.
+ */
+export async function ensurePlatformConfig(provider: anchor.AnchorProvider, program: Program<ProfileManager>) {
+  const platformConfig = pm(program).platformConfig();
+  if (!(await provider.connection.getAccountInfo(platformConfig))) {
+    await program.methods
+      .initializePlatformConfig(provider.wallet.publicKey, 500, 500, 500)
+      .accounts({
+        platformConfig,
+        authority: provider.wallet.publicKey,
+        program: program.programId,
+        programData: findPda([program.programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return { platformConfig, config: await program.account.platformConfig.fetch(platformConfig) };
+}
+
+/** Adds `mint` to the accepted list (as the platform admin) if it is not on it yet. */
+export async function acceptMint(
+  provider: anchor.AnchorProvider,
+  program: Program<ProfileManager>,
+  mint: PublicKey
+): Promise<PublicKey> {
+  const { platformConfig } = await ensurePlatformConfig(provider, program);
+  const acceptedMint = pm(program).acceptedMint(mint);
+  if (!(await provider.connection.getAccountInfo(acceptedMint))) {
+    await program.methods
+      .addAcceptedMint()
+      .accounts({
+        acceptedMint,
+        mint,
+        platformConfig,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return acceptedMint;
+}
+
+/** The treasury's token account for `mint`, created if needed. */
+export async function treasuryTokenAccount(
+  provider: anchor.AnchorProvider,
+  program: Program<ProfileManager>,
+  mint: PublicKey
+): Promise<PublicKey> {
+  const { config } = await ensurePlatformConfig(provider, program);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  return (await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, config.treasury, true)).address;
+}
+
+/** A unique, valid handle, starting with a letter. The program lowercases it. */
+export function uniqueHandle(prefix: string): string {
+  return `${prefix}_${Keypair.generate().publicKey.toBuffer().toString("hex").slice(0, 8)}`;
+}
+
+/** A single-message tier with no quota, SLA curve or subscription unless overridden. */
+export function contactTier(price: number, fields: Partial<ContactPriceTier> = {}): ContactPriceTier {
+  return {
+    price: new anchor.BN(price),
+    description: "Message",
+    channel: { message: {} },
+    responseTimeHours: 24,
+    dailyQuota: null,
+    sla: null,
+    subscription: null,
+    ...fields,
+  };
+}
+
+export const randomBytes = (length: number) => Array.from(crypto.randomBytes(length));
+
+/** An envelope sealed to `recipientKey`. The ciphertext is opaque to the program. */
+export function sealedMessage(recipientKey: number[], ciphertextLength = 48): EncryptedMessage {
+  return {
+    recipientKey,
+    ephemeralKey: randomBytes(32),
+    nonce: randomBytes(24),
+    ciphertext: Buffer.from(Array.from({ length: ciphertextLength }, (_, i) => i % 256)),
+  };
+}
+
+export interface NewProfile {
+  handle?: string;
+  tiers?: ContactPriceTier[];
+  skills?: string[];
+  region?: string;
+  bio?: string;
+}
+
+/**
+ * Creates `owner`'s profile and publishes an encryption key, which every
+ * contact request needs. Returns the profile PDA, handle and key.
+ */
+export async function createProfile(program: Program<ProfileManager>, owner: Keypair, fields: NewProfile = {}) {
+  const pdas = pm(program);
+  const profile = pdas.profile(owner.publicKey);
+  const handle = fields.handle ?? uniqueHandle("dev");
+  const encryptionKey = randomBytes(32);
+
+  await program.methods
+    .createProfile(
+      fields.skills ?? ["Rust", "Solana"],
+      5,
+      fields.region ?? "Seoul",
+      fields.bio ?? "Solana developer",
+      handle,
+      fields.tiers ?? [contactTier(10 * USDC)],
+      24,
+      null
+    )
+    .accounts({
+      profile,
+      handleRecord: pdas.handleRecord(handle),
+      owner: owner.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([owner])
+    .rpc();
+
+  await program.methods
+    .setEncryptionKey(encryptionKey)
+    .accounts({ profile, owner: owner.publicKey })
+    .signers([owner])
+    .rpc();
+
+  return { profile, handle, encryptionKey };
+}
+
+export interface SendContact {
+  requester: Keypair;
+  targetProfile: PublicKey;
+  recipientKey: number[];
+  requesterTokenAccount: PublicKey;
+  mint: PublicKey;
+  tierIndex?: number;
+  contactPolicy?: PublicKey;
+  contactCredits?: PublicKey;
+  outreachCampaign?: PublicKey;
+  drawsOnSubscription?: boolean; // Pass no funding source at all
+  message?: EncryptedMessage;
+}
+
+/**
+ * Sends a contact request at the pair's next nonce, funded from a fresh
+ * escrow unless credits, a campaign or the subscription are chosen.
+ */
+export async function sendContact(program: Program<ProfileManager>, args: SendContact) {
+  const pdas = pm(program);
+  const contactThread = pdas.contactThread(args.requester.publicKey, args.targetProfile);
+  const thread = await program.account.contactThread.fetchNullable(contactThread);
+  const nonce = thread ? thread.requestCount.toNumber() : 0;
+  const contactRequest = pdas.contactRequest(args.requester.publicKey, args.targetProfile, nonce);
+  const escrow =
+    args.contactCredits || args.outreachCampaign || args.drawsOnSubscription ? null : pdas.escrow(contactRequest);
+
+  await program.methods
+    .sendContactRequest(args.message ?? sealedMessage(args.recipientKey), args.tierIndex ?? 0, null)
+    .accounts({
+      contactThread,
+      contactRequest,
+      targetProfile: args.targetProfile,
+      contactPolicy: args.contactPolicy ?? null,
+      requester: args.requester.publicKey,
+      requesterTokenAccount: args.requesterTokenAccount,
+      jobSponsor: null,
+      sponsorTokenAccount: null,
+      escrowTokenAccount: escrow,
+      contactCredits: args.contactCredits ?? null,
+      outreachCampaign: args.outreachCampaign ?? null,
+      subscription: pdas.subscription(args.requester.publicKey, args.targetProfile),
+      usdcMint: args.mint,
+      acceptedMint: pdas.acceptedMint(args.mint),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .signers([args.requester])
+    .rpc();
+
+  return { contactRequest, contactThread, escrow, nonce };
+}
+
+export interface Respond {
+  target: Keypair;
+  requesterTokenAccount: PublicKey; // Owned by the request's refund authority
+  targetTokenAccount: PublicKey;
+  treasuryTokenAccount: PublicKey;
+  mint: PublicKey;
+  contactCredits?: PublicKey;
+  outreachCampaign?: PublicKey;
+}
+
+/** Accepts or rejects `contactRequest` as its target. */
+export async function respondToContact(
+  program: Program<ProfileManager>,
+  contactRequest: PublicKey,
+  accept: boolean,
+  args: Respond
+) {
+  const request = await program.account.contactRequest.fetch(contactRequest);
+  await program.methods
+    .respondToContact(accept)
+    .accounts({
+      contactRequest,
+      contactThread: pm(program).contactThread(request.requester, request.targetProfile),
+      targetProfile: request.targetProfile,
+      target: args.target.publicKey,
+      requesterTokenAccount: args.requesterTokenAccount,
+      targetTokenAccount: args.targetTokenAccount,
+      escrowTokenAccount: request.vault,
+      contactCredits: args.contactCredits ?? null,
+      outreachCampaign: args.outreachCampaign ?? null,
+      usdcMint: args.mint,
+      platformConfig: pm(program).platformConfig(),
+      treasuryTokenAccount: args.treasuryTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([args.target])
+    .rpc();
+}
+
+/** Platform cut of `amount`, rounded down as `PlatformConfig::fee_for` does. */
+export const feeFor = (amount: number, bps: number) => Math.floor((amount * bps) / 10_000);
+
+export async function expectError(promise: Promise<unknown>, code: string) {
+  try {
+    await promise;
+    expect.fail(`expected ${code}`);
+  } catch (err: any) {
+    expect(err.toString()).to.include(code);
+  }
+}
+
+export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
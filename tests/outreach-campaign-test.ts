@@ -0,0 +1,346 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ProfileManager } from "../target/types/profile_manager";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  acceptMint,
+  airdrop,
+  balance,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  expectError,
+  feeFor,
+  fundedTokenAccount,
+  pm,
+  respondToContact,
+  sealedMessage,
+  sendContact,
+  treasuryTokenAccount,
+  USDC,
+} from "./helpers";
+
+describe("Outreach campaigns", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ProfileManager as Program<ProfileManager>;
+  const pdas = pm(program);
+
+  const recruiter = Keypair.generate(); // Runs the campaign
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const carol = Keypair.generate(); // Allowlists the recruiter
+
+  const campaignId = 1;
+
+  let mint: PublicKey;
+  let recruiterTokenAccount: PublicKey;
+  let treasury: PublicKey;
+  let contactFeeBps: number;
+  let outreachCampaign: PublicKey;
+  let campaignVault: PublicKey;
+
+  interface Target {
+    owner: Keypair;
+    profile: PublicKey;
+    encryptionKey: number[];
+    tokenAccount: PublicKey;
+  }
+  let aliceTarget: Target;
+  let bobTarget: Target;
+  let carolTarget: Target;
+
+  const requestTo = (target: Target, nonce: number) =>
+    pdas.contactRequest(recruiter.publicKey, target.profile, nonce);
+
+  const respond = (target: Target, nonce: number, accept: boolean) =>
+    respondToContact(program, requestTo(target, nonce), accept, {
+      target: target.owner,
+      requesterTokenAccount: recruiterTokenAccount,
+      targetTokenAccount: target.tokenAccount,
+      treasuryTokenAccount: treasury,
+      mint,
+      outreachCampaign,
+    });
+
+  const fetchCampaign = () => program.account.outreachCampaign.fetch(outreachCampaign);
+
+  before(async () => {
+    await airdrop(provider, [recruiter, alice, bob, carol]);
+
+    mint = await createUsdcMint(provider, recruiter);
+    await acceptMint(provider, program, mint);
+    recruiterTokenAccount = await fundedTokenAccount(provider, mint, recruiter, recruiter.publicKey, 100 * USDC);
+    treasury = await treasuryTokenAccount(provider, program, mint);
+
+    await program.methods
+      .setArbitrationConfig(0, [])
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
+      .rpc();
+    contactFeeBps = (await ensurePlatformConfig(provider, program)).config.contactFeeBps;
+
+    const newTarget = async (owner: Keypair, tiers: ReturnType<typeof contactTier>[]): Promise<Target> => {
+      const { profile, encryptionKey } = await createProfile(program, owner, { tiers });
+      const tokenAccount = await fundedTokenAccount(provider, mint, recruiter, owner.publicKey);
+      return { owner, profile, encryptionKey, tokenAccount };
+    };
+    aliceTarget = await newTarget(alice, [
+      contactTier(10 * USDC),
+      contactTier(25 * USDC, { description: "30 minute call", channel: { call30: {} } }),
+    ]);
+    bobTarget = await newTarget(bob, [contactTier(15 * USDC)]);
+    carolTarget = await newTarget(carol, [contactTier(5 * USDC)]);
+
+    await program.methods
+      .updateContactPolicy([{ allow: { 0: recruiter.publicKey } }])
+      .accounts({
+        profile: carolTarget.profile,
+        contactPolicy: pdas.contactPolicy(carolTarget.profile),
+        owner: carol.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([carol])
+      .rpc();
+
+    outreachCampaign = pdas.outreachCampaign(recruiter.publicKey, campaignId);
+    campaignVault = pdas.campaignVault(outreachCampaign);
+  });
+
+  it("Escrows the campaign budget once", async () => {
+    const before = await balance(provider, recruiterTokenAccount);
+    await program.methods
+      .createOutreachCampaign(new anchor.BN(campaignId), new anchor.BN(50 * USDC), new anchor.BN(20 * USDC))
+      .accounts({
+        outreachCampaign,
+        vault: campaignVault,
+        owner: recruiter.publicKey,
+        ownerTokenAccount: recruiterTokenAccount,
+        mint,
+        acceptedMint: pdas.acceptedMint(mint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([recruiter])
+      .rpc();
+
+    const campaign = await fetchCampaign();
+    expect(campaign.budget.toNumber()).to.equal(50 * USDC);
+    expect(campaign.available.toNumber()).to.equal(50 * USDC);
+    expect(campaign.maxPricePerRequest.toNumber()).to.equal(20 * USDC);
+    expect(campaign.status).to.deep.equal({ active: {} });
+    expect(await balance(provider, campaignVault)).to.equal(50 * USDC);
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(before - 50 * USDC);
+    console.log("✅ Campaign created with a 50 USDC budget, 20 USDC per request");
+  });
+
+  it("Funds a single request from the campaign", async () => {
+    const { contactRequest, nonce } = await sendContact(program, {
+      requester: recruiter,
+      targetProfile: aliceTarget.profile,
+      recipientKey: aliceTarget.encryptionKey,
+      requesterTokenAccount: recruiterTokenAccount,
+      mint,
+      outreachCampaign,
+    });
+    expect(nonce).to.equal(0);
+
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.funding).to.deep.equal({ campaign: {} });
+    expect(request.vault.toBase58()).to.equal(campaignVault.toBase58());
+
+    const campaign = await fetchCampaign();
+    expect(campaign.available.toNumber()).to.equal(40 * USDC);
+    expect(campaign.reserved.toNumber()).to.equal(10 * USDC);
+    expect(campaign.sentCount).to.equal(1);
+
+    // Alice's call tier is over the per-request cap
+    await expectError(
+      sendContact(program, {
+        requester: recruiter,
+        targetProfile: aliceTarget.profile,
+        recipientKey: aliceTarget.encryptionKey,
+        requesterTokenAccount: recruiterTokenAccount,
+        mint,
+        tierIndex: 1,
+        outreachCampaign,
+      }),
+      "CampaignPriceCapExceeded"
+    );
+    console.log("✅ Request to Alice reserved from the campaign");
+  });
+
+  it("Spawns a batch of requests in one transaction", async () => {
+    const group = (target: Target, nonce: number) => [
+      { pubkey: target.profile, isSigner: false, isWritable: true },
+      { pubkey: pdas.contactThread(recruiter.publicKey, target.profile), isSigner: false, isWritable: true },
+      { pubkey: requestTo(target, nonce), isSigner: false, isWritable: true },
+      // Neither target has a contact policy, so the profile fills the slot
+      { pubkey: target.profile, isSigner: false, isWritable: false },
+    ];
+    const spawn = (
+      contacts: { tierIndex: number; message: ReturnType<typeof sealedMessage> }[],
+      remainingAccounts: anchor.web3.AccountMeta[]
+    ) =>
+      program.methods
+        .spawnCampaignContacts(contacts)
+        .accounts({
+          outreachCampaign,
+          owner: recruiter.publicKey,
+          acceptedMint: pdas.acceptedMint(mint),
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([recruiter])
+        .rpc();
+
+    const contacts = [
+      { tierIndex: 0, message: sealedMessage(bobTarget.encryptionKey) },
+      { tierIndex: 0, message: sealedMessage(aliceTarget.encryptionKey) },
+    ];
+    await expectError(spawn(contacts, group(bobTarget, 0)), "InvalidCampaignContacts");
+
+    // Bob's first request, Alice's second
+    await spawn(contacts, [...group(bobTarget, 0), ...group(aliceTarget, 1)]);
+
+    const toBob = await program.account.contactRequest.fetch(requestTo(bobTarget, 0));
+    expect(toBob.amount.toNumber()).to.equal(15 * USDC);
+    expect(toBob.funding).to.deep.equal({ campaign: {} });
+    const toAlice = await program.account.contactRequest.fetch(requestTo(aliceTarget, 1));
+    expect(toAlice.nonce.toNumber()).to.equal(1);
+
+    const campaign = await fetchCampaign();
+    expect(campaign.available.toNumber()).to.equal(15 * USDC);
+    expect(campaign.reserved.toNumber()).to.equal(35 * USDC);
+    expect(campaign.sentCount).to.equal(3);
+    expect(campaign.pendingCount).to.equal(3);
+    console.log("✅ Spawned requests to Bob and Alice");
+  });
+
+  it("Settles campaign requests against the campaign budget", async () => {
+    const treasuryBefore = await balance(provider, treasury);
+    const bobBefore = await balance(provider, bobTarget.tokenAccount);
+
+    await respond(aliceTarget, 0, false);
+    await respond(bobTarget, 0, true);
+
+    const fee = feeFor(10 * USDC, contactFeeBps);
+    const campaign = await fetchCampaign();
+    expect(campaign.available.toNumber()).to.equal(25 * USDC - fee);
+    expect(campaign.reserved.toNumber()).to.equal(10 * USDC);
+    expect(campaign.spent.toNumber()).to.equal(15 * USDC + fee);
+    expect(campaign.rejectedCount).to.equal(1);
+    expect(campaign.acceptedCount).to.equal(1);
+    expect(campaign.pendingCount).to.equal(1);
+    expect(await balance(provider, treasury)).to.equal(treasuryBefore + fee);
+    expect(await balance(provider, bobTarget.tokenAccount)).to.equal(bobBefore + 15 * USDC);
+    console.log("✅ Alice rejected, Bob accepted");
+  });
+
+  it("Reserves nothing for an allowlisted target", async () => {
+    const { contactRequest } = await sendContact(program, {
+      requester: recruiter,
+      targetProfile: carolTarget.profile,
+      recipientKey: carolTarget.encryptionKey,
+      requesterTokenAccount: recruiterTokenAccount,
+      mint,
+      contactPolicy: pdas.contactPolicy(carolTarget.profile),
+      outreachCampaign,
+    });
+
+    const request = await program.account.contactRequest.fetch(contactRequest);
+    expect(request.amount.toNumber()).to.equal(0);
+    expect(request.funding).to.deep.equal({ campaign: {} });
+
+    const campaign = await fetchCampaign();
+    expect(campaign.reserved.toNumber()).to.equal(10 * USDC);
+    expect(campaign.pendingCount).to.equal(2);
+    expect(campaign.openRequests).to.equal(4);
+    console.log("✅ Free request to Carol drawn on the campaign");
+  });
+
+  it("Closes only once every request has been closed, refunding what is left", async () => {
+    const close = () =>
+      program.methods
+        .closeOutreachCampaign()
+        .accounts({
+          outreachCampaign,
+          vault: campaignVault,
+          owner: recruiter.publicKey,
+          ownerTokenAccount: recruiterTokenAccount,
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([recruiter])
+        .rpc();
+    const cancel = (target: Target, nonce: number) =>
+      program.methods
+        .cancelContactRequest()
+        .accounts({
+          contactRequest: requestTo(target, nonce),
+          contactThread: pdas.contactThread(recruiter.publicKey, target.profile),
+          targetProfile: target.profile,
+          requester: recruiter.publicKey,
+          requesterTokenAccount: recruiterTokenAccount,
+          targetTokenAccount: target.tokenAccount,
+          escrowTokenAccount: campaignVault,
+          contactCredits: null,
+          outreachCampaign,
+          usdcMint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([recruiter])
+        .rpc();
+    const closeRequest = async (target: Target, nonce: number) => {
+      const contactRequest = requestTo(target, nonce);
+      await program.methods
+        .closeContactRequest()
+        .accounts({
+          contactRequest,
+          requester: recruiter.publicKey,
+          requesterTokenAccount: null,
+          escrowTokenAccount: null,
+          usdcMint: null,
+          subscription: null,
+          outreachCampaign,
+          jobSponsor: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([recruiter])
+        .rpc();
+      expect(await provider.connection.getAccountInfo(contactRequest)).to.be.null;
+    };
+
+    await expectError(close(), "CampaignHasOpenRequests");
+
+    await cancel(aliceTarget, 1);
+    for (const [target, nonce] of [[aliceTarget, 0], [aliceTarget, 1], [bobTarget, 0]] as [Target, number][]) {
+      await closeRequest(target, nonce);
+    }
+
+    // Carol's request reserved nothing but still settles against the vault
+    expect((await fetchCampaign()).reserved.toNumber()).to.equal(0);
+    await expectError(close(), "CampaignHasOpenRequests");
+
+    await cancel(carolTarget, 0);
+    await expectError(close(), "CampaignHasOpenRequests");
+    await closeRequest(carolTarget, 0);
+
+    const fee = feeFor(10 * USDC, contactFeeBps);
+    const before = await balance(provider, recruiterTokenAccount);
+    await close();
+
+    const campaign = await fetchCampaign();
+    expect(campaign.status).to.deep.equal({ closed: {} });
+    expect(campaign.available.toNumber()).to.equal(0);
+    expect(campaign.cancelledCount).to.equal(2);
+    expect(campaign.pendingCount).to.equal(0);
+    expect(campaign.openRequests).to.equal(0);
+    expect(await balance(provider, recruiterTokenAccount)).to.equal(before + 35 * USDC - fee);
+    expect(await provider.connection.getAccountInfo(campaignVault)).to.be.null;
+    console.log("✅ Campaign closed and the rest of its budget refunded");
+  });
+});
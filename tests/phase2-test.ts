@@ -5,91 +5,76 @@ import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
-  createMint,
   getOrCreateAssociatedTokenAccount,
-  mintTo,
 } from "@solana/spl-token";
 import { expect } from "chai";
+import {
+  acceptMint,
+  airdrop,
+  balance,
+  BUBBLEGUM_PROGRAM_ID,
+  COMPRESSION_PROGRAM_ID,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  expectError,
+  findPda,
+  fundedTokenAccount,
+  NOOP_PROGRAM_ID,
+  pm,
+  sendContact,
+  uniqueHandle,
+  USDC,
+} from "./helpers";
 
 describe("Phase 2 Testing - NFT and USDC Payment", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.ProfileManager as Program<ProfileManager>;
+  const pdas = pm(program);
 
   // Test accounts
   const profileOwner = Keypair.generate();
   const requester = Keypair.generate();
+  const handle = uniqueHandle("SolDev"); // Stored lowercased
   let usdcMint: PublicKey;
+  let requesterUsdcAccount: PublicKey;
   let profilePda: PublicKey;
-  let profileBump: number;
+  let encryptionKey: number[];
 
   before(async () => {
-    // Airdrop SOL to test accounts
-    const airdropTx1 = await provider.connection.requestAirdrop(
-      profileOwner.publicKey,
-      2 * anchor.web3.LAMPORTS_PER_SOL
-    );
-    const airdropTx2 = await provider.connection.requestAirdrop(
-      requester.publicKey,
-      2 * anchor.web3.LAMPORTS_PER_SOL
-    );
-
-    await provider.connection.confirmTransaction(airdropTx1);
-    await provider.connection.confirmTransaction(airdropTx2);
+    await airdrop(provider, [profileOwner, requester], 2);
 
     // Create USDC mint for testing
-    usdcMint = await createMint(
-      provider.connection,
-      profileOwner, // payer
-      profileOwner.publicKey, // mint authority
-      null, // freeze authority
-      6 // decimals for USDC
+    usdcMint = await createUsdcMint(provider, profileOwner);
+    await acceptMint(provider, program, usdcMint);
+    requesterUsdcAccount = await fundedTokenAccount(
+      provider, usdcMint, profileOwner, requester.publicKey, 100 * USDC
     );
 
     console.log("USDC Mint created:", usdcMint.toString());
-
-    // Calculate profile PDA
-    [profilePda, profileBump] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), profileOwner.publicKey.toBuffer()],
-      program.programId
-    );
   });
 
   it("Should create a profile successfully", async () => {
-    try {
-      const tx = await program.methods
-        .createProfile(
-          ["JavaScript", "Rust", "Solana"], // skills
-          3, // experience_years
-          "Seoul", // region
-          "Senior Solana Developer", // bio
-          "soldev123", // handle
-          [{ price: new anchor.BN(50 * 1000000), description: "Standard contact" }], // 50 USDC
-          24, // response_time_hours
-          "https://ipfs.io/ipfs/QmTestResumeHash" // resume_link - new parameter for hybrid architecture
-        )
-        .accounts({
-          profile: profilePda,
-          owner: profileOwner.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([profileOwner])
-        .rpc();
-
-      console.log("Profile created successfully. Tx:", tx);
-
-      // Verify profile was created
-      const profile = await program.account.profile.fetch(profilePda);
-      console.log("Fetched Profile:", profile);
-      expect(profile.owner.toString()).to.equal(profileOwner.publicKey.toString());
-      expect(profile.handle).to.equal("soldev123");
-      expect(profile.skills).to.deep.equal(["JavaScript", "Rust", "Solana"]);
-
-    } catch (error) {
-      console.error("Profile creation failed:", error);
-      throw error;
-    }
+    ({ profile: profilePda, encryptionKey } = await createProfile(program, profileOwner, {
+      handle,
+      skills: ["JavaScript", "Rust", "Solana"],
+      bio: "Senior Solana Developer",
+      tiers: [contactTier(50 * USDC, { description: "Standard contact" })], // 50 USDC
+    }));
+
+    // Verify profile was created
+    const profile = await program.account.profile.fetch(profilePda);
+    expect(profile.owner.toString()).to.equal(profileOwner.publicKey.toString());
+    expect(profile.handle).to.equal(handle.toLowerCase());
+    expect(profile.skills).to.deep.equal(["JavaScript", "Rust", "Solana"]);
+
+    // The handle is reserved for this profile, whatever its case
+    const record = await program.account.handleRecord.fetch(pdas.handleRecord(handle));
+    expect(record.handle).to.equal(handle.toLowerCase());
+    expect(record.profile.toString()).to.equal(profilePda.toString());
+    console.log("Profile created with handle", record.handle);
   });
 
   it("Should create NFT for profile successfully", async () => {
@@ -155,7 +140,7 @@ describe("Phase 2 Testing - NFT and USDC Payment", () => {
       const profile = await program.account.profile.fetch(profilePda);
       expect(profile.nftMint?.toString()).to.equal(nftMint.publicKey.toString());
 
-    } catch (error) {
+    } catch (error: any) {
       console.error("NFT creation failed:", error);
       console.error("Error details:", error.logs || error.message);
       // Don't throw - this might fail due to missing Token Metadata program
@@ -163,40 +148,32 @@ describe("Phase 2 Testing - NFT and USDC Payment", () => {
     }
   });
 
-  it("Should test payment system structure", async () => {
-    try {
-      // This test verifies the payment system structure without actual USDC transfer
-      // since we'd need to set up proper USDC tokens on localnet
-
-      // Calculate contact request PDA
-      const [contactRequestPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("contact_request"),
-          requester.publicKey.toBuffer(),
-          profilePda.toBuffer(),
-        ],
-        program.programId
-      );
-
-      // Calculate escrow authority PDA
-      const [escrowAuthority] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow_authority")],
-        program.programId
-      );
-
-      console.log("Payment system PDAs calculated successfully:");
-      console.log("- Contact Request PDA:", contactRequestPda.toString());
-      console.log("- Escrow Authority PDA:", escrowAuthority.toString());
-      console.log("- USDC Mint:", usdcMint.toString());
-
-      // Verify the profile has the correct contact price
-      const profile = await program.account.profile.fetch(profilePda);
-      expect(profile.contactPrices[0].price.toNumber()).to.equal(50 * 1000000); // 50 USDC
-
-    } catch (error) {
-      console.error("Payment system test failed:", error);
-      throw error;
-    }
+  it("Should escrow a USDC contact payment", async () => {
+    // Requests are numbered per requester/profile pair, each with its own escrow
+    const contactRequestPda = pdas.contactRequest(requester.publicKey, profilePda, 0);
+    const escrowPda = pdas.escrow(contactRequestPda);
+
+    const { contactRequest, escrow } = await sendContact(program, {
+      requester,
+      targetProfile: profilePda,
+      recipientKey: encryptionKey,
+      requesterTokenAccount: requesterUsdcAccount,
+      mint: usdcMint,
+    });
+    expect(contactRequest.toString()).to.equal(contactRequestPda.toString());
+    expect(escrow!.toString()).to.equal(escrowPda.toString());
+
+    console.log("Payment system PDAs:");
+    console.log("- Contact Request PDA:", contactRequestPda.toString());
+    console.log("- Escrow PDA:", escrowPda.toString());
+    console.log("- USDC Mint:", usdcMint.toString());
+
+    // The tier's price is held in escrow until the profile owner responds
+    const request = await program.account.contactRequest.fetch(contactRequestPda);
+    expect(request.amount.toNumber()).to.equal(50 * USDC);
+    expect(request.status).to.deep.equal({ pending: {} });
+    expect(await balance(provider, escrowPda)).to.equal(50 * USDC);
+    expect(await balance(provider, requesterUsdcAccount)).to.equal(50 * USDC);
   });
 
   it("Should verify all Phase 2 program structure", async () => {
@@ -209,11 +186,14 @@ describe("Phase 2 Testing - NFT and USDC Payment", () => {
     // Verify Phase 2 instructions exist (including new hybrid architecture)
     const phase2Instructions = [
       "createProfileNft",
-      "processPayment",
-      "completePayment",
-      "refundPayment",
-      "compressResume", // New zk-compression instruction
-      "verifyResumeAccess" // New zk-compression verification
+      "sendContactRequest",
+      "respondToContact",
+      "cancelContactRequest",
+      "handleExpiredContact",
+      "closeContactRequest",
+      "createResumeTree", // zk-compression tree setup
+      "compressResume", // zk-compression instruction
+      "verifyResumeAccess" // zk-compression verification
     ];
 
     for (const instruction of phase2Instructions) {
@@ -223,90 +203,75 @@ describe("Phase 2 Testing - NFT and USDC Payment", () => {
     console.log("✅ All Phase 2 instructions are available in the program");
   });
 
-  it("Should test zk-compressed resume functionality", async () => {
-    try {
-      // Create mock merkle tree accounts
-      const merkleTree = Keypair.generate();
-      const treeConfig = Keypair.generate();
+  it("Should test zk-compressed resume functionality", async function () {
+    // Mock merkle tree account; the profile has not created its resume tree
+    const merkleTree = Keypair.generate();
+    const treeConfig = findPda([merkleTree.publicKey.toBuffer()], BUBBLEGUM_PROGRAM_ID);
 
-      // Mock resume data hash and metadata URI
-      const resumeDataHash = Array.from(Buffer.alloc(32, 1));
-      const metadataUri = "https://ipfs.io/ipfs/QmCompressedResumeMetadata";
+    const metadataUri = "https://ipfs.io/ipfs/QmCompressedResumeMetadata";
 
-      // Test compress_resume instruction
-      const compressTx = await program.methods
-        .compressResume(resumeDataHash, metadataUri)
+    // Proofs are only checked against the profile's own tree
+    await expectError(
+      program.methods
+        .verifyResumeAccess([Array.from(Buffer.alloc(32, 2))], Array.from(Buffer.alloc(32, 3)))
+        .accounts({
+          profile: profilePda,
+          requester: requester.publicKey,
+          merkleTree: merkleTree.publicKey,
+        })
+        .signers([requester])
+        .rpc(),
+      "InvalidMerkleTree"
+    );
+    console.log("✅ Resume verification rejects a foreign tree");
+
+    if (!(await provider.connection.getAccountInfo(BUBBLEGUM_PROGRAM_ID))) {
+      console.log("Compression test skipped - Bubblegum is not deployed on localnet");
+      this.skip();
+    }
+
+    // Compressing needs the tree from create_resume_tree first
+    await expectError(
+      program.methods
+        .compressResume(metadataUri)
         .accounts({
           profile: profilePda,
           owner: profileOwner.publicKey,
           merkleTree: merkleTree.publicKey,
-          treeConfig: treeConfig.publicKey,
+          treeConfig,
+          bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+          compressionProgram: COMPRESSION_PROGRAM_ID,
+          logWrapper: NOOP_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([profileOwner])
-        .rpc();
-
-      console.log("Resume compressed successfully. Tx:", compressTx);
+        .rpc(),
+      "NoResumeTree"
+    );
 
-      // Verify profile was updated with resume data
-      const profile = await program.account.profile.fetch(profilePda);
-      expect(profile.resumeMerkleTree?.toString()).to.equal(merkleTree.publicKey.toString());
-      expect(profile.resumeLeafIndex).to.not.be.null;
-      expect(profile.resumeRootHash).to.not.be.null;
-
-      console.log("✅ Resume compression functionality working correctly");
-
-      // Test verify_resume_access instruction
-      const mockMerkleProof = [Array.from(Buffer.alloc(32, 2))];
-
-      try {
-        const verifyTx = await program.methods
-          .verifyResumeAccess(mockMerkleProof)
-          .accounts({
-            profile: profilePda,
-            requester: requester.publicKey,
-            merkleTree: merkleTree.publicKey,
-          })
-          .signers([requester])
-          .rpc();
-
-        console.log("Resume access verified successfully. Tx:", verifyTx);
-      } catch (error) {
-        // This might fail due to the placeholder implementation
-        console.log("✅ Resume verification instruction exists (placeholder implementation)");
-      }
-
-    } catch (error) {
-      console.error("zk-compression test failed:", error);
-      console.error("Error details:", error.logs || error.message);
-      throw error;
-    }
+    const profile = await program.account.profile.fetch(profilePda);
+    expect(profile.resumeMerkleTree).to.be.null;
+    console.log("✅ Resume compression requires the profile's resume tree");
   });
 
   it("Should verify hybrid architecture data separation", async () => {
-    try {
-      // Fetch the profile and verify hybrid data structure
-      const profile = await program.account.profile.fetch(profilePda);
-
-      // Verify public indexable data exists
-      expect(profile.skills).to.not.be.empty;
-      expect(profile.experienceYears).to.be.greaterThan(0);
-      expect(profile.region).to.not.be.empty;
-      expect(profile.bio).to.not.be.empty;
-      expect(profile.handle).to.not.be.empty;
-      expect(profile.contactPrices).to.not.be.empty;
-      expect(profile.responseTimeHours).to.be.greaterThan(0);
-
-      // Verify private data fields exist (may be null initially)
-      expect(profile.hasOwnProperty('resumeMerkleTree')).to.be.true;
-      expect(profile.hasOwnProperty('resumeLeafIndex')).to.be.true;
-      expect(profile.hasOwnProperty('resumeRootHash')).to.be.true;
-
-      console.log("✅ Hybrid architecture verified - public indexable data and private zk-compressed data fields present");
-
-    } catch (error) {
-      console.error("Hybrid architecture verification failed:", error);
-      throw error;
-    }
+    // Fetch the profile and verify hybrid data structure
+    const profile = await program.account.profile.fetch(profilePda);
+
+    // Verify public indexable data exists
+    expect(profile.skills).to.not.be.empty;
+    expect(profile.experienceYears).to.be.greaterThan(0);
+    expect(profile.region).to.not.be.empty;
+    expect(profile.bio).to.not.be.empty;
+    expect(profile.handle).to.not.be.empty;
+    expect(profile.contactPrices).to.not.be.empty;
+    expect(profile.responseTimeHours).to.be.greaterThan(0);
+
+    // Verify private data fields exist (may be null initially)
+    expect(profile.hasOwnProperty('resumeMerkleTree')).to.be.true;
+    expect(profile.hasOwnProperty('resumeLeafIndex')).to.be.true;
+    expect(profile.hasOwnProperty('resumeRootHash')).to.be.true;
+
+    console.log("✅ Hybrid architecture verified - public indexable data and private zk-compressed data fields present");
   });
-});
\ No newline at end of file
+});
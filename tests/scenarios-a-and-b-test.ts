@@ -3,13 +3,31 @@ import { Program } from "@coral-xyz/anchor";
 import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
 import {
-  getOrCreateAssociatedTokenAccount,
-  createMint,
-  mintTo,
   getAccount,
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
+import {
+  acceptMint,
+  airdrop,
+  BUBBLEGUM_PROGRAM_ID,
+  COMPRESSION_PROGRAM_ID,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  expectError,
+  feeFor,
+  findPda,
+  fundedTokenAccount,
+  NOOP_PROGRAM_ID,
+  pm,
+  respondToContact,
+  sendContact,
+  treasuryTokenAccount,
+  uniqueHandle,
+  USDC,
+} from "./helpers";
 
 // Program types
 import { ProfileManager } from "../target/types/profile_manager";
@@ -23,6 +41,7 @@ describe("End-to-End User Scenarios A & B", () => {
   const profileManager = anchor.workspace.ProfileManager as Program<ProfileManager>;
   const jobApplication = anchor.workspace.JobApplication as Program<JobApplication>;
   const hiringRewards = anchor.workspace.HiringRewards as Program<HiringRewards>;
+  const pdas = pm(profileManager);
 
   const recruiter = Keypair.generate();
   const jobSeeker = Keypair.generate();
@@ -32,24 +51,27 @@ describe("End-to-End User Scenarios A & B", () => {
   let recruiterUsdcAccount: PublicKey;
   let jobSeekerUsdcAccount: PublicKey;
   let referrerUsdcAccount: PublicKey;
+  let treasuryUsdcAccount: PublicKey;
 
   before(async () => {
     console.log("Setting up wallets and USDC mint...");
-    await Promise.all(
-      [recruiter, jobSeeker, referrer].map(async (wallet) => {
-        const sig = await provider.connection.requestAirdrop(wallet.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
-        await provider.connection.confirmTransaction(sig, "confirmed");
-      })
-    );
+    await airdrop(provider, [recruiter, jobSeeker, referrer], 10);
+
+    usdcMint = await createUsdcMint(provider, recruiter);
+    await acceptMint(provider, profileManager, usdcMint);
+
+    recruiterUsdcAccount = await fundedTokenAccount(provider, usdcMint, recruiter, recruiter.publicKey, 1000 * USDC);
+    jobSeekerUsdcAccount = await fundedTokenAccount(provider, usdcMint, recruiter, jobSeeker.publicKey);
+    referrerUsdcAccount = await fundedTokenAccount(provider, usdcMint, recruiter, referrer.publicKey);
+    treasuryUsdcAccount = await treasuryTokenAccount(provider, profileManager, usdcMint);
 
-    usdcMint = await createMint(provider.connection, recruiter, recruiter.publicKey, null, 6);
+    // Contact payouts to the target are held back while disputes are enabled
+    await profileManager.methods
+      .setArbitrationConfig(0, [])
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
+      .rpc();
 
-    recruiterUsdcAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, recruiter, usdcMint, recruiter.publicKey)).address;
-    jobSeekerUsdcAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, jobSeeker, usdcMint, jobSeeker.publicKey)).address;
-    referrerUsdcAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, referrer, usdcMint, referrer.publicKey)).address;
 
-    await mintTo(provider.connection, recruiter, usdcMint, recruiterUsdcAccount, recruiter, 1000 * 1000000);
-    
     console.log(`Recruiter: ${recruiter.publicKey.toBase58()}`);
     console.log(`Job Seeker: ${jobSeeker.publicKey.toBase58()}`);
     console.log(`Referrer: ${referrer.publicKey.toBase58()}`);
@@ -58,152 +80,117 @@ describe("End-to-End User Scenarios A & B", () => {
   });
 
   describe("Scenario A: Outbound Sourcing", () => {
-    const contactPrice = new anchor.BN(10 * 1000000);
+    const contactPrice = new anchor.BN(10 * USDC);
     let jobSeekerProfilePda: PublicKey;
+    let encryptionKey: number[];
     let contactRequestPda: PublicKey;
     let escrowVaultPda: PublicKey;
 
     before(async () => {
-        [jobSeekerProfilePda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("profile"), jobSeeker.publicKey.toBuffer()],
-            profileManager.programId
-        );
-        try {
-            await profileManager.methods
-                .createProfile(
-                    ["Solana", "Rust", "Anchor"], 5, "Seoul, KR",
-                    "Experienced Solana developer looking for new opportunities.",
-                    `user-${jobSeeker.publicKey.toBase58().slice(0, 5)}`,
-                    [{ price: contactPrice, description: "Initial consultation" }], 12,
-                    "https://ipfs.io/ipfs/QmExampleResumeHash" // resume_link parameter added
-                )
-                .accounts({
-                    profile: jobSeekerProfilePda,
-                    owner: jobSeeker.publicKey,
-                    systemProgram: SystemProgram.programId
-                })
-                .signers([jobSeeker]).rpc();
-        } catch (error) {
-            console.error("Profile creation error:", error);
-            if (error.logs) console.error("Error logs:", error.logs);
-            throw error;
-        }
+        ({ profile: jobSeekerProfilePda, encryptionKey } = await createProfile(profileManager, jobSeeker, {
+            handle: uniqueHandle("seeker"),
+            skills: ["Solana", "Rust", "Anchor"],
+            region: "Seoul, KR",
+            bio: "Experienced Solana developer looking for new opportunities.",
+            tiers: [contactTier(contactPrice.toNumber(), { description: "Initial consultation" })],
+        }));
     });
 
     it("A recruiter sends a contact request", async () => {
-        [contactRequestPda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("contact"), recruiter.publicKey.toBuffer(), jobSeekerProfilePda.toBuffer()],
-            profileManager.programId
-        );
-        [escrowVaultPda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("escrow"), contactRequestPda.toBuffer()],
-            profileManager.programId
-        );
-        const [escrowAuthority] = PublicKey.findProgramAddressSync(
-            [Buffer.from("escrow"), contactRequestPda.toBuffer()],
-            profileManager.programId
-        );
-
-        await profileManager.methods
-            .sendContactRequest("Impressed with your profile.", 0)
-            .accounts({
-                contactRequest: contactRequestPda,
-                requester: recruiter.publicKey,
-                targetProfile: jobSeekerProfilePda,
-                requesterTokenAccount: recruiterUsdcAccount,
-                escrowTokenAccount: escrowVaultPda,
-                escrowAuthority: escrowAuthority,
-                usdcMint: usdcMint,
-                tokenProgram: TOKEN_PROGRAM_ID,
-                systemProgram: SystemProgram.programId,
-                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-            })
-            .signers([recruiter]).rpc();
+        ({ contactRequest: contactRequestPda } = await sendContact(profileManager, {
+            requester: recruiter,
+            targetProfile: jobSeekerProfilePda,
+            recipientKey: encryptionKey,
+            requesterTokenAccount: recruiterUsdcAccount,
+            mint: usdcMint,
+        }));
+        escrowVaultPda = pdas.escrow(contactRequestPda);
 
         const escrowBalance = (await getAccount(provider.connection, escrowVaultPda)).amount;
         expect(Number(escrowBalance)).to.equal(Number(contactPrice));
-        console.log(`  ✅ Recruiter sent contact request, ${contactPrice.toNumber() / 1000000} USDC now in escrow.`);
+        console.log(`  ✅ Recruiter sent contact request, ${contactPrice.toNumber() / USDC} USDC now in escrow.`);
     });
 
-    it("The job seeker responds, and the recruiter is refunded", async () => {
-        const recruiterBalanceBefore = (await getAccount(provider.connection, recruiterUsdcAccount)).amount;
-        const [escrowAuthority] = PublicKey.findProgramAddressSync(
-            [Buffer.from("escrow"), contactRequestPda.toBuffer()],
-            profileManager.programId
-        );
+    it("The job seeker accepts, and is paid the contact fee", async () => {
+        const jobSeekerBalanceBefore = (await getAccount(provider.connection, jobSeekerUsdcAccount)).amount;
 
-        await profileManager.methods
-            .respondToContact(false)
-            .accounts({
-                contactRequest: contactRequestPda,
-                targetProfile: jobSeekerProfilePda,
-                target: jobSeeker.publicKey,
-                requesterTokenAccount: recruiterUsdcAccount,
-                targetTokenAccount: jobSeekerUsdcAccount,
-                escrowTokenAccount: escrowVaultPda,
-                escrowAuthority: escrowAuthority,
-                tokenProgram: TOKEN_PROGRAM_ID,
-            })
-            .signers([jobSeeker]).rpc();
+        await respondToContact(profileManager, contactRequestPda, true, {
+            target: jobSeeker,
+            requesterTokenAccount: recruiterUsdcAccount,
+            targetTokenAccount: jobSeekerUsdcAccount,
+            treasuryTokenAccount: treasuryUsdcAccount,
+            mint: usdcMint,
+        });
 
-        const recruiterBalanceAfter = (await getAccount(provider.connection, recruiterUsdcAccount)).amount;
-        expect(Number(recruiterBalanceAfter)).to.equal(Number(recruiterBalanceBefore) + Number(contactPrice));
-        console.log(`  ✅ Job Seeker responded. Recruiter was refunded.`);
+        // A timely response earns the full price, without a platform fee
+        const jobSeekerBalanceAfter = (await getAccount(provider.connection, jobSeekerUsdcAccount)).amount;
+        expect(Number(jobSeekerBalanceAfter)).to.equal(Number(jobSeekerBalanceBefore) + Number(contactPrice));
+        const request = await profileManager.account.contactRequest.fetch(contactRequestPda);
+        expect(request.status).to.deep.equal({ responded: {} });
+        console.log(`  ✅ Job Seeker responded and received ${contactPrice.toNumber() / USDC} USDC.`);
     });
 
-    it("Job seeker compresses their resume using zk-compression", async () => {
-        // Create a mock merkle tree account (in real implementation, this would be created via mpl-bubblegum)
-        const merkleTree = Keypair.generate();
-        const treeConfig = Keypair.generate();
+    it("Job seeker compresses their resume using zk-compression", async function () {
+        if (!(await provider.connection.getAccountInfo(BUBBLEGUM_PROGRAM_ID))) {
+            console.log(`  Compression test skipped - Bubblegum is not deployed on localnet`);
+            this.skip();
+        }
 
-        // Mock resume data hash
-        const resumeDataHash = Array.from(Buffer.alloc(32, 1));
+        // Without create_resume_tree the profile has no tree to compress into
+        const merkleTree = Keypair.generate();
         const metadataUri = "https://ipfs.io/ipfs/QmCompressedResumeMetadata";
 
-        await profileManager.methods
-            .compressResume(resumeDataHash, metadataUri)
-            .accounts({
-                profile: jobSeekerProfilePda,
-                owner: jobSeeker.publicKey,
-                merkleTree: merkleTree.publicKey,
-                treeConfig: treeConfig.publicKey,
-                systemProgram: SystemProgram.programId,
-            })
-            .signers([jobSeeker]).rpc();
+        await expectError(
+            profileManager.methods
+                .compressResume(metadataUri)
+                .accounts({
+                    profile: jobSeekerProfilePda,
+                    owner: jobSeeker.publicKey,
+                    merkleTree: merkleTree.publicKey,
+                    treeConfig: findPda([merkleTree.publicKey.toBuffer()], BUBBLEGUM_PROGRAM_ID),
+                    bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+                    compressionProgram: COMPRESSION_PROGRAM_ID,
+                    logWrapper: NOOP_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([jobSeeker]).rpc(),
+            "NoResumeTree"
+        );
 
-        console.log(`  ✅ Resume compressed successfully using zk-compression technology.`);
+        console.log(`  ✅ Resume compression requires the profile's resume tree.`);
     });
 
     it("Recruiter verifies access to compressed resume", async () => {
-        // Mock merkle tree account and proof
+        // Proofs are only checked against the profile's own tree
         const merkleTree = Keypair.generate();
         const mockMerkleProof = [Array.from(Buffer.alloc(32, 2))];
+        const mockLeaf = Array.from(Buffer.alloc(32, 3));
 
-        try {
-            const result = await profileManager.methods
-                .verifyResumeAccess(mockMerkleProof)
+        await expectError(
+            profileManager.methods
+                .verifyResumeAccess(mockMerkleProof, mockLeaf)
                 .accounts({
                     profile: jobSeekerProfilePda,
                     requester: recruiter.publicKey,
                     merkleTree: merkleTree.publicKey,
                 })
-                .signers([recruiter]).rpc();
+                .signers([recruiter]).rpc(),
+            "InvalidMerkleTree"
+        );
 
-            console.log(`  ✅ Resume access verified. Recruiter can now view private resume data.`);
-        } catch (error) {
-            // This is expected to work in the placeholder implementation
-            console.log(`  ✅ Resume verification system is working (placeholder implementation).`);
-        }
+        console.log(`  ✅ Resume verification rejects a tree the profile does not own.`);
     });
   });
 
   describe("Scenario B: Inbound Application with Referral", () => {
     let rewardPoolPda: PublicKey, jobPda: PublicKey, applicationPda: PublicKey, referralPda: PublicKey, jobSeekerProfilePda: PublicKey, rewardVault: PublicKey;
     const jobId = new anchor.BN(Date.now());
-    const bountyAmount = new anchor.BN(500 * 1000000);
+    const bountyAmount = new anchor.BN(500 * USDC);
+    let bountyFeeBps: number;
 
     before(async () => {
-        [jobSeekerProfilePda] = PublicKey.findProgramAddressSync([Buffer.from("profile"), jobSeeker.publicKey.toBuffer()], profileManager.programId);
+        jobSeekerProfilePda = pdas.profile(jobSeeker.publicKey);
+        bountyFeeBps = (await ensurePlatformConfig(provider, profileManager)).config.bountyFeeBps;
     });
 
     it("A recruiter creates a reward pool and a job with a bounty", async () => {
@@ -216,10 +203,12 @@ describe("End-to-End User Scenarios A & B", () => {
                 authority: recruiter.publicKey,
                 rewardPool: rewardPoolPda,
                 usdcMint,
+                acceptedMint: pdas.acceptedMint(usdcMint),
                 systemProgram: SystemProgram.programId,
             })
             .signers([recruiter]).rpc();
-        
+
+
         await hiringRewards.methods.depositToPool(bountyAmount)
             .accounts({
                 authority: recruiter.publicKey,
@@ -227,6 +216,7 @@ describe("End-to-End User Scenarios A & B", () => {
                 rewardVault,
                 sourceTokenAccount: recruiterUsdcAccount,
                 usdcMint,
+                acceptedMint: pdas.acceptedMint(usdcMint),
                 tokenProgram: TOKEN_PROGRAM_ID,
                 associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
@@ -248,11 +238,13 @@ describe("End-to-End User Scenarios A & B", () => {
                 bountyEscrowAccount: bountyEscrowPda,
                 bountyAuthority: bountyAuthorityPda,
                 usdcMint: usdcMint,
+                acceptedMint: pdas.acceptedMint(usdcMint),
                 tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
             }).signers([recruiter]).rpc();
-        
+
+
         console.log(`  ✅ Job with bounty created.`);
     });
 
@@ -284,7 +276,13 @@ describe("End-to-End User Scenarios A & B", () => {
     it("Recruiter hires, triggering rewards", async () => {
         const referrerBalanceBefore = (await getAccount(provider.connection, referrerUsdcAccount)).amount;
         const jobSeekerBalanceBefore = (await getAccount(provider.connection, jobSeekerUsdcAccount)).amount;
-        const expectedReward = bountyAmount.div(new anchor.BN(2));
+        const treasuryBalanceBefore = (await getAccount(provider.connection, treasuryUsdcAccount)).amount;
+
+        // The platform takes its bounty fee first; the rest is split between referee and referrer
+        const platformFee = feeFor(bountyAmount.toNumber(), bountyFeeBps);
+        const payout = bountyAmount.toNumber() - platformFee;
+        const refereeReward = Math.floor(payout / 2);
+        const referrerReward = payout - refereeReward;
 
         await jobApplication.methods
             .hireApplicant(0)
@@ -300,15 +298,19 @@ describe("End-to-End User Scenarios A & B", () => {
                 usdcMint: usdcMint,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 referral: referralPda,
+                platformConfig: pdas.platformConfig(),
+                treasuryTokenAccount: treasuryUsdcAccount,
             })
             .signers([recruiter]).rpc();
 
         const referrerBalanceAfter = (await getAccount(provider.connection, referrerUsdcAccount)).amount;
         const jobSeekerBalanceAfter = (await getAccount(provider.connection, jobSeekerUsdcAccount)).amount;
+        const treasuryBalanceAfter = (await getAccount(provider.connection, treasuryUsdcAccount)).amount;
 
-        expect(Number(referrerBalanceAfter)).to.equal(Number(referrerBalanceBefore) + Number(expectedReward));
-        expect(Number(jobSeekerBalanceAfter)).to.equal(Number(jobSeekerBalanceBefore) + Number(expectedReward));
-        console.log(`  ✅ Applicant hired! Referrer and Job Seeker received ${expectedReward.toNumber() / 1000000} USDC each.`);
+        expect(Number(referrerBalanceAfter)).to.equal(Number(referrerBalanceBefore) + referrerReward);
+        expect(Number(jobSeekerBalanceAfter)).to.equal(Number(jobSeekerBalanceBefore) + refereeReward);
+        expect(Number(treasuryBalanceAfter)).to.equal(Number(treasuryBalanceBefore) + platformFee);
+        console.log(`  ✅ Applicant hired! Referrer and Job Seeker received ${referrerReward / USDC} and ${refereeReward / USDC} USDC.`);
     });
   });
-});
\ No newline at end of file
+});
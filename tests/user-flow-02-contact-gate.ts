@@ -1,21 +1,34 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ProfileManager } from "../target/types/profile_manager";
-import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
+import { getAccount } from "@solana/spl-token";
 import {
-  getOrCreateAssociatedTokenAccount,
-  createMint,
-  mintTo,
-  getAccount,
-  TOKEN_PROGRAM_ID,
-} from "@solana/spl-token";
+  acceptMint,
+  airdrop,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  expectError,
+  feeFor,
+  fundedTokenAccount,
+  pm,
+  respondToContact,
+  sealedMessage,
+  sendContact,
+  treasuryTokenAccount,
+  uniqueHandle,
+  USDC,
+} from "./helpers";
 
 describe("User Flow 02: Contact Gate System", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const profileManager = anchor.workspace.ProfileManager as Program<ProfileManager>;
+  const pdas = pm(profileManager);
 
   // Test accounts
   const talent = Keypair.generate();
@@ -26,12 +39,19 @@ describe("User Flow 02: Contact Gate System", () => {
   let talentUsdcAccount: PublicKey;
   let recruiter1UsdcAccount: PublicKey;
   let recruiter2UsdcAccount: PublicKey;
+  let treasuryUsdcAccount: PublicKey;
   let talentProfilePda: PublicKey;
+  let talentEncryptionKey: number[];
+  let contactFeeBps: number;
 
   // Contact prices for different scenarios
-  const quickConsultPrice = new anchor.BN(25 * 1000000); // 25 USDC
-  const technicalInterviewPrice = new anchor.BN(50 * 1000000); // 50 USDC
-  const collaborationPrice = new anchor.BN(100 * 1000000); // 100 USDC
+  const quickConsultPrice = new anchor.BN(25 * USDC); // 25 USDC
+  const technicalInterviewPrice = new anchor.BN(50 * USDC); // 50 USDC
+  const collaborationPrice = new anchor.BN(100 * USDC); // 100 USDC
+
+  // Each recruiter's first request to the talent
+  const contactRequest1Pda = () => pdas.contactRequest(recruiter1.publicKey, talentProfilePda, 0);
+  const contactRequest2Pda = () => pdas.contactRequest(recruiter2.publicKey, talentProfilePda, 0);
 
   before(async () => {
     console.log("🚀 Starting Contact Gate System Test");
@@ -40,63 +60,50 @@ describe("User Flow 02: Contact Gate System", () => {
     console.log(`Recruiter 2: ${recruiter2.publicKey.toBase58()}`);
 
     // Setup wallets
-    await Promise.all([
-      provider.connection.requestAirdrop(talent.publicKey, 3 * anchor.web3.LAMPORTS_PER_SOL),
-      provider.connection.requestAirdrop(recruiter1.publicKey, 3 * anchor.web3.LAMPORTS_PER_SOL),
-      provider.connection.requestAirdrop(recruiter2.publicKey, 3 * anchor.web3.LAMPORTS_PER_SOL),
-    ].map(async (airdrop) => {
-      const signature = await airdrop;
-      return provider.connection.confirmTransaction(signature, "confirmed");
-    }));
+    await airdrop(provider, [talent, recruiter1, recruiter2], 3);
 
     // Create USDC mint and accounts
-    usdcMint = await createMint(provider.connection, talent, talent.publicKey, null, 6);
-
-    talentUsdcAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, talent, usdcMint, talent.publicKey
-    )).address;
-
-    recruiter1UsdcAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, recruiter1, usdcMint, recruiter1.publicKey
-    )).address;
+    usdcMint = await createUsdcMint(provider, talent);
+    await acceptMint(provider, profileManager, usdcMint);
 
-    recruiter2UsdcAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, recruiter2, usdcMint, recruiter2.publicKey
-    )).address;
+    talentUsdcAccount = await fundedTokenAccount(provider, usdcMint, talent, talent.publicKey);
 
     // Give recruiters USDC for contact payments
-    await mintTo(provider.connection, talent, usdcMint, recruiter1UsdcAccount, talent, 500 * 1000000);
-    await mintTo(provider.connection, talent, usdcMint, recruiter2UsdcAccount, talent, 500 * 1000000);
-
-    // Create talent profile
-    [talentProfilePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), talent.publicKey.toBuffer()],
-      profileManager.programId
-    );
+    recruiter1UsdcAccount = await fundedTokenAccount(provider, usdcMint, talent, recruiter1.publicKey, 500 * USDC);
+    recruiter2UsdcAccount = await fundedTokenAccount(provider, usdcMint, talent, recruiter2.publicKey, 500 * USDC);
+    treasuryUsdcAccount = await treasuryTokenAccount(provider, profileManager, usdcMint);
+    contactFeeBps = (await ensurePlatformConfig(provider, profileManager)).config.contactFeeBps;
 
+    // Payouts are released on accept, without a dispute window
     await profileManager.methods
-      .createProfile(
-        ["Blockchain", "Solana", "Rust", "Smart Contracts"],
-        4,
-        "San Francisco, CA",
-        "Senior blockchain developer with expertise in Solana ecosystem",
-        `blockchain-dev-${talent.publicKey.toBase58().slice(0, 6)}`,
-        [
-          { price: quickConsultPrice, description: "Quick consultation (30min)" },
-          { price: technicalInterviewPrice, description: "Technical interview (1hr)" },
-          { price: collaborationPrice, description: "Project collaboration discussion" }
-        ],
-        12, // 12 hour response time
-        "https://ipfs.io/ipfs/QmTalentResumeExample"
-      )
-      .accounts({
-        profile: talentProfilePda,
-        owner: talent.publicKey,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([talent])
+      .setArbitrationConfig(0, [])
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
       .rpc();
 
+    // Create talent profile
+    ({ profile: talentProfilePda, encryptionKey: talentEncryptionKey } = await createProfile(profileManager, talent, {
+      handle: uniqueHandle("blockchain_dev"),
+      skills: ["Blockchain", "Solana", "Rust", "Smart Contracts"],
+      region: "San Francisco, CA",
+      bio: "Senior blockchain developer with expertise in Solana ecosystem",
+      tiers: [
+        contactTier(quickConsultPrice.toNumber(), {
+          description: "Quick consultation (30min)",
+          channel: { call30: {} },
+          responseTimeHours: 12,
+        }),
+        contactTier(technicalInterviewPrice.toNumber(), {
+          description: "Technical interview (1hr)",
+          channel: { call60: {} },
+          responseTimeHours: 12,
+        }),
+        contactTier(collaborationPrice.toNumber(), {
+          description: "Project collaboration discussion",
+          responseTimeHours: 12,
+        }),
+      ],
+    }));
+
     console.log("✅ Setup complete - Talent profile created with 3-tier pricing");
   });
 
@@ -107,7 +114,7 @@ describe("User Flow 02: Contact Gate System", () => {
       // Verify public indexable data is accessible
       expect(profile.skills).to.include("Solana");
       expect(profile.skills).to.include("Rust");
-      expect(profile.experienceYears).to.equal(4);
+      expect(profile.experienceYears).to.equal(5);
       expect(profile.region).to.include("San Francisco");
       expect(profile.isPublic).to.be.true;
 
@@ -121,12 +128,12 @@ describe("User Flow 02: Contact Gate System", () => {
 
       console.log(`  📋 Contact Pricing Policy for ${profile.handle}:`);
       profile.contactPrices.forEach((tier, index) => {
-        console.log(`    Tier ${index + 1}: ${tier.price.toNumber() / 1000000} USDC - ${tier.description}`);
+        console.log(`    Tier ${index + 1}: ${tier.price.toNumber() / USDC} USDC - ${tier.description}`);
       });
-      console.log(`  ⏰ Expected response time: ${profile.responseTimeHours} hours`);
+      console.log(`  ⏰ Expected response time: ${profile.contactPrices[0].responseTimeHours} hours`);
 
       expect(profile.contactPrices.length).to.equal(3);
-      expect(profile.responseTimeHours).to.equal(12);
+      expect(profile.contactPrices.every((tier) => tier.responseTimeHours === 12)).to.be.true;
     });
 
     it("Should verify private resume data is protected", async () => {
@@ -141,261 +148,171 @@ describe("User Flow 02: Contact Gate System", () => {
   });
 
   describe("Step 2: Contact Request Scenarios", () => {
-    let contactRequest1Pda: PublicKey;
-    let contactRequest2Pda: PublicKey;
-    let escrowVault1Pda: PublicKey;
-    let escrowVault2Pda: PublicKey;
-
     it("Scenario A: Recruiter 1 sends contact request (Quick Consultation)", async () => {
-      [contactRequest1Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("contact"), recruiter1.publicKey.toBuffer(), talentProfilePda.toBuffer()],
-        profileManager.programId
-      );
-
-      [escrowVault1Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest1Pda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowAuthority] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest1Pda.toBuffer()],
-        profileManager.programId
-      );
-
       const recruiter1BalanceBefore = await getAccount(provider.connection, recruiter1UsdcAccount);
 
-      await profileManager.methods
-        .sendContactRequest("Hi! I'm impressed with your Solana expertise. Would love to discuss a potential opportunity.", 0) // Tier 0 - Quick consultation
-        .accounts({
-          contactRequest: contactRequest1Pda,
-          requester: recruiter1.publicKey,
-          targetProfile: talentProfilePda,
-          requesterTokenAccount: recruiter1UsdcAccount,
-          escrowTokenAccount: escrowVault1Pda,
-          escrowAuthority: escrowAuthority,
-          usdcMint: usdcMint,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([recruiter1])
-        .rpc();
+      // The message is sealed to the talent's key; only the talent can read it
+      const { contactRequest, escrow } = await sendContact(profileManager, {
+        requester: recruiter1,
+        targetProfile: talentProfilePda,
+        recipientKey: talentEncryptionKey,
+        requesterTokenAccount: recruiter1UsdcAccount,
+        mint: usdcMint,
+        tierIndex: 0, // Tier 0 - Quick consultation
+        message: sealedMessage(talentEncryptionKey, 96),
+      });
+      expect(contactRequest.toString()).to.equal(contactRequest1Pda().toString());
 
       const recruiter1BalanceAfter = await getAccount(provider.connection, recruiter1UsdcAccount);
-      const escrowBalance = await getAccount(provider.connection, escrowVault1Pda);
+      const escrowBalance = await getAccount(provider.connection, escrow!);
 
       expect(Number(recruiter1BalanceBefore.amount) - Number(recruiter1BalanceAfter.amount)).to.equal(Number(quickConsultPrice));
       expect(Number(escrowBalance.amount)).to.equal(Number(quickConsultPrice));
 
       console.log(`  ✅ Recruiter 1 sent contact request`);
-      console.log(`  💰 ${quickConsultPrice.toNumber() / 1000000} USDC deposited in escrow`);
+      console.log(`  💰 ${quickConsultPrice.toNumber() / USDC} USDC deposited in escrow`);
     });
 
     it("Scenario B: Recruiter 2 sends contact request (Technical Interview)", async () => {
-      [contactRequest2Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("contact"), recruiter2.publicKey.toBuffer(), talentProfilePda.toBuffer()],
-        profileManager.programId
-      );
-
-      [escrowVault2Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest2Pda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowAuthority] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest2Pda.toBuffer()],
-        profileManager.programId
-      );
-
       const recruiter2BalanceBefore = await getAccount(provider.connection, recruiter2UsdcAccount);
 
-      await profileManager.methods
-        .sendContactRequest("We're looking for a senior Solana developer. Your background looks perfect for our team. Let's schedule a technical interview.", 1) // Tier 1 - Technical interview
-        .accounts({
-          contactRequest: contactRequest2Pda,
-          requester: recruiter2.publicKey,
-          targetProfile: talentProfilePda,
-          requesterTokenAccount: recruiter2UsdcAccount,
-          escrowTokenAccount: escrowVault2Pda,
-          escrowAuthority: escrowAuthority,
-          usdcMint: usdcMint,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([recruiter2])
-        .rpc();
+      const { contactRequest, escrow } = await sendContact(profileManager, {
+        requester: recruiter2,
+        targetProfile: talentProfilePda,
+        recipientKey: talentEncryptionKey,
+        requesterTokenAccount: recruiter2UsdcAccount,
+        mint: usdcMint,
+        tierIndex: 1, // Tier 1 - Technical interview
+        message: sealedMessage(talentEncryptionKey, 128),
+      });
+      expect(contactRequest.toString()).to.equal(contactRequest2Pda().toString());
 
       const recruiter2BalanceAfter = await getAccount(provider.connection, recruiter2UsdcAccount);
-      const escrowBalance = await getAccount(provider.connection, escrowVault2Pda);
+      const escrowBalance = await getAccount(provider.connection, escrow!);
 
       expect(Number(recruiter2BalanceBefore.amount) - Number(recruiter2BalanceAfter.amount)).to.equal(Number(technicalInterviewPrice));
       expect(Number(escrowBalance.amount)).to.equal(Number(technicalInterviewPrice));
 
       console.log(`  ✅ Recruiter 2 sent contact request`);
-      console.log(`  💰 ${technicalInterviewPrice.toNumber() / 1000000} USDC deposited in escrow`);
+      console.log(`  💰 ${technicalInterviewPrice.toNumber() / USDC} USDC deposited in escrow`);
     });
   });
 
   describe("Step 3: Response Processing", () => {
-    it("Scenario A: Talent accepts Recruiter 1's request (Refund)", async () => {
+    it("Scenario A: Talent accepts Recruiter 1's request (Payment to talent)", async () => {
       const recruiter1BalanceBefore = await getAccount(provider.connection, recruiter1UsdcAccount);
       const talentBalanceBefore = await getAccount(provider.connection, talentUsdcAccount);
 
-      const [contactRequest1Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("contact"), recruiter1.publicKey.toBuffer(), talentProfilePda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowVault1Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest1Pda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowAuthority] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest1Pda.toBuffer()],
-        profileManager.programId
-      );
-
-      await profileManager.methods
-        .respondToContact(true) // Accept the contact request
-        .accounts({
-          contactRequest: contactRequest1Pda,
-          targetProfile: talentProfilePda,
-          target: talent.publicKey,
-          requesterTokenAccount: recruiter1UsdcAccount,
-          targetTokenAccount: talentUsdcAccount,
-          escrowTokenAccount: escrowVault1Pda,
-          escrowAuthority: escrowAuthority,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([talent])
-        .rpc();
+      await respondToContact(profileManager, contactRequest1Pda(), true, {
+        target: talent,
+        requesterTokenAccount: recruiter1UsdcAccount,
+        targetTokenAccount: talentUsdcAccount,
+        treasuryTokenAccount: treasuryUsdcAccount,
+        mint: usdcMint,
+      });
 
       const recruiter1BalanceAfter = await getAccount(provider.connection, recruiter1UsdcAccount);
       const talentBalanceAfter = await getAccount(provider.connection, talentUsdcAccount);
 
-      // Recruiter should get full refund when accepted
-      expect(Number(recruiter1BalanceAfter.amount)).to.equal(Number(recruiter1BalanceBefore.amount) + Number(quickConsultPrice));
-      // Talent balance should remain unchanged (no payment for accepted requests)
-      expect(Number(talentBalanceAfter.amount)).to.equal(Number(talentBalanceBefore.amount));
+      // A timely response earns the talent the full price, without a platform fee
+      expect(Number(talentBalanceAfter.amount)).to.equal(Number(talentBalanceBefore.amount) + Number(quickConsultPrice));
+      expect(Number(recruiter1BalanceAfter.amount)).to.equal(Number(recruiter1BalanceBefore.amount));
+
+      const request = await profileManager.account.contactRequest.fetch(contactRequest1Pda());
+      expect(request.status).to.deep.equal({ responded: {} });
 
       console.log(`  ✅ Talent accepted Recruiter 1's request`);
-      console.log(`  💰 Recruiter 1 received full refund: ${quickConsultPrice.toNumber() / 1000000} USDC`);
+      console.log(`  💰 Talent received: ${quickConsultPrice.toNumber() / USDC} USDC`);
       console.log(`  🤝 Communication channel established`);
     });
 
-    it("Scenario B: Talent rejects Recruiter 2's request (Payment to talent)", async () => {
+    it("Scenario B: Talent rejects Recruiter 2's request (Refund less platform fee)", async () => {
       const recruiter2BalanceBefore = await getAccount(provider.connection, recruiter2UsdcAccount);
       const talentBalanceBefore = await getAccount(provider.connection, talentUsdcAccount);
-
-      const [contactRequest2Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("contact"), recruiter2.publicKey.toBuffer(), talentProfilePda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowVault2Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest2Pda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowAuthority] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequest2Pda.toBuffer()],
-        profileManager.programId
-      );
-
-      await profileManager.methods
-        .respondToContact(false) // Reject the contact request
-        .accounts({
-          contactRequest: contactRequest2Pda,
-          targetProfile: talentProfilePda,
-          target: talent.publicKey,
-          requesterTokenAccount: recruiter2UsdcAccount,
-          targetTokenAccount: talentUsdcAccount,
-          escrowTokenAccount: escrowVault2Pda,
-          escrowAuthority: escrowAuthority,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([talent])
-        .rpc();
+      const treasuryBalanceBefore = await getAccount(provider.connection, treasuryUsdcAccount);
+
+      await respondToContact(profileManager, contactRequest2Pda(), false, {
+        target: talent,
+        requesterTokenAccount: recruiter2UsdcAccount,
+        targetTokenAccount: talentUsdcAccount,
+        treasuryTokenAccount: treasuryUsdcAccount,
+        mint: usdcMint,
+      });
 
       const recruiter2BalanceAfter = await getAccount(provider.connection, recruiter2UsdcAccount);
       const talentBalanceAfter = await getAccount(provider.connection, talentUsdcAccount);
+      const treasuryBalanceAfter = await getAccount(provider.connection, treasuryUsdcAccount);
 
-      // Recruiter should not get refund when rejected
-      expect(Number(recruiter2BalanceAfter.amount)).to.equal(Number(recruiter2BalanceBefore.amount));
-      // Talent should receive the payment for rejected request
-      expect(Number(talentBalanceAfter.amount)).to.equal(Number(talentBalanceBefore.amount) + Number(technicalInterviewPrice));
+      // The platform keeps its fee; the rest goes back to the recruiter
+      const platformFee = feeFor(technicalInterviewPrice.toNumber(), contactFeeBps);
+      expect(Number(recruiter2BalanceAfter.amount)).to.equal(
+        Number(recruiter2BalanceBefore.amount) + technicalInterviewPrice.toNumber() - platformFee
+      );
+      expect(Number(treasuryBalanceAfter.amount)).to.equal(Number(treasuryBalanceBefore.amount) + platformFee);
+      expect(Number(talentBalanceAfter.amount)).to.equal(Number(talentBalanceBefore.amount));
 
       console.log(`  ✅ Talent rejected Recruiter 2's request`);
-      console.log(`  💰 Talent received compensation: ${technicalInterviewPrice.toNumber() / 1000000} USDC`);
+      console.log(`  💰 Recruiter 2 refunded: ${(technicalInterviewPrice.toNumber() - platformFee) / USDC} USDC`);
       console.log(`  🚫 No communication channel established`);
     });
+
+    it("Should not let a request be answered twice", async () => {
+      await expectError(
+        respondToContact(profileManager, contactRequest1Pda(), false, {
+          target: talent,
+          requesterTokenAccount: recruiter1UsdcAccount,
+          targetTokenAccount: talentUsdcAccount,
+          treasuryTokenAccount: treasuryUsdcAccount,
+          mint: usdcMint,
+        }),
+        "ContactAlreadyProcessed"
+      );
+      console.log(`  ✅ Settled requests are final`);
+    });
   });
 
   describe("Step 4: Private Resume Access Control", () => {
-    it("Should grant resume access to accepted recruiters", async () => {
-      // First, talent compresses their resume
+    it("Should only verify resume access against the talent's own tree", async () => {
+      // The talent has not created a resume tree, so any tree is foreign
       const merkleTree = Keypair.generate();
-      const treeConfig = Keypair.generate();
-      const resumeDataHash = Array.from(Buffer.alloc(32, 123));
-      const metadataUri = "https://ipfs.io/ipfs/QmEncryptedTalentResume123";
-
-      await profileManager.methods
-        .compressResume(resumeDataHash, metadataUri)
-        .accounts({
-          profile: talentProfilePda,
-          owner: talent.publicKey,
-          merkleTree: merkleTree.publicKey,
-          treeConfig: treeConfig.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([talent])
-        .rpc();
-
-      console.log(`  ✅ Talent compressed their resume using zk-compression`);
-
-      // Now recruiter 1 (accepted) can verify access
-      const mockMerkleProof = [Array.from(Buffer.alloc(32, 456))];
-
-      try {
-        await profileManager.methods
-          .verifyResumeAccess(mockMerkleProof)
+      const mockMerkleProof = [Array.from(Buffer.alloc(32, 200))];
+      const mockLeaf = Array.from(Buffer.alloc(32, 123));
+
+      await expectError(
+        profileManager.methods
+          .verifyResumeAccess(mockMerkleProof, mockLeaf)
           .accounts({
             profile: talentProfilePda,
             requester: recruiter1.publicKey,
             merkleTree: merkleTree.publicKey,
           })
           .signers([recruiter1])
-          .rpc();
+          .rpc(),
+        "InvalidMerkleTree"
+      );
 
-        console.log(`  ✅ Recruiter 1 (accepted) can access resume data`);
-      } catch (error) {
-        console.log(`  ✅ Resume access verification structure working (placeholder implementation)`);
-      }
+      console.log(`  ✅ Resume access cannot be proven against another tree`);
     });
 
     it("Should verify contact gate effectiveness", async () => {
       // Summary of contact gate system performance
       const talentBalance = await getAccount(provider.connection, talentUsdcAccount);
-      const recruiter1Balance = await getAccount(provider.connection, recruiter1UsdcAccount);
-      const recruiter2Balance = await getAccount(provider.connection, recruiter2UsdcAccount);
 
       console.log(`\n📊 Contact Gate System Results:`);
       console.log(`\n💼 TALENT EARNINGS:`);
-      console.log(`   ✅ From rejected requests: ${Number(talentBalance.amount) / 1000000} USDC`);
+      console.log(`   ✅ From accepted requests: ${Number(talentBalance.amount) / USDC} USDC`);
       console.log(`   ✅ Spam prevention: Effective (all contacts had value)`);
 
       console.log(`\n🎯 RECRUITER OUTCOMES:`);
-      console.log(`   ✅ Recruiter 1 (Accepted): Got refund, established contact`);
-      console.log(`   ❌ Recruiter 2 (Rejected): Paid for talent's time`);
+      console.log(`   ✅ Recruiter 1 (Accepted): Paid for the talent's time, established contact`);
+      console.log(`   ❌ Recruiter 2 (Rejected): Refunded less the platform fee`);
 
       console.log(`\n🔐 PRIVACY PROTECTION:`);
       console.log(`   ✅ Resume data: zk-compressed and access-controlled`);
       console.log(`   ✅ Public data: Searchable and indexable`);
       console.log(`   ✅ Value-based filtering: Working effectively`);
 
-      expect(Number(talentBalance.amount)).to.be.greaterThan(0);
+      expect(Number(talentBalance.amount)).to.equal(quickConsultPrice.toNumber());
     });
   });
 
@@ -403,7 +320,7 @@ describe("User Flow 02: Contact Gate System", () => {
     it("Should demonstrate anti-spam effectiveness", async () => {
       console.log(`\n🛡️  ANTI-SPAM EFFECTIVENESS:`);
       console.log(`   ✅ All contact requests required upfront payment`);
-      console.log(`   ✅ Talent compensated for time spent on rejections`);
+      console.log(`   ✅ Talent paid for every request answered in time`);
       console.log(`   ✅ Quality filter: Only serious recruiters proceed`);
       console.log(`   ✅ Three-tier pricing allows flexible engagement levels`);
     });
@@ -412,7 +329,7 @@ describe("User Flow 02: Contact Gate System", () => {
       console.log(`\n💰 COST EFFICIENCY:`);
       console.log(`   ✅ Transaction costs: <0.01 SOL per contact request`);
       console.log(`   ✅ USDC payments: Instant and transparent`);
-      console.log(`   ✅ No intermediary fees: Direct peer-to-peer value transfer`);
+      console.log(`   ✅ Platform fee only on rejected or expired requests`);
       console.log(`   ✅ Global accessibility: No geographic restrictions`);
     });
 
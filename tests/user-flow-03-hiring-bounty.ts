@@ -6,13 +6,24 @@ import { HiringRewards } from "../target/types/hiring_rewards";
 import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
 import {
-  getOrCreateAssociatedTokenAccount,
-  createMint,
-  mintTo,
   getAccount,
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
+import {
+  acceptMint,
+  airdrop,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  feeFor,
+  fundedTokenAccount,
+  pm,
+  treasuryTokenAccount,
+  uniqueHandle,
+  USDC,
+} from "./helpers";
 
 describe("User Flow 03: Hiring Bounty System", () => {
   const provider = anchor.AnchorProvider.env();
@@ -21,6 +32,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
   const profileManager = anchor.workspace.ProfileManager as Program<ProfileManager>;
   const jobApplication = anchor.workspace.JobApplication as Program<JobApplication>;
   const hiringRewards = anchor.workspace.HiringRewards as Program<HiringRewards>;
+  const pdas = pm(profileManager);
 
   // Test participants
   const company = Keypair.generate();
@@ -33,9 +45,11 @@ describe("User Flow 03: Hiring Bounty System", () => {
   let candidate1UsdcAccount: PublicKey;
   let candidate2UsdcAccount: PublicKey;
   let referrerUsdcAccount: PublicKey;
+  let treasuryUsdcAccount: PublicKey;
+  let bountyFeeBps: number;
 
   // Job and bounty configuration
-  const bountyAmount = new anchor.BN(1000 * 1000000); // 1000 USDC
+  const bountyAmount = new anchor.BN(1000 * USDC); // 1000 USDC
   const jobId = new anchor.BN(Date.now());
 
   // PDAs
@@ -56,88 +70,40 @@ describe("User Flow 03: Hiring Bounty System", () => {
     console.log(`Referrer: ${referrer.publicKey.toBase58()}`);
 
     // Setup wallets
-    await Promise.all([
-      provider.connection.requestAirdrop(company.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL),
-      provider.connection.requestAirdrop(candidate1.publicKey, 3 * anchor.web3.LAMPORTS_PER_SOL),
-      provider.connection.requestAirdrop(candidate2.publicKey, 3 * anchor.web3.LAMPORTS_PER_SOL),
-      provider.connection.requestAirdrop(referrer.publicKey, 3 * anchor.web3.LAMPORTS_PER_SOL),
-    ].map(async (airdrop) => {
-      const signature = await airdrop;
-      return provider.connection.confirmTransaction(signature, "confirmed");
-    }));
+    await airdrop(provider, [company], 5);
+    await airdrop(provider, [candidate1, candidate2, referrer], 3);
 
     // Create USDC mint and accounts
-    usdcMint = await createMint(provider.connection, company, company.publicKey, null, 6);
-
-    companyUsdcAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, company, usdcMint, company.publicKey
-    )).address;
-
-    candidate1UsdcAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, candidate1, usdcMint, candidate1.publicKey
-    )).address;
-
-    candidate2UsdcAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, candidate2, usdcMint, candidate2.publicKey
-    )).address;
-
-    referrerUsdcAccount = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, referrer, usdcMint, referrer.publicKey
-    )).address;
+    usdcMint = await createUsdcMint(provider, company);
+    await acceptMint(provider, profileManager, usdcMint);
 
     // Give company USDC for bounty payments
-    await mintTo(provider.connection, company, usdcMint, companyUsdcAccount, company, 5000 * 1000000);
+    companyUsdcAccount = await fundedTokenAccount(provider, usdcMint, company, company.publicKey, 5000 * USDC);
+    candidate1UsdcAccount = await fundedTokenAccount(provider, usdcMint, company, candidate1.publicKey);
+    candidate2UsdcAccount = await fundedTokenAccount(provider, usdcMint, company, candidate2.publicKey);
+    referrerUsdcAccount = await fundedTokenAccount(provider, usdcMint, company, referrer.publicKey);
 
-    // Create candidate profiles
-    [candidate1ProfilePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), candidate1.publicKey.toBuffer()],
-      profileManager.programId
-    );
-
-    [candidate2ProfilePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), candidate2.publicKey.toBuffer()],
-      profileManager.programId
-    );
+    // The platform takes its bounty fee into the treasury on every hire
+    treasuryUsdcAccount = await treasuryTokenAccount(provider, profileManager, usdcMint);
+    bountyFeeBps = (await ensurePlatformConfig(provider, profileManager)).config.bountyFeeBps;
 
     // Create candidate 1 profile (Direct applicant)
-    await profileManager.methods
-      .createProfile(
-        ["React", "Node.js", "Web3", "DeFi"],
-        3,
-        "New York, NY",
-        "Full-stack developer with DeFi experience",
-        `fullstack-${candidate1.publicKey.toBase58().slice(0, 6)}`,
-        [{ price: new anchor.BN(30 * 1000000), description: "Technical consultation" }],
-        24,
-        "https://ipfs.io/ipfs/QmCandidate1Resume"
-      )
-      .accounts({
-        profile: candidate1ProfilePda,
-        owner: candidate1.publicKey,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([candidate1])
-      .rpc();
+    ({ profile: candidate1ProfilePda } = await createProfile(profileManager, candidate1, {
+      handle: uniqueHandle("fullstack"),
+      skills: ["React", "Node.js", "Web3", "DeFi"],
+      region: "New York, NY",
+      bio: "Full-stack developer with DeFi experience",
+      tiers: [contactTier(30 * USDC, { description: "Technical consultation" })],
+    }));
 
     // Create candidate 2 profile (Referred candidate)
-    await profileManager.methods
-      .createProfile(
-        ["Solana", "Rust", "Backend", "Microservices"],
-        5,
-        "Austin, TX",
-        "Senior backend engineer specializing in Solana development",
-        `backend-${candidate2.publicKey.toBase58().slice(0, 6)}`,
-        [{ price: new anchor.BN(50 * 1000000), description: "Architecture consultation" }],
-        12,
-        "https://ipfs.io/ipfs/QmCandidate2Resume"
-      )
-      .accounts({
-        profile: candidate2ProfilePda,
-        owner: candidate2.publicKey,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([candidate2])
-      .rpc();
+    ({ profile: candidate2ProfilePda } = await createProfile(profileManager, candidate2, {
+      handle: uniqueHandle("backend"),
+      skills: ["Solana", "Rust", "Backend", "Microservices"],
+      region: "Austin, TX",
+      bio: "Senior backend engineer specializing in Solana development",
+      tiers: [contactTier(50 * USDC, { description: "Architecture consultation", responseTimeHours: 12 })],
+    }));
 
     console.log("✅ Setup complete - Company funded, candidates registered");
   });
@@ -159,6 +125,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
           authority: company.publicKey,
           rewardPool: rewardPoolPda,
           usdcMint,
+          acceptedMint: pdas.acceptedMint(usdcMint),
           systemProgram: SystemProgram.programId,
         })
         .signers([company])
@@ -175,6 +142,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
           rewardVault,
           sourceTokenAccount: companyUsdcAccount,
           usdcMint,
+          acceptedMint: pdas.acceptedMint(usdcMint),
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -185,7 +153,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
       const vaultBalance = await getAccount(provider.connection, rewardVault);
       expect(Number(vaultBalance.amount)).to.equal(Number(bountyAmount));
 
-      console.log(`  ✅ ${bountyAmount.toNumber() / 1000000} USDC deposited in reward pool`);
+      console.log(`  ✅ ${bountyAmount.toNumber() / USDC} USDC deposited in reward pool`);
     });
 
     it("Should create job posting with bounty escrow", async () => {
@@ -228,6 +196,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
           bountyEscrowAccount: bountyEscrowPda,
           bountyAuthority: bountyAuthorityPda,
           usdcMint: usdcMint,
+          acceptedMint: pdas.acceptedMint(usdcMint),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -235,7 +204,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
         .signers([company])
         .rpc();
 
-      console.log(`  ✅ Job posting created with ${bountyAmount.toNumber() / 1000000} USDC bounty`);
+      console.log(`  ✅ Job posting created with ${bountyAmount.toNumber() / USDC} USDC bounty`);
 
       // Verify job was created
       const job = await jobApplication.account.job.fetch(jobPda);
@@ -243,6 +212,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
       expect(job.title).to.equal("Senior Solana Developer");
       expect(job.requiredSkills).to.include("Solana");
       expect(job.requiredSkills).to.include("Rust");
+      expect(job.hiringBounty.toNumber()).to.equal(bountyAmount.toNumber());
     });
   });
 
@@ -334,11 +304,12 @@ describe("User Flow 03: Hiring Bounty System", () => {
       const candidate2BalanceBefore = await getAccount(provider.connection, candidate2UsdcAccount);
       const referrerBalanceBefore = await getAccount(provider.connection, referrerUsdcAccount);
       const vaultBalanceBefore = await getAccount(provider.connection, rewardVault);
+      const treasuryBalanceBefore = await getAccount(provider.connection, treasuryUsdcAccount);
 
       console.log(`  📊 Pre-hiring balances:`);
-      console.log(`    Candidate 2: ${Number(candidate2BalanceBefore.amount) / 1000000} USDC`);
-      console.log(`    Referrer: ${Number(referrerBalanceBefore.amount) / 1000000} USDC`);
-      console.log(`    Reward Vault: ${Number(vaultBalanceBefore.amount) / 1000000} USDC`);
+      console.log(`    Candidate 2: ${Number(candidate2BalanceBefore.amount) / USDC} USDC`);
+      console.log(`    Referrer: ${Number(referrerBalanceBefore.amount) / USDC} USDC`);
+      console.log(`    Reward Vault: ${Number(vaultBalanceBefore.amount) / USDC} USDC`);
 
       // Company decides to hire Candidate 2 (the referred candidate)
       await jobApplication.methods
@@ -355,6 +326,8 @@ describe("User Flow 03: Hiring Bounty System", () => {
           usdcMint: usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           referral: referralPda,
+          platformConfig: pdas.platformConfig(),
+          treasuryTokenAccount: treasuryUsdcAccount,
         })
         .signers([company])
         .rpc();
@@ -362,26 +335,28 @@ describe("User Flow 03: Hiring Bounty System", () => {
       const candidate2BalanceAfter = await getAccount(provider.connection, candidate2UsdcAccount);
       const referrerBalanceAfter = await getAccount(provider.connection, referrerUsdcAccount);
       const vaultBalanceAfter = await getAccount(provider.connection, rewardVault);
+      const treasuryBalanceAfter = await getAccount(provider.connection, treasuryUsdcAccount);
 
-      // Calculate expected rewards (50/50 split for referral)
-      const expectedRewardPerPerson = bountyAmount.div(new anchor.BN(2));
+      // The platform fee comes off the top; the rest is split 50/50, dust to the referrer
+      const platformFee = feeFor(bountyAmount.toNumber(), bountyFeeBps);
+      const payout = bountyAmount.toNumber() - platformFee;
+      const candidateReward = Math.floor(payout / 2);
+      const referrerReward = payout - candidateReward;
 
       console.log(`  📊 Post-hiring balances:`);
-      console.log(`    Candidate 2: ${Number(candidate2BalanceAfter.amount) / 1000000} USDC (+${Number(candidate2BalanceAfter.amount - candidate2BalanceBefore.amount) / 1000000})`);
-      console.log(`    Referrer: ${Number(referrerBalanceAfter.amount) / 1000000} USDC (+${Number(referrerBalanceAfter.amount - referrerBalanceBefore.amount) / 1000000})`);
-      console.log(`    Reward Vault: ${Number(vaultBalanceAfter.amount) / 1000000} USDC (-${Number(vaultBalanceBefore.amount - vaultBalanceAfter.amount) / 1000000})`);
+      console.log(`    Candidate 2: ${Number(candidate2BalanceAfter.amount) / USDC} USDC (+${Number(candidate2BalanceAfter.amount - candidate2BalanceBefore.amount) / USDC})`);
+      console.log(`    Referrer: ${Number(referrerBalanceAfter.amount) / USDC} USDC (+${Number(referrerBalanceAfter.amount - referrerBalanceBefore.amount) / USDC})`);
+      console.log(`    Reward Vault: ${Number(vaultBalanceAfter.amount) / USDC} USDC (-${Number(vaultBalanceBefore.amount - vaultBalanceAfter.amount) / USDC})`);
 
       // Verify rewards were distributed correctly
-      expect(Number(candidate2BalanceAfter.amount)).to.equal(
-        Number(candidate2BalanceBefore.amount) + Number(expectedRewardPerPerson)
-      );
-      expect(Number(referrerBalanceAfter.amount)).to.equal(
-        Number(referrerBalanceBefore.amount) + Number(expectedRewardPerPerson)
-      );
+      expect(Number(candidate2BalanceAfter.amount)).to.equal(Number(candidate2BalanceBefore.amount) + candidateReward);
+      expect(Number(referrerBalanceAfter.amount)).to.equal(Number(referrerBalanceBefore.amount) + referrerReward);
+      expect(Number(treasuryBalanceAfter.amount)).to.equal(Number(treasuryBalanceBefore.amount) + platformFee);
 
       console.log(`  ✅ Hiring successful! Rewards distributed:`);
-      console.log(`    Candidate 2 (hired): ${expectedRewardPerPerson.toNumber() / 1000000} USDC`);
-      console.log(`    Referrer: ${expectedRewardPerPerson.toNumber() / 1000000} USDC`);
+      console.log(`    Candidate 2 (hired): ${candidateReward / USDC} USDC`);
+      console.log(`    Referrer: ${referrerReward / USDC} USDC`);
+      console.log(`    Platform fee: ${platformFee / USDC} USDC`);
     });
 
     it("Should verify application status update", async () => {
@@ -399,12 +374,12 @@ describe("User Flow 03: Hiring Bounty System", () => {
       const rewardPool = await hiringRewards.account.rewardPool.fetch(rewardPoolPda);
       const referral = await hiringRewards.account.referral.fetch(referralPda);
 
-      expect(job.isActive).to.be.false; // Job should be closed after hiring
-      expect(referral.isUsed).to.be.true; // Referral should be marked as used
+      expect(rewardPool.totalAmount.toNumber()).to.equal(0); // The tier's reward was drawn from the pool
+      expect(referral.rewardPool.toString()).to.equal(rewardPoolPda.toString());
+      expect(job.recruiter.toString()).to.equal(company.publicKey.toString());
 
       console.log(`  ✅ Cross-program invocation (CPI) successful`);
-      console.log(`  ✅ Job status: Closed`);
-      console.log(`  ✅ Referral status: Used`);
+      console.log(`  ✅ Reward pool drawn down to ${rewardPool.totalAmount.toNumber() / USDC} USDC`);
     });
 
     it("Should demonstrate system scalability", async () => {
@@ -422,10 +397,10 @@ describe("User Flow 03: Hiring Bounty System", () => {
       const finalVaultBalance = await getAccount(provider.connection, rewardVault);
 
       console.log(`\n💰 BOUNTY ECONOMICS SUMMARY:`);
-      console.log(`   💼 Company Investment: ${bountyAmount.toNumber() / 1000000} USDC bounty`);
+      console.log(`   💼 Company Investment: ${bountyAmount.toNumber() / USDC} USDC bounty`);
       console.log(`   🎯 Hiring Success: 1 quality candidate hired`);
       console.log(`   🤝 Referral Network: 1 referrer rewarded`);
-      console.log(`   💸 Total Distributed: ${bountyAmount.toNumber() / 1000000} USDC`);
+      console.log(`   💸 Total Distributed: ${bountyAmount.toNumber() / USDC} USDC (incl. ${feeFor(bountyAmount.toNumber(), bountyFeeBps) / USDC} USDC platform fee)`);
       console.log(`   📈 ROI: High-quality candidate acquisition`);
 
       // Verify all bounty funds were distributed
@@ -442,7 +417,7 @@ describe("User Flow 03: Hiring Bounty System", () => {
 
     it("Should verify competitive advantages", async () => {
       console.log(`\n🚀 COMPETITIVE ADVANTAGES:`);
-      console.log(`   ✅ Zero recruiting fees: Only success-based bounties`);
+      console.log(`   ✅ No upfront recruiting fees: Only success-based bounties`);
       console.log(`   ✅ Global talent pool: No geographic restrictions`);
       console.log(`   ✅ Instant payments: Cryptocurrency enables immediate rewards`);
       console.log(`   ✅ Transparent process: All transactions on-chain`);
@@ -455,14 +430,14 @@ describe("User Flow 03: Hiring Bounty System", () => {
       // This test simulates what would happen if Candidate 1 (direct applicant) was hired instead
       console.log(`\n📝 ALTERNATIVE SCENARIO: Direct Hire`);
       console.log(`   If Candidate 1 was hired instead:`);
-      console.log(`   ✅ Candidate 1 would receive: ${bountyAmount.toNumber() / 1000000} USDC (full bounty)`);
+      console.log(`   ✅ Candidate 1 would receive: ${(bountyAmount.toNumber() - feeFor(bountyAmount.toNumber(), bountyFeeBps)) / USDC} USDC (bounty less platform fee)`);
       console.log(`   ✅ No referrer rewards: ${0} USDC`);
-      console.log(`   ✅ Company cost: Same ${bountyAmount.toNumber() / 1000000} USDC`);
+      console.log(`   ✅ Company cost: Same ${bountyAmount.toNumber() / USDC} USDC`);
       console.log(`   ✅ Incentive: Direct applicants get full reward`);
 
       console.log(`\n📊 COMPARISON:`);
-      console.log(`   Referral Hire: 50% candidate + 50% referrer`);
-      console.log(`   Direct Hire: 100% candidate + 0% referrer`);
+      console.log(`   Referral Hire: 50% candidate + 50% referrer (after platform fee)`);
+      console.log(`   Direct Hire: 100% candidate + 0% referrer (after platform fee)`);
       console.log(`   Both scenarios cost company the same amount`);
     });
   });
@@ -6,13 +6,32 @@ import { HiringRewards } from "../target/types/hiring_rewards";
 import { Keypair, SystemProgram, PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
 import {
-  getOrCreateAssociatedTokenAccount,
-  createMint,
-  mintTo,
   getAccount,
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
+import {
+  acceptMint,
+  airdrop,
+  BUBBLEGUM_PROGRAM_ID,
+  COMPRESSION_PROGRAM_ID,
+  contactTier,
+  createProfile,
+  createUsdcMint,
+  ensurePlatformConfig,
+  expectError,
+  feeFor,
+  findPda,
+  fundedTokenAccount,
+  NOOP_PROGRAM_ID,
+  pm,
+  respondToContact,
+  sealedMessage,
+  sendContact,
+  treasuryTokenAccount,
+  uniqueHandle,
+  USDC,
+} from "./helpers";
 
 describe("User Flow 05: Complete Platform Integration", () => {
   const provider = anchor.AnchorProvider.env();
@@ -21,6 +40,7 @@ describe("User Flow 05: Complete Platform Integration", () => {
   const profileManager = anchor.workspace.ProfileManager as Program<ProfileManager>;
   const jobApplication = anchor.workspace.JobApplication as Program<JobApplication>;
   const hiringRewards = anchor.workspace.HiringRewards as Program<HiringRewards>;
+  const pdas = pm(profileManager);
 
   // Complete ecosystem participants
   const company = Keypair.generate();
@@ -37,11 +57,16 @@ describe("User Flow 05: Complete Platform Integration", () => {
   let talent2UsdcAccount: PublicKey;
   let referrerUsdcAccount: PublicKey;
   let jobSeekerUsdcAccount: PublicKey;
+  let treasuryUsdcAccount: PublicKey;
 
   // Program state variables that need to be accessible across tests
   let rewardPoolPda: PublicKey;
   let jobPda: PublicKey;
   let rewardVault: PublicKey;
+  let talent1ProfilePda: PublicKey;
+  let talent2ProfilePda: PublicKey;
+  let talent1EncryptionKey: number[];
+  let bountyFeeBps: number;
 
   // System state tracking
   let systemStats = {
@@ -65,100 +90,62 @@ describe("User Flow 05: Complete Platform Integration", () => {
 
     // Setup all wallets
     const participants = [company, recruiter, talent1, talent2, referrer, jobSeeker];
-    await Promise.all(participants.map(async (participant) => {
-      const signature = await provider.connection.requestAirdrop(
-        participant.publicKey,
-        5 * anchor.web3.LAMPORTS_PER_SOL
-      );
-      return provider.connection.confirmTransaction(signature, "confirmed");
-    }));
+    await airdrop(provider, participants, 5);
 
     // Create USDC ecosystem
-    usdcMint = await createMint(provider.connection, company, company.publicKey, null, 6);
-
-    // Create token accounts for all participants
-    const tokenAccounts = await Promise.all(participants.map(participant =>
-      getOrCreateAssociatedTokenAccount(
-        provider.connection, participant, usdcMint, participant.publicKey
-      )
-    ));
-
-    [companyUsdcAccount, recruiterUsdcAccount, talent1UsdcAccount,
-     talent2UsdcAccount, referrerUsdcAccount, jobSeekerUsdcAccount] =
-     tokenAccounts.map(account => account.address);
-
-    // Fund the ecosystem with USDC
-    await mintTo(provider.connection, company, usdcMint, companyUsdcAccount, company, 10000 * 1000000); // Company gets 10k USDC
-    await mintTo(provider.connection, company, usdcMint, recruiterUsdcAccount, company, 2000 * 1000000); // Recruiter gets 2k USDC
-    await mintTo(provider.connection, company, usdcMint, jobSeekerUsdcAccount, company, 100 * 1000000);  // Job seeker gets 100 USDC
+    usdcMint = await createUsdcMint(provider, company);
+    await acceptMint(provider, profileManager, usdcMint);
+
+    // Create token accounts for all participants and fund the ecosystem with USDC
+    companyUsdcAccount = await fundedTokenAccount(provider, usdcMint, company, company.publicKey, 10000 * USDC); // Company gets 10k USDC
+    recruiterUsdcAccount = await fundedTokenAccount(provider, usdcMint, company, recruiter.publicKey, 2000 * USDC); // Recruiter gets 2k USDC
+    talent1UsdcAccount = await fundedTokenAccount(provider, usdcMint, company, talent1.publicKey);
+    talent2UsdcAccount = await fundedTokenAccount(provider, usdcMint, company, talent2.publicKey);
+    referrerUsdcAccount = await fundedTokenAccount(provider, usdcMint, company, referrer.publicKey);
+    jobSeekerUsdcAccount = await fundedTokenAccount(provider, usdcMint, company, jobSeeker.publicKey, 100 * USDC); // Job seeker gets 100 USDC
+
+    // Platform fees land in the treasury
+    treasuryUsdcAccount = await treasuryTokenAccount(provider, profileManager, usdcMint);
+    bountyFeeBps = (await ensurePlatformConfig(provider, profileManager)).config.bountyFeeBps;
+
+    // Contact payouts are released on accept, without a dispute window
+    await profileManager.methods
+      .setArbitrationConfig(0, [])
+      .accounts({ platformConfig: pdas.platformConfig(), admin: provider.wallet.publicKey })
+      .rpc();
 
     console.log("✅ Complete ecosystem setup finished");
   });
 
   describe("Act I: Profile Creation & Discovery", () => {
-    let talent1ProfilePda: PublicKey;
-    let talent2ProfilePda: PublicKey;
-
     it("Should create talent profiles with hybrid architecture", async () => {
       // Create Senior Developer Profile (Talent 1)
-      [talent1ProfilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("profile"), talent1.publicKey.toBuffer()],
-        profileManager.programId
-      );
-
-      await profileManager.methods
-        .createProfile(
-          ["Solana", "Rust", "TypeScript", "Smart Contracts", "DeFi"],
-          6, // 6 years experience
-          "San Francisco, CA",
-          "Senior blockchain architect with deep Solana expertise. Led multiple DeFi protocol launches.",
-          `solana-architect-${talent1.publicKey.toBase58().slice(0, 6)}`,
-          [
-            { price: new anchor.BN(100 * 1000000), description: "Quick consultation (1hr)" },
-            { price: new anchor.BN(250 * 1000000), description: "Architecture review (3hr)" },
-            { price: new anchor.BN(500 * 1000000), description: "Full project consultation" }
-          ],
-          8, // 8 hour response time
-          "https://ipfs.io/ipfs/QmSeniorDeveloperResume"
-        )
-        .accounts({
-          profile: talent1ProfilePda,
-          owner: talent1.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([talent1])
-        .rpc();
+      ({ profile: talent1ProfilePda, encryptionKey: talent1EncryptionKey } = await createProfile(profileManager, talent1, {
+        handle: uniqueHandle("solana_architect"),
+        skills: ["Solana", "Rust", "TypeScript", "Smart Contracts", "DeFi"],
+        region: "San Francisco, CA",
+        bio: "Senior blockchain architect with deep Solana expertise. Led multiple DeFi protocol launches.",
+        tiers: [
+          contactTier(100 * USDC, { description: "Quick consultation (1hr)", channel: { call60: {} }, responseTimeHours: 8 }),
+          contactTier(250 * USDC, { description: "Architecture review (3hr)", responseTimeHours: 48 }),
+          contactTier(500 * USDC, { description: "Full project consultation", responseTimeHours: 72 }),
+        ],
+      }));
 
       // Create Junior Developer Profile (Talent 2)
-      [talent2ProfilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("profile"), talent2.publicKey.toBuffer()],
-        profileManager.programId
-      );
-
-      await profileManager.methods
-        .createProfile(
-          ["JavaScript", "React", "Node.js", "Learning Solana"],
-          2, // 2 years experience
-          "Austin, TX",
-          "Passionate full-stack developer transitioning into Web3. Quick learner with strong fundamentals.",
-          `fullstack-junior-${talent2.publicKey.toBase58().slice(0, 6)}`,
-          [
-            { price: new anchor.BN(25 * 1000000), description: "Technical discussion (30min)" },
-            { price: new anchor.BN(50 * 1000000), description: "Pair programming session (1hr)" }
-          ],
-          24, // 24 hour response time
-          "https://ipfs.io/ipfs/QmJuniorDeveloperResume"
-        )
-        .accounts({
-          profile: talent2ProfilePda,
-          owner: talent2.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([talent2])
-        .rpc();
+      ({ profile: talent2ProfilePda } = await createProfile(profileManager, talent2, {
+        handle: uniqueHandle("fullstack_junior"),
+        skills: ["JavaScript", "React", "Node.js", "Learning Solana"],
+        region: "Austin, TX",
+        bio: "Passionate full-stack developer transitioning into Web3. Quick learner with strong fundamentals.",
+        tiers: [
+          contactTier(25 * USDC, { description: "Technical discussion (30min)", channel: { call30: {} } }),
+          contactTier(50 * USDC, { description: "Pair programming session (1hr)", channel: { call60: {} } }),
+        ],
+      }));
 
       console.log("  ✅ Talent profiles created with public indexable data");
-      console.log("  ✅ Private resume data stored with zk-compression placeholders");
+      console.log("  ✅ Handles reserved and encryption keys published");
 
       // Verify profiles are discoverable
       const talent1Profile = await profileManager.account.profile.fetch(talent1ProfilePda);
@@ -170,120 +157,75 @@ describe("User Flow 05: Complete Platform Integration", () => {
       expect(talent2Profile.isPublic).to.be.true;
     });
 
-    it("Should compress private resume data", async () => {
-      // Talent 1 compresses their resume
-      const merkleTree1 = Keypair.generate();
-      const treeConfig1 = Keypair.generate();
-      const resumeDataHash1 = Array.from(Buffer.alloc(32, 111));
-
-      await profileManager.methods
-        .compressResume(resumeDataHash1, "https://ipfs.io/ipfs/QmEncryptedSeniorResume")
-        .accounts({
-          profile: talent1ProfilePda,
-          owner: talent1.publicKey,
-          merkleTree: merkleTree1.publicKey,
-          treeConfig: treeConfig1.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([talent1])
-        .rpc();
-
-      // Talent 2 compresses their resume
-      const merkleTree2 = Keypair.generate();
-      const treeConfig2 = Keypair.generate();
-      const resumeDataHash2 = Array.from(Buffer.alloc(32, 222));
+    it("Should compress private resume data", async function () {
+      if (!(await provider.connection.getAccountInfo(BUBBLEGUM_PROGRAM_ID))) {
+        console.log("  ⚠️  Resume compression skipped (Bubblegum not available on localnet)");
+        this.skip();
+      }
 
-      await profileManager.methods
-        .compressResume(resumeDataHash2, "https://ipfs.io/ipfs/QmEncryptedJuniorResume")
-        .accounts({
-          profile: talent2ProfilePda,
-          owner: talent2.publicKey,
-          merkleTree: merkleTree2.publicKey,
-          treeConfig: treeConfig2.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([talent2])
-        .rpc();
+      // Compression goes into the tree from create_resume_tree; an unregistered tree is refused
+      const merkleTree = Keypair.generate();
+
+      await expectError(
+        profileManager.methods
+          .compressResume("https://ipfs.io/ipfs/QmEncryptedSeniorResume")
+          .accounts({
+            profile: talent1ProfilePda,
+            owner: talent1.publicKey,
+            merkleTree: merkleTree.publicKey,
+            treeConfig: findPda([merkleTree.publicKey.toBuffer()], BUBBLEGUM_PROGRAM_ID),
+            bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+            compressionProgram: COMPRESSION_PROGRAM_ID,
+            logWrapper: NOOP_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([talent1])
+          .rpc(),
+        "NoResumeTree"
+      );
 
-      console.log("  ✅ Private resume data compressed with zk-compression");
+      console.log("  ✅ Private resume data only goes into the talent's own tree");
       console.log("  ✅ Platform cannot access sensitive information");
     });
   });
 
   describe("Act II: Contact Gate & Network Building", () => {
     it("Should execute contact gate workflow", async () => {
-      const talent1ProfilePda = PublicKey.findProgramAddressSync(
-        [Buffer.from("profile"), talent1.publicKey.toBuffer()],
-        profileManager.programId
-      )[0];
-
-      // Recruiter contacts senior talent
-      const [contactRequestPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("contact"), recruiter.publicKey.toBuffer(), talent1ProfilePda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowVaultPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequestPda.toBuffer()],
-        profileManager.programId
-      );
-
-      const [escrowAuthority] = PublicKey.findProgramAddressSync(
-        [Buffer.from("escrow"), contactRequestPda.toBuffer()],
-        profileManager.programId
-      );
-
-      const contactPrice = new anchor.BN(100 * 1000000); // Tier 0 - Quick consultation
-
-      await profileManager.methods
-        .sendContactRequest(
-          "Hi! I represent a leading DeFi protocol. We're looking for a Solana architect for our next product launch. Your experience looks perfect!",
-          0 // Tier 0
-        )
-        .accounts({
-          contactRequest: contactRequestPda,
-          requester: recruiter.publicKey,
-          targetProfile: talent1ProfilePda,
-          requesterTokenAccount: recruiterUsdcAccount,
-          escrowTokenAccount: escrowVaultPda,
-          escrowAuthority: escrowAuthority,
-          usdcMint: usdcMint,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([recruiter])
-        .rpc();
+      const contactPrice = new anchor.BN(100 * USDC); // Tier 0 - Quick consultation
+
+      // Recruiter contacts senior talent with a message sealed to their key
+      const { contactRequest, escrow } = await sendContact(profileManager, {
+        requester: recruiter,
+        targetProfile: talent1ProfilePda,
+        recipientKey: talent1EncryptionKey,
+        requesterTokenAccount: recruiterUsdcAccount,
+        mint: usdcMint,
+        tierIndex: 0,
+        message: sealedMessage(talent1EncryptionKey, 140),
+      });
+      expect(Number((await getAccount(provider.connection, escrow!)).amount)).to.equal(contactPrice.toNumber());
 
       systemStats.totalContacts++;
       systemStats.totalUsdcCirculated = systemStats.totalUsdcCirculated.add(contactPrice);
 
-      // Talent accepts the contact (establishing relationship)
-      await profileManager.methods
-        .respondToContact(true)
-        .accounts({
-          contactRequest: contactRequestPda,
-          targetProfile: talent1ProfilePda,
-          target: talent1.publicKey,
-          requesterTokenAccount: recruiterUsdcAccount,
-          targetTokenAccount: talent1UsdcAccount,
-          escrowTokenAccount: escrowVaultPda,
-          escrowAuthority: escrowAuthority,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([talent1])
-        .rpc();
+      // Talent accepts the contact (establishing relationship) and is paid for their time
+      const talent1BalanceBefore = await getAccount(provider.connection, talent1UsdcAccount);
+      await respondToContact(profileManager, contactRequest, true, {
+        target: talent1,
+        requesterTokenAccount: recruiterUsdcAccount,
+        targetTokenAccount: talent1UsdcAccount,
+        treasuryTokenAccount: treasuryUsdcAccount,
+        mint: usdcMint,
+      });
+      const talent1BalanceAfter = await getAccount(provider.connection, talent1UsdcAccount);
+      expect(Number(talent1BalanceAfter.amount)).to.equal(Number(talent1BalanceBefore.amount) + contactPrice.toNumber());
 
       console.log("  ✅ Contact established between recruiter and senior talent");
-      console.log("  💰 Contact gate processed successfully with refund");
+      console.log(`  💰 Senior talent paid ${contactPrice.toNumber() / USDC} USDC for a timely response`);
     });
   });
 
   describe("Act III: Job Creation & Application Flow", () => {
-    let rewardPoolPda: PublicKey;
-    let jobPda: PublicKey;
-    let rewardVault: PublicKey;
-
     it("Should create comprehensive job posting with bounty", async () => {
       // Create reward pool
       [rewardPoolPda] = PublicKey.findProgramAddressSync(
@@ -293,7 +235,7 @@ describe("User Flow 05: Complete Platform Integration", () => {
 
       rewardVault = await getAssociatedTokenAddress(usdcMint, rewardPoolPda, true);
 
-      const bountyAmount = new anchor.BN(2000 * 1000000); // 2000 USDC bounty
+      const bountyAmount = new anchor.BN(2000 * USDC); // 2000 USDC bounty
 
       await hiringRewards.methods
         .createRewardPool([
@@ -303,6 +245,7 @@ describe("User Flow 05: Complete Platform Integration", () => {
           authority: company.publicKey,
           rewardPool: rewardPoolPda,
           usdcMint,
+          acceptedMint: pdas.acceptedMint(usdcMint),
           systemProgram: SystemProgram.programId,
         })
         .signers([company])
@@ -316,6 +259,7 @@ describe("User Flow 05: Complete Platform Integration", () => {
           rewardVault,
           sourceTokenAccount: companyUsdcAccount,
           usdcMint,
+          acceptedMint: pdas.acceptedMint(usdcMint),
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -364,6 +308,7 @@ describe("User Flow 05: Complete Platform Integration", () => {
           bountyEscrowAccount: bountyEscrowPda,
           bountyAuthority: bountyAuthorityPda,
           usdcMint: usdcMint,
+          acceptedMint: pdas.acceptedMint(usdcMint),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -379,16 +324,6 @@ describe("User Flow 05: Complete Platform Integration", () => {
     });
 
     it("Should process multiple application types", async () => {
-      const talent1ProfilePda = PublicKey.findProgramAddressSync(
-        [Buffer.from("profile"), talent1.publicKey.toBuffer()],
-        profileManager.programId
-      )[0];
-
-      const talent2ProfilePda = PublicKey.findProgramAddressSync(
-        [Buffer.from("profile"), talent2.publicKey.toBuffer()],
-        profileManager.programId
-      )[0];
-
       // Create referral for talent1 (senior developer)
       const [referralPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("referral"), rewardPoolPda.toBuffer(), referrer.publicKey.toBuffer(), talent1.publicKey.toBuffer()],
@@ -464,6 +399,7 @@ describe("User Flow 05: Complete Platform Integration", () => {
     it("Should execute hiring with automatic reward distribution", async () => {
       const talent1BalanceBefore = await getAccount(provider.connection, talent1UsdcAccount);
       const referrerBalanceBefore = await getAccount(provider.connection, referrerUsdcAccount);
+      const treasuryBalanceBefore = await getAccount(provider.connection, treasuryUsdcAccount);
 
       // Ensure PDAs are available for hiring
       if (!jobPda) {
@@ -498,6 +434,8 @@ describe("User Flow 05: Complete Platform Integration", () => {
           usdcMint: usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           referral: referralPda,
+          platformConfig: pdas.platformConfig(),
+          treasuryTokenAccount: treasuryUsdcAccount,
         })
         .signers([company])
         .rpc();
@@ -505,21 +443,25 @@ describe("User Flow 05: Complete Platform Integration", () => {
       const talent1BalanceAfter = await getAccount(provider.connection, talent1UsdcAccount);
       const referrerBalanceAfter = await getAccount(provider.connection, referrerUsdcAccount);
 
-      const expectedReward = new anchor.BN(1000 * 1000000); // 1000 USDC each (50/50 split)
+      const treasuryBalanceAfter = await getAccount(provider.connection, treasuryUsdcAccount);
 
-      expect(Number(talent1BalanceAfter.amount)).to.equal(
-        Number(talent1BalanceBefore.amount) + Number(expectedReward)
-      );
-      expect(Number(referrerBalanceAfter.amount)).to.equal(
-        Number(referrerBalanceBefore.amount) + Number(expectedReward)
-      );
+      // Platform fee first, then a 50/50 split with the odd unit to the referrer
+      const bounty = 2000 * USDC;
+      const platformFee = feeFor(bounty, bountyFeeBps);
+      const talentReward = Math.floor((bounty - platformFee) / 2);
+      const referrerReward = bounty - platformFee - talentReward;
+
+      expect(Number(talent1BalanceAfter.amount)).to.equal(Number(talent1BalanceBefore.amount) + talentReward);
+      expect(Number(referrerBalanceAfter.amount)).to.equal(Number(referrerBalanceBefore.amount) + referrerReward);
+      expect(Number(treasuryBalanceAfter.amount)).to.equal(Number(treasuryBalanceBefore.amount) + platformFee);
 
       systemStats.totalHires++;
 
       console.log("  ✅ Hiring completed with automatic reward distribution");
-      console.log("  💰 Senior developer hired: +1000 USDC");
-      console.log("  💰 Referrer rewarded: +1000 USDC");
-      console.log("  🎯 Total bounty distributed: 2000 USDC");
+      console.log(`  💰 Senior developer hired: +${talentReward / USDC} USDC`);
+      console.log(`  💰 Referrer rewarded: +${referrerReward / USDC} USDC`);
+      console.log(`  🏛️ Platform fee: ${platformFee / USDC} USDC`);
+      console.log(`  🎯 Total bounty distributed: ${bounty / USDC} USDC`);
     });
   });
 
@@ -534,13 +476,13 @@ describe("User Flow 05: Complete Platform Integration", () => {
       console.log("    ✅ Original author receives royalties");
 
       // Simulate the economics
-      const resumePrice = new anchor.BN(50 * 1000000); // 50 USDC
+      const resumePrice = new anchor.BN(50 * USDC); // 50 USDC
       const royaltyRate = 0.7; // 70% to original author, 30% platform fee
       const authorRoyalty = resumePrice.muln(70).divn(100);
 
-      console.log(`    💰 Resume price: ${resumePrice.toNumber() / 1000000} USDC`);
-      console.log(`    👑 Author royalty: ${authorRoyalty.toNumber() / 1000000} USDC (70%)`);
-      console.log(`    🏛️ Platform fee: ${(resumePrice.toNumber() - authorRoyalty.toNumber()) / 1000000} USDC (30%)`);
+      console.log(`    💰 Resume price: ${resumePrice.toNumber() / USDC} USDC`);
+      console.log(`    👑 Author royalty: ${authorRoyalty.toNumber() / USDC} USDC (70%)`);
+      console.log(`    🏛️ Platform fee: ${(resumePrice.toNumber() - authorRoyalty.toNumber()) / USDC} USDC (30%)`);
 
       systemStats.totalResumesSold = 1; // Simulated
     });
@@ -570,13 +512,13 @@ describe("User Flow 05: Complete Platform Integration", () => {
       console.log(`   📄 Total Resume Sales: ${systemStats.totalResumesSold}`);
 
       console.log(`\n💰 ECONOMIC ACTIVITY:`);
-      console.log(`   💵 Total USDC Circulated: ${systemStats.totalUsdcCirculated.toNumber() / 1000000} USDC`);
-      console.log(`   🏢 Company Balance: ${Number(companyBalance.amount) / 1000000} USDC`);
-      console.log(`   👔 Recruiter Balance: ${Number(recruiterBalance.amount) / 1000000} USDC`);
-      console.log(`   💻 Talent 1 Balance: ${Number(talent1Balance.amount) / 1000000} USDC`);
-      console.log(`   🌱 Talent 2 Balance: ${Number(talent2Balance.amount) / 1000000} USDC`);
-      console.log(`   🤝 Referrer Balance: ${Number(referrerBalance.amount) / 1000000} USDC`);
-      console.log(`   🔍 Job Seeker Balance: ${Number(jobSeekerBalance.amount) / 1000000} USDC`);
+      console.log(`   💵 Total USDC Circulated: ${systemStats.totalUsdcCirculated.toNumber() / USDC} USDC`);
+      console.log(`   🏢 Company Balance: ${Number(companyBalance.amount) / USDC} USDC`);
+      console.log(`   👔 Recruiter Balance: ${Number(recruiterBalance.amount) / USDC} USDC`);
+      console.log(`   💻 Talent 1 Balance: ${Number(talent1Balance.amount) / USDC} USDC`);
+      console.log(`   🌱 Talent 2 Balance: ${Number(talent2Balance.amount) / USDC} USDC`);
+      console.log(`   🤝 Referrer Balance: ${Number(referrerBalance.amount) / USDC} USDC`);
+      console.log(`   🔍 Job Seeker Balance: ${Number(jobSeekerBalance.amount) / USDC} USDC`);
 
       console.log(`\n🏗️ TECHNICAL ACHIEVEMENTS:`);
       console.log(`   ✅ Hybrid Architecture: Public searchable + Private zk-compressed`);
@@ -613,8 +555,8 @@ describe("User Flow 05: Complete Platform Integration", () => {
       // Note: totalHires and earnings depend on hiring test success
       // These are validated separately in the hiring test
       if (systemStats.totalHires > 0) {
-        expect(Number(talent1Balance.amount)).to.be.greaterThan(1000 * 1000000); // Talent1 should have earned from hiring
-        expect(Number(referrerBalance.amount)).to.be.greaterThan(1000 * 1000000); // Referrer should have earned from referral
+        expect(Number(talent1Balance.amount)).to.be.greaterThan(100 * USDC); // Talent1 earned the contact fee and the hiring reward
+        expect(Number(referrerBalance.amount)).to.be.greaterThan(0); // Referrer should have earned from referral
       }
     });
 